@@ -0,0 +1,48 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use un::enums::{Temperature, Unit};
+use un::{convert, convert_slice, convert_slice_in_place};
+
+// Sensor-style readings converted °F -> °C, which has both a scale and an offset:
+// a per-element loop over `convert` against the slice functions.
+
+fn readings(len: usize) -> Vec<f64> {
+    (0..len).map(|i| 32.0 + (i % 1000) as f64 * 0.18).collect()
+}
+
+fn bench_convert_slice(c: &mut Criterion) {
+    let from = Unit::Temperature(Temperature::Fahrenheit);
+    let to = Unit::Temperature(Temperature::Celsius);
+    let mut group = c.benchmark_group("fahrenheit_to_celsius");
+    for len in [64, 4096, 1 << 20] {
+        let input = readings(len);
+        let mut output = vec![0.0; len];
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("convert_per_element", len), &input, |b, input| {
+            b.iter(|| {
+                for (out, value) in output.iter_mut().zip(input) {
+                    *out = convert(black_box(*value), &from, &to).unwrap_or(f64::NAN);
+                }
+                black_box(&output);
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("convert_slice", len), &input, |b, input| {
+            b.iter(|| {
+                convert_slice(black_box(input), &from, &to, &mut output).unwrap();
+                black_box(&output);
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("convert_slice_in_place", len), &input, |b, input| {
+            b.iter(|| {
+                output.copy_from_slice(input);
+                convert_slice_in_place(black_box(&mut output), &from, &to).unwrap();
+                black_box(&output);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_convert_slice);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use un::enums::{CustomUnit, Dimension};
+use un::registry::UnitRegistry;
+
+// Unit lookups through the generated perfect-hash tables against the registry's
+// hash maps. Defining a unit takes a registry off the tables, so the second
+// registry answers every lookup from its maps.
+
+// Exact spellings, case-folded ones, prefixed ones and unknown names.
+const NAMES: &[&str] = &[
+    "m", "km", "meters", "ft", "feet", "mi", "lb", "kg", "degF", "°C", "kPa", "psi", "mph", "kWh", "Hz", "mA",
+    "METERS", "Feet", "Pounds", "KILOGRAMS", "Celsius", "Gallons",
+    "kilofeet", "megagram", "mmeter",
+    "furlongs per fortnight", "zzz", "smoot",
+];
+
+fn bench_lookup(c: &mut Criterion) {
+    let tables = UnitRegistry::builtin();
+    let mut maps = UnitRegistry::new();
+    let custom = CustomUnit { name: "smoot".to_string(), dimension: Dimension::Length, factor: 1.7018, offset: 0.0 };
+    maps.define(custom, vec![], 1).unwrap();
+
+    let mut group = c.benchmark_group("lookup");
+    group.bench_function("tables", |b| {
+        b.iter(|| {
+            for name in NAMES {
+                black_box(tables.lookup(black_box(name)));
+            }
+        })
+    });
+    group.bench_function("maps", |b| {
+        b.iter(|| {
+            for name in NAMES {
+                black_box(maps.lookup(black_box(name)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_lookup);
+criterion_main!(benches);
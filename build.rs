@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+include!("src/spelling_hash.rs");
+
+// Generates perfect-hash tables of the built-in units' spellings from the rows of
+// src/builtin.rs, the same table the registry is built from. A unit is identified
+// by its row, which is also its index in the registry. Three tables are written: exact
+// spellings, lowercased ones resolved the way `UnitRegistry::resolve` resolves a
+// case-insensitive match, and the lowercased symbols whose prefix letter has to keep
+// its case, with that letter.
+
+const AMBIGUOUS: u16 = u16::MAX;
+
+// The same lists as `WORDS` and `PREFIX_CASES` in src/registry.rs.
+const WORDS: [&str; 7] = ["a", "r", "rd", "am", "ph", "fl", "de"];
+const PREFIX_CASES: [(char, &str); 2] = [('m', "milli"), ('p', "pico")];
+
+struct Row {
+    spellings: Vec<String>, // Symbol, name, plural, aliases
+}
+
+// Reads a string literal starting at `text`, returning it and the rest of the text.
+fn string_literal(text: &str) -> (String, &str) {
+    let text = text.trim_start();
+    let mut chars = text.strip_prefix('"').unwrap_or_else(|| panic!("expected a string literal at {:?}", text)).char_indices();
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return (value, &text[i + 2..]),
+            '\\' => match chars.next() {
+                Some((_, '"')) => value.push('"'),
+                Some((_, '\\')) => value.push('\\'),
+                other => panic!("unsupported escape {:?} in {:?}", other, text),
+            },
+            c => value.push(c),
+        }
+    }
+    panic!("unterminated string literal at {:?}", text)
+}
+
+// The string literals of a list "[...]" at the start of `text`.
+fn string_list(text: &str) -> (Vec<String>, &str) {
+    let mut rest = text.trim_start().strip_prefix('[').unwrap_or_else(|| panic!("expected a list at {:?}", text));
+    let mut values = vec![];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix(']') {
+            return (values, after);
+        }
+        let (value, after) = string_literal(rest);
+        values.push(value);
+        rest = after.trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest);
+    }
+}
+
+// unit(Unit::Length(Length::Meter), "m", ["meter", "meters"], &["aliases"], ...
+fn parse_row(line: &str) -> Row {
+    let after_unit = line.find("), ").map(|at| &line[at + 3..]).unwrap_or_else(|| panic!("unexpected row {:?}", line));
+    let (symbol, rest) = string_literal(after_unit);
+    let rest = rest.trim_start().strip_prefix(',').unwrap_or(rest);
+    let (names, rest) = string_list(rest);
+    let rest = rest.trim_start().strip_prefix(',').unwrap_or(rest).trim_start();
+    let rest = rest.strip_prefix('&').unwrap_or_else(|| panic!("expected aliases in {:?}", line));
+    let (aliases, _) = string_list(rest);
+    let mut spellings = vec![symbol.clone()];
+    spellings.extend(names);
+    spellings.extend(aliases);
+    Row { spellings }
+}
+
+struct Table {
+    displacements: Vec<u32>,
+    keys: Vec<String>, // Empty for unused slots
+    values: Vec<u16>,
+}
+
+// Hash and displace: keys are grouped into buckets by one hash, then each bucket,
+// largest first, gets the first seed that sends all its keys to free slots.
+fn perfect_hash(entries: &[(String, u16)]) -> Table {
+    let slots = entries.len().next_power_of_two().max(1);
+    let bucket_count = (entries.len() / 4).max(1);
+    let mut buckets: Vec<Vec<usize>> = vec![vec![]; bucket_count];
+    for (i, (key, _)) in entries.iter().enumerate() {
+        buckets[(spelling_hash(key.chars(), 0) % bucket_count as u64) as usize].push(i);
+    }
+    let mut order: Vec<usize> = (0..bucket_count).collect();
+    order.sort_by_key(|&bucket| std::cmp::Reverse(buckets[bucket].len()));
+    let mut table = Table { displacements: vec![0; bucket_count], keys: vec![String::new(); slots], values: vec![0; slots] };
+    let mut taken = vec![false; slots];
+    for bucket in order {
+        if buckets[bucket].is_empty() {
+            continue;
+        }
+        let (seed, placed) = (1..)
+            .find_map(|seed| {
+                let mut placed: Vec<usize> = vec![];
+                for &i in &buckets[bucket] {
+                    let slot = (spelling_hash(entries[i].0.chars(), seed) % slots as u64) as usize;
+                    if taken[slot] || placed.contains(&slot) {
+                        return None;
+                    }
+                    placed.push(slot);
+                }
+                Some((seed, placed))
+            })
+            .unwrap();
+        table.displacements[bucket] = seed;
+        for (&i, &slot) in buckets[bucket].iter().zip(&placed) {
+            taken[slot] = true;
+            table.keys[slot] = entries[i].0.clone();
+            table.values[slot] = entries[i].1;
+        }
+    }
+    table
+}
+
+fn write_table(out: &mut String, name: &str, table: &Table) {
+    writeln!(out, "pub(crate) static {}: Table = Table {{", name).unwrap();
+    writeln!(out, "    displacements: &{:?},", table.displacements).unwrap();
+    writeln!(out, "    keys: &{:?},", table.keys).unwrap();
+    writeln!(out, "    values: &{:?},", table.values).unwrap();
+    writeln!(out, "}};").unwrap();
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/builtin.rs");
+    println!("cargo:rerun-if-changed=src/spelling_hash.rs");
+    let source = std::fs::read_to_string("src/builtin.rs").expect("reading src/builtin.rs");
+    let rows: Vec<Row> = source.lines().map(str::trim).filter(|line| line.starts_with("unit(Unit::")).map(parse_row).collect();
+
+    // Exact spellings; one claimed by two rows is ambiguous.
+    let mut exact: HashMap<String, u16> = HashMap::new();
+    // Lowercased spellings, with each candidate row and whether it only matches exactly.
+    let mut folded: HashMap<String, Vec<(u16, bool)>> = HashMap::new();
+    let mut folded_order: Vec<String> = vec![];
+    // Lowercased symbols starting with a prefix letter, e.g. "mg" -> 'm'.
+    let mut cased: Vec<(String, u16)> = vec![];
+    for (index, row) in rows.iter().enumerate() {
+        let index = u16::try_from(index).expect("too many units");
+        let (symbol, name) = (&row.spellings[0], &row.spellings[1]);
+        if let Some((letter, _)) = PREFIX_CASES.iter().find(|(letter, word)| symbol.starts_with(*letter) && name.starts_with(word)) {
+            let key = symbol.to_lowercase();
+            if !cased.iter().any(|(other, _)| *other == key) {
+                cased.push((key, u16::try_from(u32::from(*letter)).unwrap()));
+            }
+        }
+        let mut seen: Vec<&str> = vec![];
+        for spelling in &row.spellings {
+            if seen.contains(&spelling.as_str()) {
+                continue;
+            }
+            seen.push(spelling);
+            exact.entry(spelling.clone()).and_modify(|value| *value = AMBIGUOUS).or_insert(index);
+            // Words that only match exactly: see `WORDS` in the registry.
+            let key = spelling.to_lowercase();
+            let case_sensitive = WORDS.contains(&key.as_str());
+            if !folded.contains_key(&key) {
+                folded_order.push(key.clone());
+            }
+            folded.entry(key).or_default().push((index, case_sensitive));
+        }
+    }
+    let mut exact_entries: Vec<(String, u16)> = exact.into_iter().collect();
+    exact_entries.sort();
+    let mut folded_entries: Vec<(String, u16)> = vec![];
+    for key in folded_order {
+        let candidates = &folded[&key];
+        let mut all: Vec<u16> = candidates.iter().map(|(index, _)| *index).collect();
+        all.dedup();
+        let value = if all.len() > 1 {
+            Some(AMBIGUOUS)
+        } else {
+            candidates.iter().find(|(_, case_sensitive)| !case_sensitive).map(|(index, _)| *index)
+        };
+        if let Some(value) = value {
+            folded_entries.push((key, value));
+        }
+    }
+
+    let mut out = String::from("// Generated by build.rs from src/builtin.rs.\n\n");
+    writeln!(out, "pub(crate) const BUILTIN_ROWS: usize = {};\n", rows.len()).unwrap();
+    write_table(&mut out, "EXACT", &perfect_hash(&exact_entries));
+    write_table(&mut out, "FOLDED", &perfect_hash(&folded_entries));
+    write_table(&mut out, "CASED", &perfect_hash(&cased));
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR");
+    std::fs::write(Path::new(&out_dir).join("spellings.rs"), out).expect("writing the spelling tables");
+}
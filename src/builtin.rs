@@ -0,0 +1,412 @@
+use crate::enums::*;
+use crate::exact::Exact;
+use crate::registry::System::*;
+use crate::registry::{Exactness, Reference, System, UnitInfo};
+
+// Every built-in unit with its spellings and conversion to the dimension's base unit.
+// This table is the single source for lookup (`string_to_target`) and for conversion
+// factors; a variant missing here has no spelling and reports a missing factor.
+// build.rs also reads the spellings from it to generate the lookup tables, so each
+// row stays on one line with its symbol, names and aliases as string literals.
+//
+// Symbols keep their conventional case: "mA" and "MA", "Pa" and "pA" are different
+// units. No spelling may be used by two rows (see `UnitRegistry::collisions`).
+//
+// A factor written as a decimal literal is also its exact value. Factors written as
+// expressions (5/9, 1/60, 1/π) give their exact value with `.exact(...)`.
+//
+// A factor is taken to be exact by definition unless `.exactness(...)` says it is
+// rounded, measured or a placeholder, which also leaves it without an exact value.
+// The reference defaults to the one covering the unit's system (see
+// `default_reference`); `.reference(...)` overrides it.
+
+fn unit(unit: Unit, symbol: &str, [name, plural]: [&str; 2], aliases: &[&str], factor: f64, system: System) -> UnitInfo {
+    UnitInfo {
+        dimension: crate::get_unit_dimension(&unit),
+        unit,
+        symbol: symbol.to_string(),
+        name: name.to_string(),
+        plural: plural.to_string(),
+        aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        factor,
+        offset: 0.0,
+        exact_factor: Exact::from_f64(factor),
+        exact_offset: Some(Exact::ZERO),
+        system,
+        exactness: Exactness::Exact,
+        reference: default_reference(system),
+    }
+}
+
+fn default_reference(system: System) -> Option<Reference> {
+    match system {
+        Si | SiAccepted => Some(Reference::SiBrochure),
+        Cgs | CgsEsu | CgsEmu | Imperial | UsCustomary => Some(Reference::NistSp811),
+        Natural => Some(Reference::Codata2022),
+        Colloquial | Other | UserDefined => None,
+    }
+}
+
+pub fn builtin_units() -> Vec<UnitInfo> {
+    vec![
+        // Length (Base: Meter)
+        unit(Unit::Length(Length::Kilometer), "km", ["kilometer", "kilometers"], &[], 1000.0, Si),
+        unit(Unit::Length(Length::Hectometer), "hm", ["hectometer", "hectometers"], &[], 100.0, Si),
+        unit(Unit::Length(Length::Decameter), "dam", ["decameter", "decameters"], &["decam", "dekameter", "dekameters"], 10.0, Si),
+        unit(Unit::Length(Length::Meter), "m", ["meter", "meters"], &[], 1.0, Si),
+        unit(Unit::Length(Length::Decimeter), "dm", ["decimeter", "decimeters"], &["decim"], 0.1, Si),
+        unit(Unit::Length(Length::Centimeter), "cm", ["centimeter", "centimeters"], &[], 0.01, Si),
+        unit(Unit::Length(Length::Millimeter), "mm", ["millimeter", "millimeters"], &[], 0.001, Si),
+        unit(Unit::Length(Length::Micron), "µm", ["micrometer", "micrometers"], &["micron", "microns"], 1e-6, Si),
+        unit(Unit::Length(Length::Nanometer), "nm", ["nanometer", "nanometers"], &[], 1e-9, Si),
+        unit(Unit::Length(Length::Picometer), "pm", ["picometer", "picometers"], &[], 1e-12, Si),
+        unit(Unit::Length(Length::Femtometer), "fm", ["femtometer", "femtometers"], &[], 1e-15, Si),
+        unit(Unit::Length(Length::Attometer), "am", ["attometer", "attometers"], &[], 1e-18, Si),
+        unit(Unit::Length(Length::LightYear), "ly", ["light_year", "light_years"], &["lightyear", "lightyears"], 9.461e15, Other).exactness(Exactness::Rounded).reference(Some(Reference::NistSp811)),
+        unit(Unit::Length(Length::AstronomicalUnit), "au", ["astronomical_unit", "astronomical_units"], &["astronomicalunit", "astronomicalunits"], 149_597_870_700.0, SiAccepted), // IAU 2012
+        unit(Unit::Length(Length::Parsec), "pc", ["parsec", "parsecs"], &[], 3.086e16, Other).exactness(Exactness::Rounded).reference(Some(Reference::NistSp811)),
+        unit(Unit::Length(Length::Angstrom), "Å", ["angstrom", "angstroms"], &[], 1e-10, Other).reference(Some(Reference::NistSp811)),
+        unit(Unit::Length(Length::Inch), "in", ["inch", "inches"], &[], 0.0254, Imperial),
+        unit(Unit::Length(Length::Foot), "ft", ["foot", "feet"], &[], 0.3048, Imperial),
+        unit(Unit::Length(Length::Yard), "yd", ["yard", "yards"], &[], 0.9144, Imperial),
+        unit(Unit::Length(Length::Mile), "mi", ["mile", "miles"], &[], 1609.344, Imperial),
+        unit(Unit::Length(Length::NauticalMile), "nmi", ["nautical_mile", "nautical_miles"], &["nauticalmile", "nauticalmiles"], 1852.0, Other).reference(Some(Reference::NistSp811)),
+        unit(Unit::Length(Length::League), "league", ["league", "leagues"], &[], 4828.032, Imperial), // 3 miles
+        unit(Unit::Length(Length::Furlong), "furlong", ["furlong", "furlongs"], &[], 201.168, Imperial),
+        unit(Unit::Length(Length::Rod), "rod", ["rod", "rods"], &[], 5.0292, Imperial),
+        unit(Unit::Length(Length::Chain), "chain", ["chain", "chains"], &[], 20.1168, Imperial),
+        unit(Unit::Length(Length::FootballFieldLength), "football_field", ["football_field", "football_fields"], &["football_field_length", "football_field_lengths"], 91.44, Colloquial), // Approx 100 yards
+        unit(Unit::Length(Length::WhaleLength), "whale", ["whale", "whales"], &["whale_length", "whale_lengths"], 25.0, Colloquial).exactness(Exactness::Placeholder), // Approx avg length of a blue whale in meters
+
+        // Mass (Base: Kilogram)
+        unit(Unit::Mass(Mass::Kilogram), "kg", ["kilogram", "kilograms"], &[], 1.0, Si),
+        unit(Unit::Mass(Mass::Hectogram), "hg", ["hectogram", "hectograms"], &[], 0.1, Si),
+        unit(Unit::Mass(Mass::Decagram), "dag", ["decagram", "decagrams"], &[], 0.01, Si),
+        unit(Unit::Mass(Mass::Gram), "g", ["gram", "grams"], &[], 0.001, Si),
+        unit(Unit::Mass(Mass::Decigram), "dg", ["decigram", "decigrams"], &[], 0.0001, Si),
+        unit(Unit::Mass(Mass::Centigram), "cg", ["centigram", "centigrams"], &[], 1e-5, Si),
+        unit(Unit::Mass(Mass::Milligram), "mg", ["milligram", "milligrams"], &[], 1e-6, Si),
+        unit(Unit::Mass(Mass::Microgram), "µg", ["microgram", "micrograms"], &["mcg"], 1e-9, Si),
+        unit(Unit::Mass(Mass::Nanogram), "ng", ["nanogram", "nanograms"], &[], 1e-12, Si),
+        unit(Unit::Mass(Mass::Picogram), "pg", ["picogram", "picograms"], &[], 1e-15, Si),
+        unit(Unit::Mass(Mass::Femtogram), "fg", ["femtogram", "femtograms"], &[], 1e-18, Si),
+        unit(Unit::Mass(Mass::Attogram), "ag", ["attogram", "attograms"], &[], 1e-21, Si),
+        unit(Unit::Mass(Mass::Ton), "ton", ["ton", "tons"], &[], 907.18474, UsCustomary), // Short ton (US), 2000 lb
+        unit(Unit::Mass(Mass::MetricTon), "t", ["metric_ton", "metric_tons"], &["tonne", "tonnes"], 1000.0, SiAccepted), // Tonne
+        unit(Unit::Mass(Mass::Quintal), "quintal", ["quintal", "quintals"], &["centner", "centners"], 100.0, Other),
+        unit(Unit::Mass(Mass::Pounds), "lb", ["pound", "pounds"], &["lbs"], 0.45359237, Imperial),
+        unit(Unit::Mass(Mass::Ounce), "oz", ["ounce", "ounces"], &[], 0.028349523125, Imperial),
+        unit(Unit::Mass(Mass::Stone), "stone", ["stone", "stones"], &[], 6.35029, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Mass(Mass::Carat), "ct", ["carat", "carats"], &[], 0.0002, Other).reference(Some(Reference::NistSp811)),
+
+        // Area (Base: Square Meter)
+        unit(Unit::Area(Area::SquareMeter), "m²", ["square_meter", "square_meters"], &["sqm", "m2"], 1.0, Si),
+        unit(Unit::Area(Area::SquareKilometer), "km²", ["square_kilometer", "square_kilometers"], &["sqkm", "km2"], 1_000_000.0, Si),
+        unit(Unit::Area(Area::SquareCentimeter), "cm²", ["square_centimeter", "square_centimeters"], &["sqcm", "cm2"], 0.0001, Si),
+        unit(Unit::Area(Area::SquareMillimeter), "mm²", ["square_millimeter", "square_millimeters"], &["sqmm", "mm2"], 0.000001, Si),
+        unit(Unit::Area(Area::SquareDecimeter), "dm²", ["square_decimeter", "square_decimeters"], &["sqdm", "dm2"], 0.01, Si),
+        unit(Unit::Area(Area::Hectare), "ha", ["hectare", "hectares"], &[], 10_000.0, SiAccepted),
+        unit(Unit::Area(Area::Acre), "ac", ["acre", "acres"], &[], 4046.8564224, Imperial),
+        unit(Unit::Area(Area::SquareFoot), "ft²", ["square_foot", "square_feet"], &["sqft", "ft2"], 0.09290304, Imperial),
+        unit(Unit::Area(Area::SquareInch), "in²", ["square_inch", "square_inches"], &["sqin", "in2"], 0.00064516, Imperial),
+        unit(Unit::Area(Area::SquareYard), "yd²", ["square_yard", "square_yards"], &["sqyd", "yd2"], 0.83612736, Imperial),
+        unit(Unit::Area(Area::SquareMile), "mi²", ["square_mile", "square_miles"], &["sqmi", "mi2"], 2_589_988.110336, Imperial),
+
+        // Volume (Base: Cubic Meter)
+        unit(Unit::Volume(Volume::CubicMeter), "m³", ["cubic_meter", "cubic_meters"], &["cum", "m3"], 1.0, Si),
+        unit(Unit::Volume(Volume::CubicDecimeter), "dm³", ["cubic_decimeter", "cubic_decimeters"], &["cudm", "dm3"], 0.001, Si),
+        unit(Unit::Volume(Volume::CubicCentimeter), "cm³", ["cubic_centimeter", "cubic_centimeters"], &["cucc", "cc", "cm3"], 1e-6, Si),
+        unit(Unit::Volume(Volume::CubicMillimeter), "mm³", ["cubic_millimeter", "cubic_millimeters"], &["cumm", "mm3"], 1e-9, Si),
+        unit(Unit::Volume(Volume::Liter), "L", ["liter", "liters"], &[], 0.001, SiAccepted),
+        unit(Unit::Volume(Volume::Milliliter), "mL", ["milliliter", "milliliters"], &[], 1e-6, SiAccepted),
+        unit(Unit::Volume(Volume::Deciliter), "dL", ["deciliter", "deciliters"], &[], 0.0001, SiAccepted),
+        unit(Unit::Volume(Volume::Kiloliter), "kL", ["kiloliter", "kiloliters"], &[], 1.0, SiAccepted),
+        unit(Unit::Volume(Volume::Megaliter), "ML", ["megaliter", "megaliters"], &[], 1000.0, SiAccepted),
+        unit(Unit::Volume(Volume::Gigaliter), "GL", ["gigaliter", "gigaliters"], &[], 1_000_000.0, SiAccepted),
+        unit(Unit::Volume(Volume::Teraliter), "TL", ["teraliter", "teraliters"], &[], 1_000_000_000.0, SiAccepted),
+        unit(Unit::Volume(Volume::CubicInch), "in³", ["cubic_inch", "cubic_inches"], &["cuin", "in3"], 1.63871e-5, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Volume(Volume::CubicFoot), "ft³", ["cubic_foot", "cubic_feet"], &["cuft", "ft3"], 0.0283168, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Volume(Volume::CubicYard), "yd³", ["cubic_yard", "cubic_yards"], &["cuyd", "yd3"], 0.764555, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Volume(Volume::CubicMile), "mi³", ["cubic_mile", "cubic_miles"], &["cumi", "mi3"], 4.16818e9, UsCustomary).exactness(Exactness::Rounded),
+        unit(Unit::Volume(Volume::TeracubicFeet), "Tcf", ["teracubic_feet", "teracubic_feet"], &[], 2.8316846592e10, Colloquial), // 1 trillion cubic feet
+        unit(Unit::Volume(Volume::FluidOunce), "fl oz", ["fluid_ounce", "fluid_ounces"], &["floz"], 2.95735e-5, UsCustomary).exactness(Exactness::Rounded), // US fluid ounce
+        unit(Unit::Volume(Volume::Pint), "pt", ["pint", "pints"], &[], 0.000473176, UsCustomary).exactness(Exactness::Rounded), // US liquid pint
+        unit(Unit::Volume(Volume::Quart), "qt", ["quart", "quarts"], &[], 0.000946353, UsCustomary).exactness(Exactness::Rounded), // US liquid quart
+        unit(Unit::Volume(Volume::Gallon), "gal", ["gallon", "gallons"], &[], 0.003785411784, UsCustomary), // US liquid gallon, 231 in³
+        unit(Unit::Volume(Volume::BarrelLiquid), "bbl", ["barrel_liquid", "barrel_liquids"], &[], 0.11924, UsCustomary).exactness(Exactness::Rounded), // US liquid barrel
+        unit(Unit::Volume(Volume::Bushel), "bu", ["bushel", "bushels"], &[], 0.0352391, UsCustomary).exactness(Exactness::Rounded), // US bushel
+        unit(Unit::Volume(Volume::Peck), "peck", ["peck", "pecks"], &[], 0.00880977, UsCustomary).exactness(Exactness::Rounded), // US peck
+        unit(Unit::Volume(Volume::Cord), "cord", ["cord", "cords"], &[], 3.624556, UsCustomary).exactness(Exactness::Rounded), // 128 cubic feet
+        unit(Unit::Volume(Volume::Teaspoon), "tsp", ["teaspoon", "teaspoons"], &[], 4.92892e-6, UsCustomary).exactness(Exactness::Rounded), // US teaspoon
+        unit(Unit::Volume(Volume::Tablespoon), "tbsp", ["tablespoon", "tablespoons"], &[], 1.47868e-5, UsCustomary).exactness(Exactness::Rounded), // US tablespoon
+        unit(Unit::Volume(Volume::Cup), "cup", ["cup", "cups"], &[], 0.000236588, UsCustomary).exactness(Exactness::Rounded), // US cup
+        unit(Unit::Volume(Volume::AcreFoot), "ac·ft", ["acre_foot", "acre_feet"], &["acft"], 1233.48, UsCustomary).exactness(Exactness::Rounded),
+        unit(Unit::Volume(Volume::StandardCubicFoot), "scf", ["standard_cubic_foot", "standard_cubic_feet"], &[], 0.028316846592, UsCustomary), // same as cubic foot
+
+        // Force (Base: Newton)
+        unit(Unit::Force(Force::Newton), "N", ["newton", "newtons"], &[], 1.0, Si),
+        unit(Unit::Force(Force::Millinewton), "mN", ["millinewton", "millinewtons"], &[], 0.001, Si),
+        unit(Unit::Force(Force::Kilonewton), "kN", ["kilonewton", "kilonewtons"], &[], 1000.0, Si),
+        unit(Unit::Force(Force::Meganewton), "MN", ["meganewton", "meganewtons"], &[], 1_000_000.0, Si),
+        unit(Unit::Force(Force::Dyne), "dyn", ["dyne", "dynes"], &[], 1e-5, Cgs),
+        unit(Unit::Force(Force::KilogramForce), "kp", ["kilogram_force", "kilogram_forces"], &["kilopond", "kiloponds", "kgf"], 9.80665, Other).reference(Some(Reference::NistSp811)),
+        unit(Unit::Force(Force::GramForce), "gf", ["gram_force", "gram_forces"], &[], 0.00980665, Other),
+        unit(Unit::Force(Force::PoundForce), "lbf", ["pound_force", "pound_forces"], &[], 4.4482216152605, Imperial),
+        unit(Unit::Force(Force::OunceForce), "ozf", ["ounce_force", "ounce_forces"], &[], 0.278014, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Force(Force::TonForce), "ton_force", ["ton_force", "ton_forces"], &[], 9806.65, Other), // Metric ton-force
+        unit(Unit::Force(Force::Poundal), "pdl", ["poundal", "poundals"], &[], 0.138255, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Force(Force::Kip), "kip", ["kip", "kips"], &[], 4448.22, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Force(Force::Sthene), "sthene", ["sthene", "sthenes"], &[], 1000.0, Other),
+        unit(Unit::Force(Force::JoulePerMeter), "J/m", ["joule_per_meter", "joules_per_meter"], &[], 1.0, Si), // 1 J/m = 1 N
+
+        // Energy (Base: Joule)
+        unit(Unit::Energy(Energy::Joule), "J", ["joule", "joules"], &[], 1.0, Si),
+        unit(Unit::Energy(Energy::Calorie), "cal", ["calorie", "calories"], &[], 4.184, Other).reference(Some(Reference::NistSp811)), // Thermochemical calorie
+        unit(Unit::Energy(Energy::Kilocalorie), "kcal", ["kilocalorie", "kilocalories"], &["cal_(nutrition)"], 4184.0, Other).reference(Some(Reference::NistSp811)), // Food calorie
+        unit(Unit::Energy(Energy::KilowattHour), "kWh", ["kilowatt_hour", "kilowatt_hours"], &[], 3.6e6, SiAccepted),
+        unit(Unit::Energy(Energy::WattHour), "Wh", ["watt_hour", "watt_hours"], &[], 3600.0, SiAccepted),
+        unit(Unit::Energy(Energy::Electronvolt), "eV", ["electronvolt", "electronvolts"], &[], 1.60218e-19, SiAccepted).exactness(Exactness::Rounded),
+        unit(Unit::Energy(Energy::Hartree), "Eh", ["hartree", "hartrees"], &[], 4.359744e-18, Natural).exactness(Exactness::Measured),
+        unit(Unit::Energy(Energy::BritishThermalUnit), "BTU", ["british_thermal_unit", "british_thermal_units"], &[], 1055.06, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Energy(Energy::FootPound), "ft·lbf", ["foot_pound", "foot_pounds"], &["foot_pound_force", "foot_pound_forces"], 1.35582, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Energy(Energy::Erg), "erg", ["erg", "ergs"], &[], 1e-7, Cgs),
+        unit(Unit::Energy(Energy::Therm), "thm", ["therm", "therms"], &[], 1.05506e8, UsCustomary).exactness(Exactness::Rounded), // US therm
+        unit(Unit::Energy(Energy::TonOfTntEquivalent), "tTNT", ["ton_of_tnt_equivalent", "tons_of_tnt_equivalent"], &[], 4.184e9, Colloquial),
+        unit(Unit::Energy(Energy::HorsepowerHour), "hp·h", ["horsepower_hour", "horsepower_hours"], &[], 2.68452e6, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Energy(Energy::BarrelOfOilEquivalent), "BOE", ["barrel_of_oil_equivalent", "barrels_of_oil_equivalent"], &[], 6.1178632e9, Colloquial).exactness(Exactness::Placeholder), // approx
+
+        // Temperature (Base: Kelvin)
+        unit(Unit::Temperature(Temperature::Kelvin), "K", ["kelvin", "kelvin"], &[], 1.0, Si),
+        unit(Unit::Temperature(Temperature::Celsius), "°C", ["celsius", "celsius"], &[], 1.0, SiAccepted).offset(273.15),
+        unit(Unit::Temperature(Temperature::Fahrenheit), "°F", ["fahrenheit", "fahrenheit"], &[], 5.0/9.0, Imperial).offset(255.372_222_222_222_2).exact(Exact::ratio(5, 9)).exact_offset(Exact::ratio(45967, 180)), // K = (°F + 459.67) * 5/9
+        unit(Unit::Temperature(Temperature::Rankine), "°R", ["rankine", "rankines"], &[], 5.0/9.0, Imperial).exact(Exact::ratio(5, 9)),
+        unit(Unit::Temperature(Temperature::Reaumur), "°Ré", ["reaumur", "reaumurs"], &["°re"], 1.25, Other).offset(273.15), // K = °Ré * 5/4 + 273.15
+        unit(Unit::Temperature(Temperature::Delisle), "°De", ["delisle", "delisles"], &[], -2.0/3.0, Other).offset(373.15).exact(Exact::ratio(-2, 3)), // K = 373.15 - °De * 2/3
+        unit(Unit::Temperature(Temperature::NewtonScale), "°N", ["newton_scale", "newton_scales"], &[], 100.0/33.0, Other).offset(273.15).exact(Exact::ratio(100, 33)), // K = °N * 100/33 + 273.15
+        unit(Unit::Temperature(Temperature::Romer), "°Rø", ["romer", "romers"], &["°ro"], 40.0/21.0, Other).offset(258.864_285_714_285_7).exact(Exact::ratio(40, 21)).exact_offset(Exact::ratio(36241, 140)), // K = (°Rø - 7.5) * 40/21 + 273.15
+        unit(Unit::Temperature(Temperature::Leiden), "leiden", ["leiden", "leidens"], &[], 1.0, Other).exactness(Exactness::Placeholder), // Used for cryogenic temperatures, 1 K = 1 L
+        unit(Unit::Temperature(Temperature::PlanckTemperature), "planck_temperature", ["planck_temperature", "planck_temperatures"], &[], 1.416785e32, Natural).exactness(Exactness::Measured),
+
+        // Time (Base: Second)
+        unit(Unit::Time(Time::Second), "s", ["second", "seconds"], &["sec"], 1.0, Si),
+        unit(Unit::Time(Time::Minute), "min", ["minute", "minutes"], &[], 60.0, SiAccepted),
+        unit(Unit::Time(Time::Hour), "h", ["hour", "hours"], &["hr"], 3600.0, SiAccepted),
+        unit(Unit::Time(Time::Day), "d", ["day", "days"], &[], 86400.0, SiAccepted),
+        unit(Unit::Time(Time::Week), "wk", ["week", "weeks"], &[], 604800.0, Other),
+        unit(Unit::Time(Time::Month), "mo", ["month", "months"], &[], 2.629746e6, Other), // Average Gregorian month
+        unit(Unit::Time(Time::Year), "calendar_year", ["calendar_year", "calendar_years"], &[], 3.1556952e7, Other), // 12 months from a calendar anchor (see calendar.rs), else the Gregorian mean
+        unit(Unit::Time(Time::Decade), "decade", ["decade", "decades"], &[], 3.1556952e8, Other), // 10 Gregorian years, or 120 calendar months
+        unit(Unit::Time(Time::Century), "century", ["century", "centuries"], &[], 3.1556952e9, Other),
+        unit(Unit::Time(Time::Millennium), "millennium", ["millennium", "millennia"], &[], 3.1556952e10, Other),
+        unit(Unit::Time(Time::Microsecond), "µs", ["microsecond", "microseconds"], &[], 1e-6, Si),
+        unit(Unit::Time(Time::Millisecond), "ms", ["millisecond", "milliseconds"], &[], 1e-3, Si),
+        unit(Unit::Time(Time::Nanosecond), "ns", ["nanosecond", "nanoseconds"], &[], 1e-9, Si),
+        unit(Unit::Time(Time::Picosecond), "ps", ["picosecond", "picoseconds"], &[], 1e-12, Si),
+        unit(Unit::Time(Time::Fortnight), "fortnight", ["fortnight", "fortnights"], &[], 1_209_600.0, Other), // 14 days
+        unit(Unit::Time(Time::Shake), "shake", ["shake", "shakes"], &[], 1e-8, Other).reference(Some(Reference::NistSp811)),
+        unit(Unit::Time(Time::JulianYear), "julian_year", ["julian_year", "julian_years"], &[], 3.15576e7, Other),
+        unit(Unit::Time(Time::LeapYear), "leap_year", ["leap_year", "leap_years"], &[], 3.16224e7, Other),
+        unit(Unit::Time(Time::CommonYear), "common_year", ["common_year", "common_years"], &[], 3.1536e7, Other),
+        unit(Unit::Time(Time::GregorianYear), "yr", ["gregorian_year", "gregorian_years"], &["year", "years"], 3.1556952e7, Other), // A plain "year" is this one
+        unit(Unit::Time(Time::TropicalYear), "tropical_year", ["tropical_year", "tropical_years"], &["solar_year", "solar_years"], 3.15569252e7, Other).exactness(Exactness::Measured).reference(Some(Reference::NistSp811)), // 365.24219 days
+        unit(Unit::Time(Time::SiderealYear), "sidereal_year", ["sidereal_year", "sidereal_years"], &[], 3.15581498e7, Other).exactness(Exactness::Measured).reference(Some(Reference::NistSp811)), // 365.256363 days
+        unit(Unit::Time(Time::SiderealDay), "sidereal_day", ["sidereal_day", "sidereal_days"], &[], 86164.09053, Other).exactness(Exactness::Measured).reference(Some(Reference::NistSp811)),
+        unit(Unit::Time(Time::PlanckTime), "planck_time", ["planck_time", "planck_times"], &[], 5.391247e-44, Natural).exactness(Exactness::Measured),
+
+        // Speed (Base: Meter per Second)
+        unit(Unit::Speed(Speed::MeterPerSecond), "m/s", ["meter_per_second", "meters_per_second"], &[], 1.0, Si),
+        unit(Unit::Speed(Speed::KilometerPerHour), "kph", ["kilometer_per_hour", "kilometers_per_hour"], &["km/h"], 1000.0 / 3600.0, SiAccepted).exact(Exact::ratio(1000, 3600)), // km/h to m/s
+        unit(Unit::Speed(Speed::MilePerHour), "mph", ["mile_per_hour", "miles_per_hour"], &[], 1609.344 / 3600.0, Imperial).exact(Exact::ratio(1_609_344, 3_600_000)), // mph to m/s
+        unit(Unit::Speed(Speed::Knot), "kt", ["knot", "knots"], &[], 0.514444, Other).exactness(Exactness::Rounded).reference(Some(Reference::NistSp811)), // nautical miles per hour to m/s
+        unit(Unit::Speed(Speed::FootPerSecond), "ft/s", ["foot_per_second", "feet_per_second"], &[], 0.3048, Imperial),
+        unit(Unit::Speed(Speed::Mach), "mach", ["mach", "machs"], &[], 343.0, Other).exactness(Exactness::Placeholder), // Approx speed of sound in dry air at 20°C
+        unit(Unit::Speed(Speed::SpeedOfLight), "c", ["speed_of_light", "speed_of_light"], &[], 299792458.0, Natural).reference(Some(Reference::SiBrochure)), // exact
+        unit(Unit::Speed(Speed::InchPerSecond), "in/s", ["inch_per_second", "inches_per_second"], &[], 0.0254, Imperial),
+        unit(Unit::Speed(Speed::FurlongPerFortnight), "furlong_per_fortnight", ["furlong_per_fortnight", "furlongs_per_fortnight"], &[], 201.168 / 1_209_600.0, Colloquial).exact(Exact::ratio(201_168, 1_209_600_000)), // furlongs/fortnight to m/s
+        unit(Unit::Speed(Speed::BeaufortScale), "beaufort_scale", ["beaufort_scale", "beaufort_scales"], &[], 0.5, Colloquial).exactness(Exactness::Placeholder), // Approx 1 Beaufort = 0.5 m/s
+        unit(Unit::Speed(Speed::CosmicVelocity), "cosmic_velocity", ["cosmic_velocity", "cosmic_velocities"], &[], 11200.0, Colloquial).exactness(Exactness::Placeholder), // Earth's escape velocity (approx)
+        unit(Unit::Speed(Speed::GalileoUnit), "galileo_unit", ["galileo_unit", "galileo_units"], &[], 0.00001, Cgs).exactness(Exactness::Placeholder), // 1 cm/s
+        unit(Unit::Speed(Speed::Benz), "benz", ["benz", "benzs"], &[], 1.0, Colloquial), // 1 Benz = 1 m/s
+        unit(Unit::Speed(Speed::YardPerSecond), "yd/s", ["yard_per_second", "yards_per_second"], &[], 0.9144, Imperial),
+        unit(Unit::Speed(Speed::EarthsRotationSpeed), "earth's_rotation_speed", ["earth's_rotation_speed", "earth's_rotation_speed"], &[], 465.1, Colloquial).exactness(Exactness::Measured), // at equator in m/s
+        unit(Unit::Speed(Speed::ParsecPerYear), "pc/yr", ["parsec_per_year", "parsecs_per_year"], &[], 3.086e16 / 3.15569e7, Other).exactness(Exactness::Rounded), // pc/year to m/s
+        unit(Unit::Speed(Speed::SpeedOfLightC), "speed_of_light_c", ["speed_of_light_c", "speed_of_light_c"], &[], 299792458.0, Natural).reference(Some(Reference::SiBrochure)), // exact
+        unit(Unit::Speed(Speed::MilePerMinute), "mi/min", ["mile_per_minute", "miles_per_minute"], &["mpm"], 1609.344 / 60.0, Imperial).exact(Exact::ratio(1_609_344, 60_000)),
+        unit(Unit::Speed(Speed::KilometerPerSecond), "km/s", ["kilometer_per_second", "kilometers_per_second"], &[], 1000.0, Si),
+
+        // Pressure (Base: Pascal)
+        unit(Unit::Pressure(Pressure::Pascal), "Pa", ["pascal", "pascals"], &[], 1.0, Si),
+        unit(Unit::Pressure(Pressure::Decipascal), "dPa", ["decipascal", "decipascals"], &[], 0.1, Si),
+        unit(Unit::Pressure(Pressure::Hectopascal), "hPa", ["hectopascal", "hectopascals"], &[], 100.0, Si),
+        unit(Unit::Pressure(Pressure::Kilopascal), "kPa", ["kilopascal", "kilopascals"], &[], 1000.0, Si),
+        unit(Unit::Pressure(Pressure::Megapascal), "MPa", ["megapascal", "megapascals"], &[], 1_000_000.0, Si),
+        unit(Unit::Pressure(Pressure::PlanckPressure), "planck_pressure", ["planck_pressure", "planck_pressures"], &[], 4.63309e113, Natural).exactness(Exactness::Measured),
+        unit(Unit::Pressure(Pressure::Bar), "bar", ["bar", "bars"], &[], 100_000.0, Other).reference(Some(Reference::NistSp811)),
+        unit(Unit::Pressure(Pressure::AtmosphereStandard), "atm", ["atmosphere_standard", "atmosphere_standards"], &[], 101325.0, Other).reference(Some(Reference::NistSp811)),
+        unit(Unit::Pressure(Pressure::Torr), "Torr", ["torr", "torrs"], &[], 133.322, Other).exactness(Exactness::Rounded).reference(Some(Reference::NistSp811)),
+        unit(Unit::Pressure(Pressure::PoundPerSquareInch), "psi", ["pound_per_square_inch", "pounds_per_square_inch"], &[], 6894.76, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Pressure(Pressure::KipPerSquareInch), "ksi", ["kip_per_square_inch", "kips_per_square_inch"], &[], 6.89476e6, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Pressure(Pressure::MillimeterOfMercury), "mmHg", ["millimeter_of_mercury", "millimeters_of_mercury"], &[], 133.322, Other).exactness(Exactness::Rounded).reference(Some(Reference::NistSp811)),
+        unit(Unit::Pressure(Pressure::InchOfMercury), "inHg", ["inch_of_mercury", "inches_of_mercury"], &[], 3386.39, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Pressure(Pressure::InchOfWater), "inH2O", ["inch_of_water", "inches_of_water"], &["inwc"], 249.089, Imperial).exactness(Exactness::Rounded), // at 4°C
+        unit(Unit::Pressure(Pressure::FootOfWater), "ftH2O", ["foot_of_water", "feet_of_water"], &["ftwc"], 2989.07, Imperial).exactness(Exactness::Rounded), // at 4°C
+        unit(Unit::Pressure(Pressure::DynePerSquareCentimeter), "dyn/cm²", ["dyne_per_square_centimeter", "dynes_per_square_centimeter"], &[], 0.1, Cgs),
+        unit(Unit::Pressure(Pressure::Barye), "Ba", ["barye", "baryes"], &[], 0.1, Cgs),
+        unit(Unit::Pressure(Pressure::TechnicalAtmosphere), "at", ["technical_atmosphere", "technical_atmospheres"], &[], 98066.5, Other).reference(Some(Reference::NistSp811)),
+        unit(Unit::Pressure(Pressure::PoundalPerSquareFoot), "pdl/ft²", ["poundal_per_square_foot", "poundals_per_square_foot"], &[], 1.48816, Imperial).exactness(Exactness::Rounded),
+        unit(Unit::Pressure(Pressure::SthenePerSquareMeter), "sthene_per_square_meter", ["sthene_per_square_meter", "sthenes_per_square_meter"], &[], 1000.0, Other),
+
+        // Frequency (Base: Hertz)
+        unit(Unit::Frequency(Frequency::Hertz), "Hz", ["hertz", "hertzs"], &[], 1.0, Si),
+        unit(Unit::Frequency(Frequency::Millihertz), "mHz", ["millihertz", "millihertzs"], &[], 0.001, Si),
+        unit(Unit::Frequency(Frequency::Microhertz), "µHz", ["microhertz", "microhertzs"], &[], 1e-6, Si),
+        unit(Unit::Frequency(Frequency::Nanohertz), "nHz", ["nanohertz", "nanohertzs"], &[], 1e-9, Si),
+        unit(Unit::Frequency(Frequency::Kilohertz), "kHz", ["kilohertz", "kilohertzs"], &[], 1000.0, Si),
+        unit(Unit::Frequency(Frequency::Megahertz), "MHz", ["megahertz", "megahertzs"], &[], 1_000_000.0, Si),
+        unit(Unit::Frequency(Frequency::Gigahertz), "GHz", ["gigahertz", "gigahertzs"], &[], 1_000_000_000.0, Si),
+        unit(Unit::Frequency(Frequency::Terahertz), "THz", ["terahertz", "terahertzs"], &[], 1_000_000_000_000.0, Si),
+        unit(Unit::Frequency(Frequency::PlanckFrequency), "planck_frequency", ["planck_frequency", "planck_frequencies"], &[], 1.8549e43, Natural).exactness(Exactness::Measured),
+        unit(Unit::Frequency(Frequency::RevolutionsPerMinute), "rpm", ["revolutions_per_minute", "revolutions_per_minute"], &[], 1.0 / 60.0, Other).exact(Exact::ratio(1, 60)).reference(Some(Reference::NistSp811)),
+        unit(Unit::Frequency(Frequency::RadianPerSecond), "rad/s", ["radian_per_second", "radians_per_second"], &[], 1.0 / (2.0 * std::f64::consts::PI), Si).exact(Exact::ratio(1, 2).and_then(|half| half.times_pi(-1))),
+        unit(Unit::Frequency(Frequency::CyclesPerSecond), "cps", ["cycles_per_second", "cycles_per_second"], &[], 1.0, Other),
+        unit(Unit::Frequency(Frequency::FramesPerSecond), "fps", ["frames_per_second", "frames_per_second"], &[], 1.0, Colloquial),
+        unit(Unit::Frequency(Frequency::BeatsPerMinute), "bpm", ["beats_per_minute", "beats_per_minute"], &[], 1.0 / 60.0, Colloquial).exact(Exact::ratio(1, 60)),
+        unit(Unit::Frequency(Frequency::CountsPerMinute), "cpm", ["counts_per_minute", "counts_per_minute"], &[], 1.0 / 60.0, Colloquial).exact(Exact::ratio(1, 60)),
+        unit(Unit::Frequency(Frequency::Fresnel), "fresnel", ["fresnel", "fresnels"], &[], 1e12, Other),
+        unit(Unit::Frequency(Frequency::ReciprocalSecond), "1/s", ["reciprocal_second", "reciprocal_seconds"], &[], 1.0, Si),
+        unit(Unit::Frequency(Frequency::DegreePerSecond), "deg/s", ["degree_per_second", "degrees_per_second"], &[], 1.0 / 360.0, Other).exact(Exact::ratio(1, 360)),
+        unit(Unit::Frequency(Frequency::GradianPerSecond), "grad/s", ["gradian_per_second", "gradians_per_second"], &[], 1.0 / 400.0, Other).exact(Exact::ratio(1, 400)),
+
+        // Electric Current (Base: Ampere)
+        unit(Unit::ElectricCurrent(ElectricCurrent::Ampere), "A", ["ampere", "amperes"], &["amp", "amps"], 1.0, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Picoampere), "pA", ["picoampere", "picoamperes"], &[], 1e-12, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Nanoampere), "nA", ["nanoampere", "nanoamperes"], &[], 1e-9, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Microampere), "µA", ["microampere", "microamperes"], &[], 1e-6, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Milliampere), "mA", ["milliampere", "milliamperes"], &[], 1e-3, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Kiloampere), "kA", ["kiloampere", "kiloamperes"], &[], 1000.0, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Megaampere), "MA", ["megaampere", "megaamperes"], &[], 1_000_000.0, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Teraampere), "TA", ["teraampere", "teraamperes"], &[], 1_000_000_000_000.0, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::PlanckCurrent), "planck_current", ["planck_current", "planck_currents"], &[], 3.4789e25, Natural).exactness(Exactness::Measured),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Statampere), "statA", ["statampere", "statamperes"], &["s_amp"], 3.33564e-10, CgsEsu).exactness(Exactness::Rounded), // 1/c_light * 10^-9
+        unit(Unit::ElectricCurrent(ElectricCurrent::Abampere), "abA", ["abampere", "abamperes"], &["abamp"], 10.0, CgsEmu),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Biot), "Bi", ["biot", "biots"], &[], 10.0, CgsEmu),
+        unit(Unit::ElectricCurrent(ElectricCurrent::FranklinPerSecond), "franklin_per_second", ["franklin_per_second", "franklins_per_second"], &[], 3.33564e-10, CgsEsu).exactness(Exactness::Rounded),
+        unit(Unit::ElectricCurrent(ElectricCurrent::CoulombPerSecond), "C/s", ["coulomb_per_second", "coulombs_per_second"], &[], 1.0, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::VoltPerOhm), "V/Ω", ["volt_per_ohm", "volts_per_ohm"], &[], 1.0, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::WattPerVolt), "W/V", ["watt_per_volt", "watts_per_volt"], &[], 1.0, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::SiemensVolt), "siemens_volt", ["siemens_volt", "siemens_volts"], &[], 1.0, Si),
+        unit(Unit::ElectricCurrent(ElectricCurrent::EsuPerSecond), "esu_per_second", ["esu_per_second", "esu_per_seconds"], &[], 3.33564e-10, CgsEsu).exactness(Exactness::Rounded),
+        unit(Unit::ElectricCurrent(ElectricCurrent::EmuOfCurrent), "emu_of_current", ["emu_of_current", "emu_of_currents"], &[], 10.0, CgsEmu),
+        unit(Unit::ElectricCurrent(ElectricCurrent::Gilbert), "Gb", ["gilbert", "gilberts"], &[], 0.795775, CgsEmu).exactness(Exactness::Rounded), // 1 Gilbert = 0.795775 Ampere-turns
+
+        // Voltage (Base: Volt)
+        unit(Unit::Voltage(Voltage::Volt), "V", ["volt", "volts"], &[], 1.0, Si),
+        unit(Unit::Voltage(Voltage::Picovolt), "pV", ["picovolt", "picovolts"], &[], 1e-12, Si),
+        unit(Unit::Voltage(Voltage::Nanovolt), "nV", ["nanovolt", "nanovolts"], &[], 1e-9, Si),
+        unit(Unit::Voltage(Voltage::Microvolt), "µV", ["microvolt", "microvolts"], &[], 1e-6, Si),
+        unit(Unit::Voltage(Voltage::Millivolt), "mV", ["millivolt", "millivolts"], &[], 1e-3, Si),
+        unit(Unit::Voltage(Voltage::Decivolt), "dV", ["decivolt", "decivolts"], &[], 0.1, Si),
+        unit(Unit::Voltage(Voltage::Centivolt), "cV", ["centivolt", "centivolts"], &[], 0.01, Si),
+        unit(Unit::Voltage(Voltage::Hectovolt), "hV", ["hectovolt", "hectovolts"], &[], 100.0, Si),
+        unit(Unit::Voltage(Voltage::Kilovolt), "kV", ["kilovolt", "kilovolts"], &[], 1000.0, Si),
+        unit(Unit::Voltage(Voltage::Megavolt), "MV", ["megavolt", "megavolts"], &[], 1_000_000.0, Si),
+        unit(Unit::Voltage(Voltage::Gigavolt), "GV", ["gigavolt", "gigavolts"], &[], 1_000_000_000.0, Si),
+        unit(Unit::Voltage(Voltage::Teravolt), "TV", ["teravolt", "teravolts"], &[], 1_000_000_000_000.0, Si),
+        unit(Unit::Voltage(Voltage::PlanckVoltage), "planck_voltage", ["planck_voltage", "planck_voltages"], &[], 1.04295e-1, Natural).exactness(Exactness::Measured),
+        unit(Unit::Voltage(Voltage::Statvolt), "statV", ["statvolt", "statvolts"], &["s_volt"], 299.792458, CgsEsu),
+        unit(Unit::Voltage(Voltage::Abvolt), "abV", ["abvolt", "abvolts"], &["ab_v"], 1e-8, CgsEmu),
+        unit(Unit::Voltage(Voltage::EsuOfPotential), "esu_of_potential", ["esu_of_potential", "esu_of_potentials"], &[], 299.792458, CgsEsu),
+        unit(Unit::Voltage(Voltage::EmuOfPotential), "emu_of_potential", ["emu_of_potential", "emu_of_potentials"], &[], 1e-8, CgsEmu),
+        unit(Unit::Voltage(Voltage::WattPerAmpere), "W/A", ["watt_per_ampere", "watts_per_ampere"], &[], 1.0, Si),
+        unit(Unit::Voltage(Voltage::JoulePerCoulomb), "J/C", ["joule_per_coulomb", "joules_per_coulomb"], &[], 1.0, Si),
+        unit(Unit::Voltage(Voltage::ElectronvoltPerElementaryCharge), "eV/e", ["electronvolt_per_elementary_charge", "electronvolts_per_elementary_charge"], &[], 1.0, Other), // 1 eV/e = 1 V
+
+        // Capacitance (Base: Farad)
+        unit(Unit::Capacitance(Capacitance::Farad), "F", ["farad", "farads"], &[], 1.0, Si),
+        unit(Unit::Capacitance(Capacitance::Attofarad), "aF", ["attofarad", "attofarads"], &[], 1e-18, Si),
+        unit(Unit::Capacitance(Capacitance::Femtofarad), "fF", ["femtofarad", "femtofarads"], &[], 1e-15, Si),
+        unit(Unit::Capacitance(Capacitance::Picofarad), "pF", ["picofarad", "picofarads"], &[], 1e-12, Si),
+        unit(Unit::Capacitance(Capacitance::Nanofarad), "nF", ["nanofarad", "nanofarads"], &[], 1e-9, Si),
+        unit(Unit::Capacitance(Capacitance::Microfarad), "µF", ["microfarad", "microfarads"], &[], 1e-6, Si),
+        unit(Unit::Capacitance(Capacitance::Millifarad), "mF", ["millifarad", "millifarads"], &[], 1e-3, Si),
+        unit(Unit::Capacitance(Capacitance::Decafarad), "daF", ["decafarad", "decafarads"], &[], 10.0, Si),
+        unit(Unit::Capacitance(Capacitance::Hectofarad), "hF", ["hectofarad", "hectofarads"], &[], 100.0, Si),
+        unit(Unit::Capacitance(Capacitance::Kilofarad), "kF", ["kilofarad", "kilofarads"], &[], 1000.0, Si),
+        unit(Unit::Capacitance(Capacitance::Megafarad), "MF", ["megafarad", "megafarads"], &[], 1_000_000.0, Si),
+        unit(Unit::Capacitance(Capacitance::Terafarad), "TF", ["terafarad", "terafarads"], &[], 1_000_000_000_000.0, Si),
+        unit(Unit::Capacitance(Capacitance::Gigafarad), "GF", ["gigafarad", "gigafarads"], &[], 1_000_000_000.0, Si),
+        unit(Unit::Capacitance(Capacitance::PlanckCapacitance), "planck_capacitance", ["planck_capacitance", "planck_capacitances"], &[], 1.0878e-32, Natural).exactness(Exactness::Measured),
+        unit(Unit::Capacitance(Capacitance::Statfarad), "statF", ["statfarad", "statfarads"], &["s_farad"], 1.11265e-12, CgsEsu).exactness(Exactness::Rounded), // 1 / (c^2 * 10^-9)
+        unit(Unit::Capacitance(Capacitance::Abfarad), "abF", ["abfarad", "abfarads"], &[], 1e9, CgsEmu),
+        unit(Unit::Capacitance(Capacitance::EsuOfCapacitance), "esu_of_capacitance", ["esu_of_capacitance", "esu_of_capacitances"], &[], 1.11265e-12, CgsEsu).exactness(Exactness::Rounded),
+        unit(Unit::Capacitance(Capacitance::EmuOfCapacitance), "emu_of_capacitance", ["emu_of_capacitance", "emu_of_capacitances"], &[], 1e9, CgsEmu),
+        unit(Unit::Capacitance(Capacitance::CoulombPerVolt), "C/V", ["coulomb_per_volt", "coulombs_per_volt"], &[], 1.0, Si),
+        unit(Unit::Capacitance(Capacitance::SecondPerOhm), "s/Ω", ["second_per_ohm", "seconds_per_ohm"], &[], 1.0, Si),
+
+        // Luminosity (Base: Candela)
+        unit(Unit::Luminosity(Luminosity::Candela), "cd", ["candela", "candelas"], &[], 1.0, Si),
+        unit(Unit::Luminosity(Luminosity::Lumen), "lm", ["lumen", "lumens"], &[], 1.0, Si).exactness(Exactness::Placeholder), // Lumen is luminous flux, Candela is luminous intensity. This conversion is highly context-dependent (solid angle). For simplicity, assuming 1 cd = 1 lm/sr, and 1 lm = 1 cd * sr. This will be simplified to a direct conversion for now.
+        unit(Unit::Luminosity(Luminosity::Lux), "lx", ["lux", "luxes"], &[], 1.0, Si).exactness(Exactness::Placeholder), // Lux is illuminance (lm/m^2). Not directly comparable to Candela. This conversion is simplified.
+        unit(Unit::Luminosity(Luminosity::CandelaPerSquareMeter), "cd/m²", ["candela_per_square_meter", "candelas_per_square_meter"], &["cd/m2"], 1.0, Si), // Base unit for luminance
+        unit(Unit::Luminosity(Luminosity::LumenPerSquareMeter), "lm/m²", ["lumen_per_square_meter", "lumens_per_square_meter"], &["lm/m2"], 1.0, Si).exactness(Exactness::Placeholder), // Same as Lux
+        unit(Unit::Luminosity(Luminosity::Nit), "nt", ["nit", "nits"], &[], 1.0, Other), // Same as Candela per Square Meter
+        unit(Unit::Luminosity(Luminosity::Stilb), "sb", ["stilb", "stilbs"], &[], 10000.0, Cgs), // cd/cm^2 to cd/m^2
+        unit(Unit::Luminosity(Luminosity::Apostilb), "asb", ["apostilb", "apostilbs"], &[], 1.0 / std::f64::consts::PI, Other).exact(Exact::ONE.times_pi(-1)), // asb to cd/m^2
+        unit(Unit::Luminosity(Luminosity::Lambert), "lambert", ["lambert", "lamberts"], &[], 10000.0 / std::f64::consts::PI, Cgs).exact(Exact::integer(10000).times_pi(-1)), // L to cd/m^2
+        unit(Unit::Luminosity(Luminosity::FootLambert), "fL", ["foot_lambert", "foot_lamberts"], &[], 3.426259, Imperial).exactness(Exactness::Rounded), // fL to cd/m^2
+        unit(Unit::Luminosity(Luminosity::Phot), "ph", ["phot", "phots"], &[], 10000.0, Cgs).exactness(Exactness::Placeholder), // lm/cm^2 to lm/m^2 (lux)
+        unit(Unit::Luminosity(Luminosity::Bril), "bril", ["bril", "brils"], &[], 1e-8, Other).exactness(Exactness::Placeholder), // 1 bril = 10^-8 lux
+        unit(Unit::Luminosity(Luminosity::Skot), "skot", ["skot", "skots"], &[], 1e-3, Other).exactness(Exactness::Placeholder), // 1 skot = 10^-3 lux
+        unit(Unit::Luminosity(Luminosity::WattPerSteradian), "W/sr", ["watt_per_steradian", "watts_per_steradian"], &[], 1.0, Si).exactness(Exactness::Placeholder), // Radiometric unit, not directly comparable to photometric. Placeholder for now.
+        unit(Unit::Luminosity(Luminosity::LumenPerWatt), "lm/W", ["lumen_per_watt", "lumens_per_watt"], &[], 1.0, Si).exactness(Exactness::Placeholder), // Luminous efficacy. Placeholder.
+        unit(Unit::Luminosity(Luminosity::LumenSecond), "lm·s", ["lumen_second", "lumen_seconds"], &["lm_s"], 1.0, Si).exactness(Exactness::Placeholder), // Luminous energy (Talbot). Placeholder.
+        unit(Unit::Luminosity(Luminosity::Talbot), "talbot", ["talbot", "talbots"], &[], 1.0, Other).exactness(Exactness::Placeholder), // Luminous energy. Placeholder.
+        unit(Unit::Luminosity(Luminosity::WattPerSteradianSquareMeter), "W/(sr·m²)", ["watt_per_steradian_square_meter", "watts_per_steradian_square_meter"], &[], 1.0, Si).exactness(Exactness::Placeholder), // Radiance. Placeholder.
+        unit(Unit::Luminosity(Luminosity::Candlepower), "cp", ["candlepower", "candlepowers"], &[], 1.0, Colloquial).exactness(Exactness::Placeholder), // Historically 1 cp = 1 cd
+        unit(Unit::Luminosity(Luminosity::PlanckLuminance), "planck_luminance", ["planck_luminance", "planck_luminances"], &[], 1.2227e59, Natural).exactness(Exactness::Measured), // Planck luminance in cd/m^2
+
+        // Radiation (Base: Gray)
+        unit(Unit::Radiation(Radiation::Gray), "Gy", ["gray", "grays"], &[], 1.0, Si),
+        unit(Unit::Radiation(Radiation::Decigray), "dGy", ["decigray", "decigrays"], &[], 0.1, Si),
+        unit(Unit::Radiation(Radiation::Centigray), "cGy", ["centigray", "centigrays"], &[], 0.01, Si),
+        unit(Unit::Radiation(Radiation::Milligray), "mGy", ["milligray", "milligrays"], &[], 0.001, Si),
+        unit(Unit::Radiation(Radiation::Microgray), "µGy", ["microgray", "micrograys"], &[], 1e-6, Si),
+        unit(Unit::Radiation(Radiation::Nanogray), "nGy", ["nanogray", "nanograys"], &[], 1e-9, Si),
+        unit(Unit::Radiation(Radiation::Picogray), "pGy", ["picogray", "picograys"], &[], 1e-12, Si),
+        unit(Unit::Radiation(Radiation::Rad), "rad", ["rad", "rads"], &[], 0.01, Other).reference(Some(Reference::NistSp811)),
+        unit(Unit::Radiation(Radiation::Sievert), "Sv", ["sievert", "sieverts"], &[], 1.0, Si).exactness(Exactness::Placeholder), // Equivalent dose, not absorbed dose. Simplified.
+        unit(Unit::Radiation(Radiation::Rem), "rem", ["rem", "rems"], &[], 0.01, Other).exactness(Exactness::Placeholder).reference(Some(Reference::NistSp811)), // Equivalent dose, not absorbed dose. Simplified.
+        unit(Unit::Radiation(Radiation::Becquerel), "Bq", ["becquerel", "becquerels"], &[], 1.0, Si).exactness(Exactness::Placeholder), // Activity. Not absorbed dose. Placeholder.
+        unit(Unit::Radiation(Radiation::Curie), "Ci", ["curie", "curies"], &[], 3.7e10, Other).exactness(Exactness::Placeholder).reference(Some(Reference::NistSp811)), // Activity. Not absorbed dose. Placeholder.
+        unit(Unit::Radiation(Radiation::Rutherford), "Rd", ["rutherford", "rutherfords"], &[], 1e6, Other).exactness(Exactness::Placeholder), // Activity. Not absorbed dose. Placeholder.
+        unit(Unit::Radiation(Radiation::Roentgen), "R", ["roentgen", "roentgens"], &[], 0.00877, Other).exactness(Exactness::Placeholder).reference(Some(Reference::NistSp811)), // Exposure to absorbed dose in air. Placeholder.
+        unit(Unit::Radiation(Radiation::CoulombPerKilogram), "C/kg", ["coulomb_per_kilogram", "coulombs_per_kilogram"], &[], 38.75, Si).exactness(Exactness::Placeholder), // Exposure to absorbed dose in air. Placeholder.
+        unit(Unit::Radiation(Radiation::ElectronvoltPerKilogram), "eV/kg", ["electronvolt_per_kilogram", "electronvolts_per_kilogram"], &[], 1.60218e-19, Other).exactness(Exactness::Rounded), // Energy per mass. Placeholder.
+        unit(Unit::Radiation(Radiation::IonPairPerKilogram), "ion_pair_per_kilogram", ["ion_pair_per_kilogram", "ion_pairs_per_kilogram"], &[], 1.0, Other).exactness(Exactness::Placeholder), // Placeholder.
+        unit(Unit::Radiation(Radiation::Rep), "rep", ["rep", "reps"], &[], 0.0093, Other).exactness(Exactness::Placeholder), // Roentgen equivalent physical. Placeholder.
+        unit(Unit::Radiation(Radiation::WattPerKilogram), "W/kg", ["watt_per_kilogram", "watts_per_kilogram"], &[], 1.0, Si).exactness(Exactness::Placeholder), // Absorbed dose rate. Placeholder.
+        unit(Unit::Radiation(Radiation::PlanckRadiation), "planck_radiation", ["planck_radiation", "planck_radiations"], &[], 1.0, Natural).exactness(Exactness::Placeholder), // Placeholder.
+
+        // Magnetic Flux (Base: Weber)
+        unit(Unit::MagneticFlux(MagneticFlux::Weber), "Wb", ["weber", "webers"], &[], 1.0, Si),
+        unit(Unit::MagneticFlux(MagneticFlux::Microweber), "µWb", ["microweber", "microwebers"], &[], 1e-6, Si),
+        unit(Unit::MagneticFlux(MagneticFlux::Nanoweber), "nWb", ["nanoweber", "nanowebers"], &[], 1e-9, Si),
+        unit(Unit::MagneticFlux(MagneticFlux::Picoweber), "pWb", ["picoweber", "picowebers"], &[], 1e-12, Si),
+        unit(Unit::MagneticFlux(MagneticFlux::Femtoweber), "fWb", ["femtoweber", "femtowebers"], &[], 1e-15, Si),
+        unit(Unit::MagneticFlux(MagneticFlux::Attoweber), "aWb", ["attoweber", "attowebers"], &[], 1e-18, Si),
+        unit(Unit::MagneticFlux(MagneticFlux::Maxwell), "Mx", ["maxwell", "maxwells"], &[], 1e-8, CgsEmu),
+        unit(Unit::MagneticFlux(MagneticFlux::Megaline), "megaline", ["megaline", "megalines"], &[], 1e-2, CgsEmu), // 1 Megaline = 10^6 Maxwell = 0.01 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::Kiloline), "kiloline", ["kiloline", "kilolines"], &[], 1e-5, CgsEmu), // 1 Kiloline = 10^3 Maxwell = 10^-5 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::Milliline), "milliline", ["milliline", "millilines"], &[], 1e-8, CgsEmu).exactness(Exactness::Placeholder), // 1 Milliline = 10^-3 Maxwell = 10^-8 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::GammaSquareCentimeter), "γ·cm²", ["gamma_square_centimeter", "gamma_square_centimeters"], &[], 1e-12, CgsEmu).exactness(Exactness::Placeholder), // 1 Gamma = 1 nT. 1 nT*cm^2 = 10^-12 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::TeslaSquareMeter), "T·m²", ["tesla_square_meter", "tesla_square_meters"], &["t_m2"], 1.0, Si), // 1 T*m^2 = 1 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::GaussSquareCentimeter), "G·cm²", ["gauss_square_centimeter", "gauss_square_centimeters"], &["g_cm2"], 1e-8, CgsEmu), // 1 G*cm^2 = 1 Maxwell = 10^-8 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::VoltSecond), "V·s", ["volt_second", "volt_seconds"], &["v_s"], 1.0, Si), // 1 V*s = 1 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::CoulombOhm), "C·Ω", ["coulomb_ohm", "coulomb_ohms"], &["c_ω"], 1.0, Si), // 1 C*Ohm = 1 V*s = 1 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::JoulePerAmpere), "J/A", ["joule_per_ampere", "joules_per_ampere"], &[], 1.0, Si), // 1 J/A = 1 V*s = 1 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::HenryAmpere), "H·A", ["henry_ampere", "henry_amperes"], &["h_a"], 1.0, Si), // 1 H*A = 1 V*s = 1 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::MagneticFluxQuantum), "Φ0", ["magnetic_flux_quantum", "magnetic_flux_quantums"], &[], 2.06783384e-15, Natural).exactness(Exactness::Rounded).reference(Some(Reference::Codata2022)),
+        unit(Unit::MagneticFlux(MagneticFlux::UnitPole), "unit_pole", ["unit_pole", "unit_poles"], &[], 1.256637e-7, CgsEmu).exactness(Exactness::Rounded), // 1 unit pole = 4pi * 10^-7 Weber
+        unit(Unit::MagneticFlux(MagneticFlux::PlanckFlux), "planck_flux", ["planck_flux", "planck_fluxes"], &[], 2.06783384e-15, Natural).exactness(Exactness::Measured), // Same as magnetic flux quantum
+    ]
+}
@@ -0,0 +1,283 @@
+use crate::enums::*;
+use crate::convert;
+
+// Proleptic Gregorian calendar arithmetic, used to resolve month and year spans
+// exactly relative to an anchor date instead of through the average factors.
+// Only the calendar units (month, calendar_year, decade, century, millennium) move
+// with the calendar; a plain "year" is the fixed 365.2425-day Gregorian year, and
+// julian_year, common_year and the rest keep their fixed lengths too.
+//
+// Dates far enough out to overflow the day or month counts give None.
+
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32, // 1..=12
+    pub day: u32,   // 1..=days_in_month
+}
+
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+impl Date {
+    pub fn new(year: i64, month: u32, day: u32) -> Option<Date> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    // Accepts "YYYY-MM-DD", with an optional leading '-' for years before 1 BCE.
+    pub fn parse(s: &str) -> Option<Date> {
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let mut parts = rest.split('-');
+        let year = parts.next()?.parse::<i64>().ok()?;
+        let month = parts.next()?.parse::<u32>().ok()?;
+        let day = parts.next()?.parse::<u32>().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Date::new(if negative { -year } else { year }, month, day)
+    }
+
+    // Days since 1970-01-01 (negative before it).
+    pub fn days_since_epoch(&self) -> Option<i64> {
+        let y = if self.month <= 2 { self.year.checked_sub(1)? } else { self.year };
+        let era = y.div_euclid(400);
+        let yoe = y.rem_euclid(400);
+        let m = self.month as i64;
+        let mp = if m > 2 { m - 3 } else { m + 9 };
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era.checked_mul(146097)?.checked_add(doe - 719468)
+    }
+
+    pub fn from_days_since_epoch(days: i64) -> Option<Date> {
+        let z = days.checked_add(719468)?;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        Some(Date { year, month, day })
+    }
+
+    // Calendar month addition; the day is clamped to the end of the target month,
+    // so 2026-01-31 + 1 month = 2026-02-28.
+    pub fn add_months(&self, months: i64) -> Option<Date> {
+        let total = self.year.checked_mul(12)?.checked_add(self.month as i64 - 1)?.checked_add(months)?;
+        let year = total.div_euclid(12);
+        let month = total.rem_euclid(12) as u32 + 1;
+        let day = self.day.min(days_in_month(year, month));
+        Some(Date { year, month, day })
+    }
+
+    pub fn add_days(&self, days: i64) -> Option<Date> {
+        Date::from_days_since_epoch(self.days_since_epoch()?.checked_add(days)?)
+    }
+}
+
+// `value` as an i64, if it is finite and in range rather than saturating.
+fn to_i64(value: f64) -> Option<i64> {
+    (value.abs() < i64::MAX as f64).then_some(value as i64)
+}
+
+// Number of calendar months spanned by one of the given time unit, or None if the
+// unit is not calendar-dependent. Time::Year is the calendar year; "year" itself
+// resolves to Time::GregorianYear, which isn't.
+fn calendar_months(unit: &Unit) -> Option<i64> {
+    match unit {
+        Unit::Time(Time::Month) => Some(1),
+        Unit::Time(Time::Year) => Some(12),
+        Unit::Time(Time::Decade) => Some(120),
+        Unit::Time(Time::Century) => Some(1200),
+        Unit::Time(Time::Millennium) => Some(12000),
+        _ => None,
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CalendarContext {
+    pub anchor: Date,
+}
+
+impl CalendarContext {
+    pub fn new(anchor: Date) -> CalendarContext {
+        CalendarContext { anchor }
+    }
+
+    // Whole days between the anchor and the anchor moved by `months` calendar months.
+    fn days_for_months(&self, months: i64) -> Option<i64> {
+        self.anchor.add_months(months)?.days_since_epoch()?.checked_sub(self.anchor.days_since_epoch()?)
+    }
+
+    // Seconds spanned by `months` calendar months starting at the anchor. A fractional
+    // part is taken as that fraction of the month following the whole months.
+    pub fn months_to_seconds(&self, months: f64) -> Option<f64> {
+        let whole = months.floor();
+        let frac = months - whole;
+        let whole = to_i64(whole)?;
+        let start = self.days_for_months(whole)?;
+        let end = self.days_for_months(whole.checked_add(1)?)?;
+        Some((start as f64 + frac * (end - start) as f64) * SECONDS_PER_DAY)
+    }
+
+    // Inverse of `months_to_seconds`.
+    pub fn seconds_to_months(&self, seconds: f64) -> Option<f64> {
+        let days = seconds / SECONDS_PER_DAY;
+        // Start from the average month length and walk to the bracketing month.
+        let mut whole = to_i64((days / 30.436875).floor())?;
+        while self.days_for_months(whole)? as f64 > days {
+            whole = whole.checked_sub(1)?;
+        }
+        while self.days_for_months(whole.checked_add(1)?)? as f64 <= days {
+            whole += 1;
+        }
+        let start = self.days_for_months(whole)? as f64;
+        let end = self.days_for_months(whole + 1)? as f64;
+        Some(whole as f64 + (days - start) / (end - start))
+    }
+
+    // Date reached after `quantity` of `unit` from the anchor, rounded down to whole days.
+    pub fn date_after(&self, quantity: f64, unit: &Unit) -> Option<Date> {
+        if let Some(months) = calendar_months(unit) {
+            let total = quantity * months as f64;
+            if total.fract() == 0.0 {
+                return self.anchor.add_months(to_i64(total)?);
+            }
+        }
+        let seconds = self.span_to_seconds(quantity, unit)?;
+        self.anchor.add_days(to_i64((seconds / SECONDS_PER_DAY).floor())?)
+    }
+
+    fn span_to_seconds(&self, quantity: f64, unit: &Unit) -> Option<f64> {
+        match calendar_months(unit) {
+            Some(months) => self.months_to_seconds(quantity * months as f64),
+            None => convert(quantity, unit, &Unit::Time(Time::Second)),
+        }
+    }
+
+    fn seconds_to_span(&self, seconds: f64, unit: &Unit) -> Option<f64> {
+        match calendar_months(unit) {
+            Some(months) => Some(self.seconds_to_months(seconds)? / months as f64),
+            None => convert(seconds, &Unit::Time(Time::Second), unit),
+        }
+    }
+}
+
+// Like `convert`, but months, years, decades, centuries and millennia are resolved
+// as calendar spans starting at the context's anchor date.
+pub fn convert_with_calendar(quantity: f64, from_unit: &Unit, to_unit: &Unit, context: &CalendarContext) -> Option<f64> {
+    let is_time = |unit: &Unit| matches!(unit, Unit::Time(_));
+    if !is_time(from_unit) || !is_time(to_unit) {
+        return convert(quantity, from_unit, to_unit);
+    }
+    if !quantity.is_finite() {
+        return None;
+    }
+    let seconds = context.span_to_seconds(quantity, from_unit)?;
+    context.seconds_to_span(seconds, to_unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Unit = Unit::Time(Time::Day);
+
+    fn date(s: &str) -> Date {
+        Date::parse(s).unwrap()
+    }
+
+    fn at(anchor: &str) -> CalendarContext {
+        CalendarContext::new(date(anchor))
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(is_leap_year(2024) && is_leap_year(2000) && is_leap_year(-4));
+        assert!(!is_leap_year(2023) && !is_leap_year(1900) && !is_leap_year(2100));
+        assert_eq!(Date::parse("2024-02-29"), Some(Date { year: 2024, month: 2, day: 29 }));
+        assert_eq!(Date::parse("2023-02-29"), None);
+        assert_eq!(Date::parse("2023-13-01"), None);
+        assert_eq!(Date::parse("-0044-03-15"), Some(Date { year: -44, month: 3, day: 15 }));
+    }
+
+    #[test]
+    fn days_since_epoch_round_trips() {
+        assert_eq!(date("1970-01-01").days_since_epoch(), Some(0));
+        assert_eq!(date("2000-03-01").days_since_epoch(), Some(11017));
+        assert_eq!(date("1969-12-31").days_since_epoch(), Some(-1));
+        for days in [-800_000, -1, 0, 59, 60, 10_957, 2_932_896] {
+            let date = Date::from_days_since_epoch(days).unwrap();
+            assert_eq!(date.days_since_epoch(), Some(days), "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn month_ends_are_clamped() {
+        assert_eq!(date("2026-01-31").add_months(1), Some(date("2026-02-28")));
+        assert_eq!(date("2024-01-31").add_months(1), Some(date("2024-02-29")));
+        assert_eq!(date("2026-03-31").add_months(-1), Some(date("2026-02-28")));
+        assert_eq!(date("2026-01-31").add_months(3), Some(date("2026-04-30")));
+        assert_eq!(date("2024-02-29").add_months(12), Some(date("2025-02-28")));
+        assert_eq!(date("2026-11-15").add_months(2), Some(date("2027-01-15")));
+    }
+
+    #[test]
+    fn months_follow_the_calendar() {
+        // February 2028 has 29 days, February 2026 has 28.
+        assert_eq!(at("2028-02-01").months_to_seconds(1.0), Some(29.0 * SECONDS_PER_DAY));
+        assert_eq!(at("2026-02-01").months_to_seconds(1.0), Some(28.0 * SECONDS_PER_DAY));
+        assert_eq!(at("2026-02-01").months_to_seconds(1.5), Some(43.5 * SECONDS_PER_DAY));
+        assert_eq!(at("2026-02-01").seconds_to_months(43.5 * SECONDS_PER_DAY), Some(1.5));
+        assert_eq!(at("2026-01-31").date_after(3.0, &Unit::Time(Time::Month)), Some(date("2026-04-30")));
+    }
+
+    #[test]
+    fn only_the_calendar_year_follows_the_calendar() {
+        let context = at("2024-01-01");
+        let calendar_year = convert_with_calendar(1.0, &Unit::Time(Time::Year), &DAY, &context);
+        assert_eq!(calendar_year, Some(366.0));
+        assert_eq!(convert_with_calendar(1.0, &Unit::Time(Time::Decade), &DAY, &context), Some(3653.0));
+        assert_eq!(convert_with_calendar(1.0, &Unit::Time(Time::GregorianYear), &DAY, &context), Some(365.2425));
+        assert_eq!(convert_with_calendar(1.0, &Unit::Time(Time::JulianYear), &DAY, &context), Some(365.25));
+        assert_eq!(crate::string_to_target("year"), Some(Unit::Time(Time::GregorianYear)));
+    }
+
+    #[test]
+    fn overflow_is_none() {
+        let far = Date { year: i64::MAX / 2, month: 6, day: 1 };
+        assert_eq!(far.add_months(1), None);
+        assert_eq!(far.days_since_epoch(), None);
+        assert_eq!(date("2026-01-01").add_months(i64::MAX), None);
+        assert_eq!(date("2026-01-01").add_days(i64::MAX), None);
+        assert_eq!(Date::from_days_since_epoch(i64::MAX), None);
+        let context = at("2026-01-01");
+        assert_eq!(context.months_to_seconds(1e30), None);
+        assert_eq!(context.months_to_seconds(f64::NAN), None);
+        assert_eq!(context.seconds_to_months(f64::MAX), None);
+        assert_eq!(context.date_after(1e300, &Unit::Time(Time::Year)), None);
+        assert_eq!(context.date_after(1e300, &DAY), None);
+        assert_eq!(convert_with_calendar(1e300, &Unit::Time(Time::Month), &DAY, &context), None);
+    }
+}
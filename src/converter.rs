@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{OnceLock, RwLock};
+
+use crate::enums::{Dimension, Unit};
+use crate::exact::Exact;
+use crate::numeric::factors;
+use crate::registry::{UnitRegistry, builtin_key};
+use crate::{get_conversion_factor_to_base, get_offset_to_base, get_unit_dimension};
+
+// Conversions between a fixed pair of units, resolved once for use in hot loops.
+// `convert` looks both units up and checks their dimensions on every call; a
+// `Converter` reduces the pair to (scale * x + offset) / divisor up front. Mappings
+// that aren't affine can be supplied as function pointers and composed with the rest.
+
+// Not comparable, as function pointers don't compare reliably.
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Affine { scale: f64, offset: f64, divisor: f64 },
+    Function { forward: fn(f64) -> f64, inverse: fn(f64) -> f64 },
+}
+
+impl Step {
+    fn apply(&self, value: f64) -> f64 {
+        match self {
+            Step::Affine { scale, offset, divisor } => affine(value, *scale, *offset, *divisor),
+            Step::Function { forward, .. } => forward(value),
+        }
+    }
+
+    fn inverse(&self) -> Step {
+        match *self {
+            Step::Affine { scale, offset, divisor } => Step::Affine { scale: divisor, offset: -offset, divisor: scale },
+            Step::Function { forward, inverse } => Step::Function { forward: inverse, inverse: forward },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Converter {
+    from: Unit,
+    to: Unit,
+    steps: Vec<Step>, // Applied in order; adjacent affine steps are merged
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConverterError {
+    DimensionMismatch { from: Unit, to: Unit },
+    MissingFactor(Unit), // A unit whose factor to base is the 0.0 placeholder
+    LengthMismatch { input: usize, output: usize },
+}
+
+impl fmt::Display for ConverterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registry = UnitRegistry::builtin();
+        match self {
+            ConverterError::DimensionMismatch { from, to } => write!(
+                f,
+                "cannot convert {} ({}) to {} ({})",
+                registry.name_of(from),
+                get_unit_dimension(from).name(),
+                registry.name_of(to),
+                get_unit_dimension(to).name()
+            ),
+            ConverterError::MissingFactor(unit) => write!(f, "no conversion factor is defined for {}", registry.name_of(unit)),
+            ConverterError::LengthMismatch { input, output } => {
+                write!(f, "{} values cannot be converted into space for {}", input, output)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConverterError {}
+
+impl Converter {
+    pub fn new(from: &Unit, to: &Unit) -> Result<Converter, ConverterError> {
+        if get_unit_dimension(from) != get_unit_dimension(to) {
+            return Err(ConverterError::DimensionMismatch { from: from.clone(), to: to.clone() });
+        }
+        let (from_factor, to_factor) = (get_conversion_factor_to_base(from), get_conversion_factor_to_base(to));
+        for (unit, factor) in [(from, from_factor), (to, to_factor)] {
+            if factor == 0.0 {
+                return Err(ConverterError::MissingFactor(unit.clone()));
+            }
+        }
+        // Built-in pairs are worked out once, as the exact arithmetic costs far more
+        // than the conversion itself.
+        let exact = match (builtin_key(from), builtin_key(to)) {
+            (Some(from_key), Some(to_key)) => {
+                let cache = EXACT_AFFINE.get_or_init(Default::default);
+                let cached = cache.read().unwrap_or_else(|error| error.into_inner()).get(&(from_key, to_key)).copied();
+                cached.unwrap_or_else(|| {
+                    let exact = exact_affine(from, to);
+                    cache.write().unwrap_or_else(|error| error.into_inner()).insert((from_key, to_key), exact);
+                    exact
+                })
+            }
+            _ => exact_affine(from, to),
+        };
+        let (scale, offset, divisor) = exact.unwrap_or_else(|| {
+            (from_factor / to_factor, (get_offset_to_base(from) - get_offset_to_base(to)) / to_factor, 1.0)
+        });
+        Ok(Converter { from: from.clone(), to: to.clone(), steps: vec![Step::Affine { scale, offset, divisor }] })
+    }
+
+    // A mapping that isn't affine, e.g. between a level in decibels and a power
+    // ratio. `inverse` must undo `forward`.
+    pub fn nonlinear(from: &Unit, to: &Unit, forward: fn(f64) -> f64, inverse: fn(f64) -> f64) -> Converter {
+        Converter { from: from.clone(), to: to.clone(), steps: vec![Step::Function { forward, inverse }] }
+    }
+
+    pub fn from(&self) -> &Unit {
+        &self.from
+    }
+
+    pub fn to(&self) -> &Unit {
+        &self.to
+    }
+
+    // The scale and offset, if the conversion is affine.
+    pub fn affine(&self) -> Option<(f64, f64)> {
+        match self.steps.as_slice() {
+            [Step::Affine { scale, offset, divisor }] => Some((scale / divisor, offset / divisor)),
+            _ => None,
+        }
+    }
+
+    pub fn apply(&self, value: f64) -> f64 {
+        self.steps.iter().fold(value, |value, step| step.apply(value))
+    }
+
+    pub fn apply_slice(&self, values: &mut [f64]) {
+        match self.steps.as_slice() {
+            [Step::Affine { scale, offset, divisor }] => affine_in_place(values, *scale, *offset, *divisor),
+            _ => values.iter_mut().for_each(|value| *value = self.apply(*value)),
+        }
+    }
+
+    // Converts `input` into `output`, which must be the same length.
+    pub fn apply_into(&self, input: &[f64], output: &mut [f64]) -> Result<(), ConverterError> {
+        if input.len() != output.len() {
+            return Err(ConverterError::LengthMismatch { input: input.len(), output: output.len() });
+        }
+        match self.steps.as_slice() {
+            [Step::Affine { scale, offset, divisor }] => affine_into(input, output, *scale, *offset, *divisor),
+            _ => output.iter_mut().zip(input).for_each(|(out, value)| *out = self.apply(*value)),
+        }
+        Ok(())
+    }
+
+    pub fn inverse(&self) -> Converter {
+        let steps = self.steps.iter().rev().map(Step::inverse).collect();
+        Converter { from: self.to.clone(), to: self.from.clone(), steps }
+    }
+
+    // This conversion followed by `next`. If `next` starts from a different unit of
+    // the same dimension, a conversion to it goes in between.
+    pub fn then(&self, next: &Converter) -> Result<Converter, ConverterError> {
+        if get_unit_dimension(&self.to) != get_unit_dimension(&next.from) {
+            return Err(ConverterError::DimensionMismatch { from: self.to.clone(), to: next.from.clone() });
+        }
+        // Two unit conversions make another one, which is worked out afresh so that
+        // it stays exact.
+        if self.affine().is_some() && next.affine().is_some() {
+            return Converter::new(&self.from, &next.to);
+        }
+        let mut steps = self.steps.clone();
+        if next.from != self.to {
+            steps.extend(Converter::new(&self.to, &next.from)?.steps);
+        }
+        steps.extend(next.steps.iter().copied());
+        Ok(Converter { from: self.from.clone(), to: next.to.clone(), steps: merge(steps) })
+    }
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+type Key = (Dimension, usize);
+type Affine = Option<(f64, f64, f64)>; // (scale, offset, divisor)
+
+static EXACT_AFFINE: OnceLock<RwLock<HashMap<(Key, Key), Affine>>> = OnceLock::new();
+
+// (x * from_factor + from_offset - to_offset) / to_factor, worked out exactly so
+// that each part is rounded once. None if either unit has no exact definition, or
+// the exact arithmetic overflows.
+fn exact_affine(from: &Unit, to: &Unit) -> Affine {
+    let ((from_factor, from_offset), (to_factor, to_offset)) = (factors(from)?, factors(to)?);
+    let (scale, offset) = (from_factor.checked_div(to_factor)?, from_offset.checked_sub(to_offset)?.checked_div(to_factor)?);
+    // A scale that isn't whole is scaled to a whole number along with the offset,
+    // and the division comes last, so whole quantities are rounded once: 12 in is
+    // 12 * 254 / 10 = 304.8 mm, where 12 * 25.4 would be 304.79999999999995, and
+    // 37 °C is (37 * 18 + 320) / 10 = 98.6 °F rather than 98.60000000000001.
+    let whole = |exact: Exact| {
+        let (num, den, ten, pi) = exact.parts();
+        let whole = num.checked_mul(10i128.checked_pow(u32::try_from(ten).ok()?)?)?;
+        (pi == 0 && den == 1 && whole.unsigned_abs() <= 1 << 53).then_some(whole as f64)
+    };
+    let denominator = |exact: Exact| {
+        let (_, den, ten, _) = exact.parts();
+        den.checked_mul(10i128.checked_pow((-ten).max(0).unsigned_abs())?)
+    };
+    let whole_parts = || {
+        let (a, b) = (denominator(scale)?, denominator(offset)?);
+        let divisor = Exact::integer(a.checked_mul(b / gcd(a, b))?);
+        Some((whole(scale.checked_mul(divisor)?)?, whole(offset.checked_mul(divisor)?)?, whole(divisor)?))
+    };
+    let ratio = if whole(scale).is_some() { None } else { whole_parts() };
+    Some(ratio.unwrap_or((scale.to_f64(), offset.to_f64(), 1.0)))
+}
+
+// (value * scale + offset) / divisor. A whole-number scale can overflow where the
+// result wouldn't, and then the division goes first.
+fn affine(value: f64, scale: f64, offset: f64, divisor: f64) -> f64 {
+    let whole = value * scale + offset;
+    if whole.is_finite() { whole / divisor } else { value * (scale / divisor) + offset / divisor }
+}
+
+// Values per chunk. The fixed-length inner loops have no branches and no bounds
+// checks, so they compile to SIMD multiplies and adds at any vector width up to
+// 512 bits; the remainder is done one by one. A divisor of 1 is left out, as
+// dividing by it changes nothing but costs more than the multiply and add.
+const CHUNK: usize = 8;
+
+fn affine_into(input: &[f64], output: &mut [f64], scale: f64, offset: f64, divisor: f64) {
+    if divisor == 1.0 {
+        return affine_into_with(input, output, |value| value * scale + offset);
+    }
+    affine_into_with(input, output, |value| affine(value, scale, offset, divisor))
+}
+
+fn affine_into_with(input: &[f64], output: &mut [f64], step: impl Fn(f64) -> f64) {
+    let mut inputs = input.chunks_exact(CHUNK);
+    let mut outputs = output.chunks_exact_mut(CHUNK);
+    for (values, out) in (&mut inputs).zip(&mut outputs) {
+        for (out, value) in out.iter_mut().zip(values) {
+            *out = step(*value);
+        }
+    }
+    for (out, value) in outputs.into_remainder().iter_mut().zip(inputs.remainder()) {
+        *out = step(*value);
+    }
+}
+
+fn affine_in_place(values: &mut [f64], scale: f64, offset: f64, divisor: f64) {
+    if divisor == 1.0 {
+        return affine_in_place_with(values, |value| value * scale + offset);
+    }
+    affine_in_place_with(values, |value| affine(value, scale, offset, divisor))
+}
+
+fn affine_in_place_with(values: &mut [f64], step: impl Fn(f64) -> f64) {
+    let mut chunks = values.chunks_exact_mut(CHUNK);
+    for chunk in &mut chunks {
+        for value in chunk.iter_mut() {
+            *value = step(*value);
+        }
+    }
+    for value in chunks.into_remainder() {
+        *value = step(*value);
+    }
+}
+
+// Folds runs of affine steps into one: ((x * a + b) / c * d + e) / f
+// = (x * (a * d / c) + (b * d / c + e)) / f.
+fn merge(steps: Vec<Step>) -> Vec<Step> {
+    let mut merged: Vec<Step> = Vec::with_capacity(steps.len());
+    for step in steps {
+        match (merged.last_mut(), step) {
+            (
+                Some(Step::Affine { scale, offset, divisor }),
+                Step::Affine { scale: next_scale, offset: next_offset, divisor: next_divisor },
+            ) => {
+                *offset = *offset * next_scale / *divisor + next_offset;
+                *scale = *scale * next_scale / *divisor;
+                *divisor = next_divisor;
+            }
+            _ => merged.push(step),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{CustomUnit, Dimension, Length, Mass, Temperature};
+
+    const METER: Unit = Unit::Length(Length::Meter);
+    const FOOT: Unit = Unit::Length(Length::Foot);
+    const INCH: Unit = Unit::Length(Length::Inch);
+    const CELSIUS: Unit = Unit::Temperature(Temperature::Celsius);
+    const FAHRENHEIT: Unit = Unit::Temperature(Temperature::Fahrenheit);
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * b.abs().max(1.0)
+    }
+
+    fn cube(x: f64) -> f64 {
+        x * x * x
+    }
+
+    #[test]
+    fn reduces_a_pair_to_scale_and_offset() {
+        assert_eq!(Converter::new(&FOOT, &INCH).unwrap().affine(), Some((12.0, 0.0)));
+        assert_eq!(Converter::new(&CELSIUS, &FAHRENHEIT).unwrap().affine(), Some((1.8, 32.0)));
+        assert_eq!(Converter::new(&FOOT, &METER).unwrap().apply(10.0), 3.048);
+        assert_eq!(Converter::new(&INCH, &Unit::Length(Length::Millimeter)).unwrap().apply(12.0), 304.8);
+        // 1e306 * 3048 overflows on the way to 3.048e305.
+        assert!(close(Converter::new(&FOOT, &METER).unwrap().apply(1e306), 3.048e305));
+        let error = Converter::new(&METER, &Unit::Mass(Mass::Kilogram)).unwrap_err();
+        assert_eq!(error.to_string(), "cannot convert meter (length) to kilogram (mass)");
+        let nothing = Unit::Custom(CustomUnit { name: "nothing".to_string(), dimension: Dimension::Length, factor: 0.0, offset: 0.0 });
+        assert_eq!(Converter::new(&nothing, &METER).unwrap_err(), ConverterError::MissingFactor(nothing));
+    }
+
+    #[test]
+    fn inverses_undo_each_step() {
+        let forward = Converter::new(&CELSIUS, &FAHRENHEIT).unwrap();
+        let back = forward.inverse();
+        assert_eq!((back.from(), back.to()), (&FAHRENHEIT, &CELSIUS));
+        assert!(close(back.apply(98.6), 37.0));
+        let cubed = Converter::nonlinear(&METER, &METER, cube, f64::cbrt);
+        assert_eq!(cubed.affine(), None);
+        assert!(close(cubed.inverse().apply(27.0), 3.0));
+    }
+
+    #[test]
+    fn chains_through_intermediate_units() {
+        let feet = Converter::new(&FOOT, &METER).unwrap();
+        let inches = feet.then(&Converter::new(&METER, &INCH).unwrap()).unwrap();
+        // Two unit conversions are redone as one, exactly.
+        assert_eq!((inches.from(), inches.to(), inches.affine()), (&FOOT, &INCH, Some((12.0, 0.0))));
+        // Ending in inches, a conversion to meters goes in between.
+        let cubed = Converter::nonlinear(&METER, &METER, cube, f64::cbrt);
+        let chained = Converter::new(&FOOT, &INCH).unwrap().then(&cubed).unwrap();
+        assert_eq!(chained.to(), &METER);
+        assert!(close(chained.apply(10.0), cube(3.048)));
+        assert!(close(chained.inverse().apply(cube(3.048)), 10.0));
+        let error = feet.then(&Converter::new(&CELSIUS, &FAHRENHEIT).unwrap()).unwrap_err();
+        assert_eq!(error, ConverterError::DimensionMismatch { from: METER, to: CELSIUS });
+    }
+
+    #[test]
+    fn slices_match_one_at_a_time() {
+        // Longer than a chunk, with a remainder.
+        let input: Vec<f64> = (0..19).map(|i| i as f64 * 1.5 - 7.0).collect();
+        for converter in [Converter::new(&CELSIUS, &FAHRENHEIT).unwrap(), Converter::nonlinear(&METER, &METER, cube, f64::cbrt)] {
+            let expected: Vec<f64> = input.iter().map(|value| converter.apply(*value)).collect();
+            let mut output = vec![0.0; input.len()];
+            converter.apply_into(&input, &mut output).unwrap();
+            assert_eq!(output, expected);
+            let mut values = input.clone();
+            converter.apply_slice(&mut values);
+            assert_eq!(values, expected);
+        }
+        let mut short = [0.0; 3];
+        assert_eq!(
+            crate::convert_slice(&input, &FOOT, &INCH, &mut short),
+            Err(ConverterError::LengthMismatch { input: 19, output: 3 })
+        );
+        let mut values = [1.0, 2.0];
+        crate::convert_slice_in_place(&mut values, &FOOT, &INCH).unwrap();
+        assert_eq!(values, [12.0, 24.0]);
+    }
+}
@@ -0,0 +1,350 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::enums::*;
+use crate::format::FormatOptions;
+use crate::parse::{parse_number, parse_quantity};
+use crate::registry::{LookupError, UnitRegistry};
+use crate::{Quantity, get_unit_dimension};
+
+// Streaming conversion of CSV columns, one record in memory at a time. Fields that
+// are not converted are copied through byte for byte, quotes included.
+//
+// A column's source unit is given explicitly ("distance_km:km=mi"), taken from a
+// header suffix ("distance_km", "distance (km)", "distance [km]") or, failing
+// that, read from every cell ("12.3 kg").
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ColumnSpec {
+    pub column: String,
+    pub from: Option<Unit>, // None to infer from the header or the cells
+    pub to: Unit,
+    pub to_spelling: String, // As written, used when renaming the header
+}
+
+#[derive(Debug)]
+pub enum CsvError {
+    Io(io::Error),
+    InvalidSpec(String),
+    Unit(LookupError), // A unit in a column spec
+    DimensionMismatch(String), // A column spec whose units cannot be converted
+    MissingColumn(String),
+    UnterminatedQuote { line: usize },
+    Cell { line: usize, column: String, message: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Io(err) => write!(f, "{}", err),
+            CsvError::InvalidSpec(message) => write!(f, "invalid column spec: {}", message),
+            CsvError::Unit(err) => write!(f, "{}", err),
+            CsvError::DimensionMismatch(spec) => write!(f, "{:?} converts between different dimensions", spec),
+            CsvError::MissingColumn(column) => write!(f, "no column named {:?}", column),
+            CsvError::UnterminatedQuote { line } => write!(f, "line {}: unterminated quoted field", line),
+            CsvError::Cell { line, column, message } => write!(f, "line {}, column {:?}: {}", line, column, message),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<io::Error> for CsvError {
+    fn from(err: io::Error) -> CsvError {
+        CsvError::Io(err)
+    }
+}
+
+impl ColumnSpec {
+    // "name:from=to", or "name=to" to infer the source unit.
+    pub fn parse(spec: &str, registry: &UnitRegistry) -> Result<ColumnSpec, CsvError> {
+        let invalid = |message: String| CsvError::InvalidSpec(message);
+        let (left, to_spelling) = spec.rsplit_once('=').ok_or_else(|| invalid(format!("{:?} has no '=<unit>'", spec)))?;
+        let (column, from) = match left.rsplit_once(':') {
+            Some((column, from)) => (column, Some(from.trim())),
+            None => (left, None),
+        };
+        let resolve = |name: &str, dimension| registry.resolve_in(name, dimension).map_err(CsvError::Unit);
+        let to_spelling = to_spelling.trim();
+        let from = match from {
+            Some(from) => Some(resolve(from, None)?),
+            None => None,
+        };
+        let to = resolve(to_spelling, from.as_ref().map(get_unit_dimension))?;
+        if column.is_empty() {
+            return Err(invalid(format!("{:?} has no column name", spec)));
+        }
+        if let Some(from) = &from
+            && get_unit_dimension(from) != get_unit_dimension(&to)
+        {
+            return Err(CsvError::DimensionMismatch(spec.to_string()));
+        }
+        Ok(ColumnSpec { column: column.to_string(), from, to, to_spelling: to_spelling.to_string() })
+    }
+}
+
+// One field as it appeared in the input, quotes and all.
+struct Field {
+    raw: String,
+}
+
+impl Field {
+    fn is_quoted(&self) -> bool {
+        self.raw.starts_with('"')
+    }
+
+    fn value(&self) -> String {
+        if self.is_quoted() {
+            let inner = self.raw.strip_prefix('"').unwrap_or(&self.raw);
+            let inner = inner.strip_suffix('"').unwrap_or(inner);
+            inner.replace("\"\"", "\"")
+        } else {
+            self.raw.clone()
+        }
+    }
+}
+
+struct Record {
+    fields: Vec<Field>,
+    terminator: String, // "\n", "\r\n" or "" at end of input
+    line: usize,        // Line the record starts on
+}
+
+struct Reader<R> {
+    input: R,
+    delimiter: char,
+    line: usize,
+}
+
+impl<R: BufRead> Reader<R> {
+    // Reads one record, continuing across lines while inside a quoted field.
+    fn next_record(&mut self) -> Result<Option<Record>, CsvError> {
+        let start = self.line + 1;
+        let mut text = String::new();
+        loop {
+            let read = self.input.read_line(&mut text)?;
+            if read == 0 {
+                if text.is_empty() {
+                    return Ok(None);
+                }
+                if text.matches('"').count() % 2 == 1 {
+                    return Err(CsvError::UnterminatedQuote { line: start });
+                }
+                break;
+            }
+            self.line += 1;
+            if text.matches('"').count().is_multiple_of(2) {
+                break;
+            }
+        }
+        let body = text.trim_end_matches(['\n', '\r']);
+        let terminator = text[body.len()..].to_string();
+        Ok(Some(Record { fields: split_fields(body, self.delimiter), terminator, line: start }))
+    }
+}
+
+fn split_fields(body: &str, delimiter: char) -> Vec<Field> {
+    let mut fields = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in body.chars() {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        if c == delimiter && !quoted {
+            fields.push(Field { raw: std::mem::take(&mut current) });
+        } else {
+            current.push(c);
+        }
+    }
+    fields.push(Field { raw: current });
+    fields
+}
+
+// Writes `value` back in the style of the field it replaces.
+fn encode(value: &str, was_quoted: bool, delimiter: char) -> String {
+    if was_quoted || value.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// The unit in a header such as "distance_km", "distance (km)" or "distance [km]",
+// as (text before, unit, text after).
+fn header_unit(header: &str) -> Option<(&str, &str, &str)> {
+    let trimmed = header.trim_end();
+    for (open, close) in [('(', ')'), ('[', ']')] {
+        if let Some(rest) = trimmed.strip_suffix(close)
+            && let Some(at) = rest.rfind(open)
+        {
+            let unit = &rest[at + 1..];
+            return Some((&header[..at + 1], unit, &header[rest.len()..]));
+        }
+    }
+    let at = header.rfind('_')?;
+    Some((&header[..at + 1], &header[at + 1..], ""))
+}
+
+enum Source {
+    Unit(Unit),
+    PerCell,
+}
+
+struct Column {
+    index: usize,
+    name: String, // As in the input, before any renaming
+    source: Source,
+    to: Unit,
+}
+
+// Converts the columns named in `specs` from `input` to `output`. The first record
+// is the header.
+pub fn convert_csv<R: BufRead, W: Write>(
+    input: R,
+    mut output: W,
+    specs: &[ColumnSpec],
+    registry: &UnitRegistry,
+    options: &FormatOptions,
+    delimiter: char,
+) -> Result<(), CsvError> {
+    let mut reader = Reader { input, delimiter, line: 0 };
+    let Some(mut header) = reader.next_record()? else { return Ok(()) };
+
+    let mut columns = vec![];
+    for spec in specs {
+        let index = header
+            .fields
+            .iter()
+            .position(|field| field.value() == spec.column)
+            .ok_or_else(|| CsvError::MissingColumn(spec.column.clone()))?;
+        let name = header.fields[index].value();
+        // A header unit is only trusted if it can actually be converted to the target.
+        let suffix = header_unit(&name).and_then(|(before, unit, after)| {
+            let unit = registry.resolve(unit).ok()?;
+            (get_unit_dimension(&unit) == get_unit_dimension(&spec.to)).then_some((before, unit, after))
+        });
+        let source = match (&spec.from, &suffix) {
+            (Some(from), _) => Source::Unit(from.clone()),
+            (None, Some((_, unit, _))) => Source::Unit(unit.clone()),
+            (None, None) => Source::PerCell,
+        };
+        if let (Source::Unit(from), Some((before, unit, after))) = (&source, &suffix)
+            && from == unit
+        {
+            let renamed = format!("{}{}{}", before, spec.to_spelling, after);
+            header.fields[index].raw = encode(&renamed, header.fields[index].is_quoted(), delimiter);
+        }
+        columns.push(Column { index, name, source, to: spec.to.clone() });
+    }
+    write_record(&mut output, &header, delimiter)?;
+
+    let cell_options = FormatOptions { space: true, ..*options };
+    while let Some(mut record) = reader.next_record()? {
+        for column in &columns {
+            let Some(field) = record.fields.get(column.index) else { continue };
+            let value = field.value();
+            if value.trim().is_empty() {
+                continue;
+            }
+            let cell_error = |message: String| CsvError::Cell {
+                line: record.line,
+                column: column.name.clone(),
+                message,
+            };
+            let converted = match &column.source {
+                Source::Unit(from) => {
+                    let (number, uncertainty) = parse_number(&value).ok_or_else(|| cell_error(format!("invalid number {:?}", value)))?;
+                    let quantity = Quantity { value: number, unit: from.clone(), uncertainty };
+                    let result = quantity.convert_to(&column.to).ok_or_else(|| cell_error("conversion failed".to_string()))?;
+                    match result.uncertainty {
+                        Some(uncertainty) => options.format_uncertain(result.value, uncertainty),
+                        None => options.format(result.value),
+                    }
+                }
+                Source::PerCell => {
+                    let quantity = parse_quantity(&value, registry).map_err(|err| cell_error(err.to_string()))?;
+                    let result = quantity.convert_to(&column.to).ok_or_else(|| cell_error(format!("cannot convert {:?}", value)))?;
+                    cell_options.format_uncertain_quantity(result.value, result.uncertainty, &registry.symbol_of(&column.to))
+                }
+            };
+            let was_quoted = field.is_quoted();
+            record.fields[column.index].raw = encode(&converted, was_quoted, delimiter);
+        }
+        write_record(&mut output, &record, delimiter)?;
+    }
+    output.flush()?;
+    Ok(())
+}
+
+fn write_record<W: Write>(output: &mut W, record: &Record, delimiter: char) -> io::Result<()> {
+    for (i, field) in record.fields.iter().enumerate() {
+        if i > 0 {
+            write!(output, "{}", delimiter)?;
+        }
+        output.write_all(field.raw.as_bytes())?;
+    }
+    output.write_all(record.terminator.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str, specs: &[&str], delimiter: char) -> Result<String, CsvError> {
+        let registry = UnitRegistry::builtin();
+        let specs: Vec<ColumnSpec> = specs.iter().map(|spec| ColumnSpec::parse(spec, registry).unwrap()).collect();
+        let mut output = vec![];
+        convert_csv(input.as_bytes(), &mut output, &specs, registry, &FormatOptions::default(), delimiter)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn parses_column_specs() {
+        let registry = UnitRegistry::builtin();
+        let spec = ColumnSpec::parse("distance:km=mi", registry).unwrap();
+        assert_eq!((spec.column.as_str(), spec.from, spec.to), ("distance", Some(Unit::Length(Length::Kilometer)), Unit::Length(Length::Mile)));
+        assert_eq!(ColumnSpec::parse("weight=lb", registry).unwrap().from, None);
+        assert!(matches!(ColumnSpec::parse("weight", registry), Err(CsvError::InvalidSpec(_))));
+        assert!(matches!(ColumnSpec::parse("=lb", registry), Err(CsvError::InvalidSpec(_))));
+        assert!(matches!(ColumnSpec::parse("weight:kg=mi", registry), Err(CsvError::DimensionMismatch(_))));
+        assert!(matches!(ColumnSpec::parse("weight=zzz", registry), Err(CsvError::Unit(_))));
+    }
+
+    #[test]
+    fn finds_units_in_specs_headers_and_cells() {
+        let output = run("id,distance_km,note\n1,8.04672,a\n2,,b\n", &["distance_km=mi"], ',').unwrap();
+        assert_eq!(output, "id,distance_mi,note\n1,5,a\n2,,b\n");
+        let output = run("distance (km);weight [g]\r\n1.5;1500\r\n", &["distance (km)=m", "weight [g]=kg"], ';').unwrap();
+        assert_eq!(output, "distance (m);weight [kg]\r\n1500;1.5\r\n");
+        let output = run("reading\n12 in\n1 ft\n", &["reading=cm"], ',').unwrap();
+        assert_eq!(output, "reading\n30.48 cm\n30.48 cm\n");
+        let output = run("size\n2\n", &["size:km=m"], ',').unwrap();
+        assert_eq!(output, "size\n2000\n");
+    }
+
+    #[test]
+    fn keeps_quoting_and_untouched_fields() {
+        let input = "name,length_m\n\"Smith, J.\",\"1000\"\n\"multi\nline \"\"quote\"\"\",2000";
+        let output = run(input, &["length_m=km"], ',').unwrap();
+        assert_eq!(output, "name,length_km\n\"Smith, J.\",\"1\"\n\"multi\nline \"\"quote\"\"\",2");
+        let output = run("reading\n1234567 m\n", &["reading=km"], ',').unwrap();
+        assert_eq!(output, "reading\n1234.567 km\n");
+        // A value that needs quoting in the output gets it.
+        let grouped = FormatOptions { group_digits: true, ..FormatOptions::default() };
+        let registry = UnitRegistry::builtin();
+        let mut output = vec![];
+        convert_csv("d_km\n1000\n".as_bytes(), &mut output, &[ColumnSpec::parse("d_km=m", registry).unwrap()], registry, &grouped, ',').unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "d_m\n\"1,000,000\"\n");
+    }
+
+    #[test]
+    fn reports_bad_input_by_line() {
+        assert!(matches!(run("a,b\n1,2\n", &["c=m"], ','), Err(CsvError::MissingColumn(column)) if column == "c"));
+        assert!(matches!(run("d_km\n\"1\n", &["d_km=m"], ','), Err(CsvError::UnterminatedQuote { line: 2 })));
+        let error = run("d_km\n1\nx\n", &["d_km=m"], ',').unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column \"d_km\": invalid number \"x\"");
+        let error = run("reading\n5 kg\n", &["reading=m"], ',').unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column \"reading\": cannot convert \"5 kg\"");
+    }
+}
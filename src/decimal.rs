@@ -0,0 +1,490 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::exact::{Exact, decimal_string};
+
+// Arbitrary-precision decimal numbers, for results that need more than the 15-17
+// significant digits of an f64 (metrology, billing). A `Context` says how many
+// significant digits a result keeps and how it is rounded; operands are used as
+// they are.
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Rounding {
+    HalfEven, // Ties to the even digit (IEEE 754's default, "banker's rounding")
+    HalfUp,   // Ties away from zero
+    TowardZero,
+}
+
+impl Rounding {
+    pub fn from_name(name: &str) -> Option<Rounding> {
+        match name.to_lowercase().replace('_', "-").as_str() {
+            "half-even" | "even" => Some(Rounding::HalfEven),
+            "half-up" => Some(Rounding::HalfUp),
+            "toward-zero" | "down" | "truncate" => Some(Rounding::TowardZero),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Context {
+    pub digits: usize, // Significant digits kept in results
+    pub rounding: Rounding,
+}
+
+// Digits carried by intermediate results, beyond the context's.
+const GUARD_DIGITS: usize = 5;
+
+impl Default for Context {
+    // The precision of IEEE 754 decimal128.
+    fn default() -> Context {
+        Context { digits: 34, rounding: Rounding::HalfEven }
+    }
+}
+
+impl Context {
+    pub fn new(digits: usize, rounding: Rounding) -> Context {
+        Context { digits: digits.max(1), rounding }
+    }
+
+    fn guarded(&self) -> Context {
+        Context { digits: self.digits + GUARD_DIGITS, rounding: Rounding::HalfEven }
+    }
+}
+
+// An unsigned integer as decimal digits, least significant first, with no leading
+// zeros (zero has no digits).
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Natural(Vec<u8>);
+
+impl Natural {
+    fn zero() -> Natural {
+        Natural(vec![])
+    }
+
+    fn from_u128(mut n: u128) -> Natural {
+        let mut digits = vec![];
+        while n > 0 {
+            digits.push((n % 10) as u8);
+            n /= 10;
+        }
+        Natural(digits)
+    }
+
+    fn trimmed(mut digits: Vec<u8>) -> Natural {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        Natural(digits)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn digit(&self, index: usize) -> u8 {
+        self.0.get(index).copied().unwrap_or(0)
+    }
+
+    fn compare(&self, other: &Natural) -> Ordering {
+        self.len().cmp(&other.len()).then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+    }
+
+    fn add(&self, other: &Natural) -> Natural {
+        let mut digits = Vec::with_capacity(self.len().max(other.len()) + 1);
+        let mut carry = 0;
+        for i in 0..self.len().max(other.len()) {
+            let sum = self.digit(i) + other.digit(i) + carry;
+            digits.push(sum % 10);
+            carry = sum / 10;
+        }
+        digits.push(carry);
+        Natural::trimmed(digits)
+    }
+
+    // `self` - `other`, where `other` is not larger.
+    fn sub(&self, other: &Natural) -> Natural {
+        let mut digits = Vec::with_capacity(self.len());
+        let mut borrow = 0;
+        for i in 0..self.len() {
+            let (a, b) = (self.digit(i), other.digit(i) + borrow);
+            if a >= b {
+                digits.push(a - b);
+                borrow = 0;
+            } else {
+                digits.push(a + 10 - b);
+                borrow = 1;
+            }
+        }
+        Natural::trimmed(digits)
+    }
+
+    fn mul(&self, other: &Natural) -> Natural {
+        if self.is_zero() || other.is_zero() {
+            return Natural::zero();
+        }
+        let mut sums = vec![0u64; self.len() + other.len()];
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in other.0.iter().enumerate() {
+                sums[i + j] += u64::from(a * b);
+            }
+        }
+        let mut digits = Vec::with_capacity(sums.len() + 1);
+        let mut carry = 0;
+        for sum in sums {
+            let total = sum + carry;
+            digits.push((total % 10) as u8);
+            carry = total / 10;
+        }
+        while carry > 0 {
+            digits.push((carry % 10) as u8);
+            carry /= 10;
+        }
+        Natural::trimmed(digits)
+    }
+
+    // `self` * 10^places.
+    fn shift(&self, places: usize) -> Natural {
+        if self.is_zero() {
+            return Natural::zero();
+        }
+        let mut digits = vec![0; places];
+        digits.extend_from_slice(&self.0);
+        Natural(digits)
+    }
+
+    // Quotient and remainder, by long division; `divisor` must not be zero.
+    fn div_rem(&self, divisor: &Natural) -> (Natural, Natural) {
+        let mut quotient = vec![0; self.len()];
+        let mut remainder = Natural::zero();
+        for i in (0..self.len()).rev() {
+            remainder = Natural::trimmed([&[self.0[i]][..], &remainder.0].concat());
+            while remainder.compare(divisor) != Ordering::Less {
+                remainder = remainder.sub(divisor);
+                quotient[i] += 1;
+            }
+        }
+        (Natural::trimmed(quotient), remainder)
+    }
+}
+
+impl fmt::Display for Natural {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let digits: String = self.0.iter().rev().map(|digit| char::from(b'0' + digit)).collect();
+        write!(f, "{}", digits)
+    }
+}
+
+// coefficient * 10^exponent, with no trailing zeros in the coefficient.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Decimal {
+    negative: bool,
+    coefficient: Natural,
+    exponent: i64,
+}
+
+impl Decimal {
+    fn new(negative: bool, coefficient: Natural, mut exponent: i64) -> Decimal {
+        if coefficient.is_zero() {
+            return Decimal::zero();
+        }
+        let zeros = coefficient.0.iter().take_while(|digit| **digit == 0).count();
+        exponent += zeros as i64;
+        Decimal { negative, coefficient: Natural(coefficient.0[zeros..].to_vec()), exponent }
+    }
+
+    pub fn zero() -> Decimal {
+        Decimal { negative: false, coefficient: Natural::zero(), exponent: 0 }
+    }
+
+    pub fn integer(n: i128) -> Decimal {
+        Decimal::new(n < 0, Natural::from_u128(n.unsigned_abs()), 0)
+    }
+
+    // A decimal literal of any length: "1609.344", "-0.5", "6.02214076e23".
+    pub fn parse(text: &str) -> Option<Decimal> {
+        let text = text.trim();
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(at) => (&text[..at], text[at + 1..].parse::<i64>().ok()?),
+            None => (text, 0),
+        };
+        let (negative, mantissa) = match mantissa.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, mantissa.strip_prefix('+').unwrap_or(mantissa)),
+        };
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return None;
+        }
+        let mut digits = vec![];
+        for c in whole.chars().chain(fraction.chars()).rev() {
+            digits.push(c.to_digit(10)? as u8);
+        }
+        let exponent = exponent.checked_sub(fraction.len() as i64)?;
+        Some(Decimal::new(negative, Natural::trimmed(digits), exponent))
+    }
+
+    // The shortest decimal that rounds to `value`.
+    pub fn from_f64(value: f64) -> Option<Decimal> {
+        if !value.is_finite() {
+            return None;
+        }
+        Decimal::parse(&format!("{:e}", value))
+    }
+
+    // Exact unless the value is a repeating decimal or involves π.
+    pub fn from_exact(exact: Exact, context: &Context) -> Decimal {
+        let (num, den, ten, pi) = exact.parts();
+        let guarded = context.guarded();
+        let mut value = Decimal::new(num < 0, Natural::from_u128(num.unsigned_abs()), i64::from(ten));
+        if den != 1 {
+            value = value.div(&Decimal::integer(den), &guarded).unwrap_or_else(Decimal::zero);
+        }
+        if pi != 0 {
+            let pi_value = Decimal::pi(&guarded);
+            for _ in 0..pi.unsigned_abs() {
+                value = if pi > 0 { value.mul(&pi_value, &guarded) } else { value.div(&pi_value, &guarded).unwrap_or_else(Decimal::zero) };
+            }
+        }
+        value.round(context)
+    }
+
+    // π to the context's precision, from Machin's formula
+    // π = 16 atan(1/5) - 4 atan(1/239).
+    pub fn pi(context: &Context) -> Decimal {
+        let places = context.digits + GUARD_DIGITS;
+        let one = Natural::from_u128(1).shift(places);
+        let pi = atan_inverse(5, &one).mul(&Natural::from_u128(16)).sub(&atan_inverse(239, &one).mul(&Natural::from_u128(4)));
+        Decimal::new(false, pi, -(places as i64)).round(context)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let sign = if self.negative { "-" } else { "" };
+        format!("{}{}e{}", sign, self.coefficient, self.exponent).parse::<f64>().unwrap_or(f64::NAN)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficient.is_zero()
+    }
+
+    pub fn neg(&self) -> Decimal {
+        if self.is_zero() {
+            return self.clone();
+        }
+        Decimal { negative: !self.negative, ..self.clone() }
+    }
+
+    // Rounds to the context's number of significant digits.
+    pub fn round(&self, context: &Context) -> Decimal {
+        round(self.negative, &self.coefficient, self.exponent, false, context)
+    }
+
+    pub fn add(&self, other: &Decimal, context: &Context) -> Decimal {
+        let (negative, coefficient, exponent) = self.sum(other, context.digits);
+        round(negative, &coefficient, exponent, false, context)
+    }
+
+    // The sum without rounding, except that a term lying wholly below `digits`
+    // significant digits of the other only keeps its sign, which is all that rounding
+    // to `digits` or fewer can see of it.
+    pub fn add_within(&self, other: &Decimal, digits: usize) -> Decimal {
+        let (negative, coefficient, exponent) = self.sum(other, digits);
+        Decimal::new(negative, coefficient, exponent)
+    }
+
+    fn sum(&self, other: &Decimal, digits: usize) -> (bool, Natural, i64) {
+        if self.is_zero() {
+            return (other.negative, other.coefficient.clone(), other.exponent);
+        }
+        if other.is_zero() {
+            return (self.negative, self.coefficient.clone(), self.exponent);
+        }
+        let (large, small) = if self.magnitude() >= other.magnitude() { (self, other) } else { (other, self) };
+        // Below both the last kept digit and every digit of `large`, only the sign of
+        // `small` matters for rounding; a single digit stands in for it, rather than
+        // padding `large` with millions of zeros.
+        let limit = (large.magnitude() - (digits + 2) as i64).min(large.exponent);
+        let stand_in;
+        let small = if small.magnitude() < limit {
+            stand_in = Decimal { negative: small.negative, coefficient: Natural::from_u128(1), exponent: limit - 1 };
+            &stand_in
+        } else {
+            small
+        };
+        let exponent = large.exponent.min(small.exponent);
+        let a = large.coefficient.shift((large.exponent - exponent) as usize);
+        let b = small.coefficient.shift((small.exponent - exponent) as usize);
+        if large.negative == small.negative {
+            return (large.negative, a.add(&b), exponent);
+        }
+        match a.compare(&b) {
+            Ordering::Equal => (false, Natural::zero(), 0),
+            Ordering::Greater => (large.negative, a.sub(&b), exponent),
+            Ordering::Less => (small.negative, b.sub(&a), exponent),
+        }
+    }
+
+    pub fn sub(&self, other: &Decimal, context: &Context) -> Decimal {
+        self.add(&other.neg(), context)
+    }
+
+    pub fn mul(&self, other: &Decimal, context: &Context) -> Decimal {
+        let coefficient = self.coefficient.mul(&other.coefficient);
+        round(self.negative != other.negative, &coefficient, self.exponent + other.exponent, false, context)
+    }
+
+    // The product without rounding.
+    pub fn mul_exact(&self, other: &Decimal) -> Decimal {
+        Decimal::new(self.negative != other.negative, self.coefficient.mul(&other.coefficient), self.exponent + other.exponent)
+    }
+
+    // None when dividing by zero.
+    pub fn div(&self, other: &Decimal, context: &Context) -> Option<Decimal> {
+        if other.is_zero() {
+            return None;
+        }
+        if self.is_zero() {
+            return Some(Decimal::zero());
+        }
+        // Enough digits that rounding sees the first dropped digit, with the remainder
+        // standing in for everything after it.
+        let places = (context.digits + 2 + other.coefficient.len()).saturating_sub(self.coefficient.len());
+        let (quotient, remainder) = self.coefficient.shift(places).div_rem(&other.coefficient);
+        let exponent = self.exponent - other.exponent - places as i64;
+        Some(round(self.negative != other.negative, &quotient, exponent, !remainder.is_zero(), context))
+    }
+
+    // Position of the leading digit: 0 for 1.5, 3 for 1234, -2 for 0.0123.
+    fn magnitude(&self) -> i64 {
+        self.exponent + self.coefficient.len() as i64 - 1
+    }
+}
+
+// Rounds coefficient * 10^exponent to the context's digits. `sticky` marks a non-zero
+// remainder beyond the coefficient's last digit.
+fn round(negative: bool, coefficient: &Natural, exponent: i64, sticky: bool, context: &Context) -> Decimal {
+    if coefficient.len() <= context.digits {
+        return Decimal::new(negative, coefficient.clone(), exponent);
+    }
+    let dropped = coefficient.len() - context.digits;
+    let kept = Natural(coefficient.0[dropped..].to_vec());
+    let first = coefficient.digit(dropped - 1);
+    let rest = sticky || coefficient.0[..dropped - 1].iter().any(|digit| *digit != 0);
+    let up = match context.rounding {
+        Rounding::TowardZero => false,
+        Rounding::HalfUp => first >= 5,
+        Rounding::HalfEven => first > 5 || (first == 5 && (rest || kept.digit(0) % 2 == 1)),
+    };
+    let kept = if up { kept.add(&Natural::from_u128(1)) } else { kept };
+    // 999 rounding up to 1000 gains a digit, which is a trailing zero and drops out.
+    Decimal::new(negative, kept, exponent + dropped as i64)
+}
+
+// atan(1/x) in fixed point, `one` being 1: the sum of (-1)^k / ((2k + 1) x^(2k + 1)).
+fn atan_inverse(x: u128, one: &Natural) -> Natural {
+    let x_squared = Natural::from_u128(x * x);
+    let mut power = one.div_rem(&Natural::from_u128(x)).0;
+    let (mut positive, mut negative) = (Natural::zero(), Natural::zero());
+    let mut k = 0;
+    while !power.is_zero() {
+        let term = power.div_rem(&Natural::from_u128(2 * k + 1)).0;
+        if k % 2 == 0 {
+            positive = positive.add(&term);
+        } else {
+            negative = negative.add(&term);
+        }
+        power = power.div_rem(&x_squared).0;
+        k += 1;
+    }
+    positive.sub(&negative)
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        write!(f, "{}", decimal_string(self.negative, &self.coefficient.to_string(), self.exponent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(text: &str) -> Decimal {
+        Decimal::parse(text).unwrap()
+    }
+
+    fn context(digits: usize, rounding: Rounding) -> Context {
+        Context::new(digits, rounding)
+    }
+
+    #[test]
+    fn parses_and_prints_literals() {
+        assert_eq!(decimal("1609.344").to_string(), "1609.344");
+        assert_eq!(decimal("-0.5").to_string(), "-0.5");
+        assert_eq!(decimal("6.02214076e23"), decimal("602214076000000000000000"));
+        assert_eq!(decimal("1.500"), decimal("1.5"));
+        assert_eq!(Decimal::parse("."), None);
+        assert_eq!(Decimal::parse("1x"), None);
+        assert_eq!(Decimal::from_f64(0.1), Some(decimal("0.1")));
+        assert_eq!(Decimal::from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn rounds_ties_by_mode() {
+        let cases = [
+            ("2.5", Rounding::HalfEven, "2"),
+            ("3.5", Rounding::HalfEven, "4"),
+            ("2.5", Rounding::HalfUp, "3"),
+            ("-2.5", Rounding::HalfUp, "-3"),
+            ("2.9", Rounding::TowardZero, "2"),
+            ("2.5000001", Rounding::HalfEven, "3"),
+            ("9.99", Rounding::HalfEven, "10"),
+        ];
+        for (value, rounding, expected) in cases {
+            assert_eq!(decimal(value).round(&context(1, rounding)), decimal(expected), "{} {:?}", value, rounding);
+        }
+    }
+
+    #[test]
+    fn arithmetic_rounds_once() {
+        let ctx = context(34, Rounding::HalfEven);
+        assert_eq!(decimal("0.1").add(&decimal("0.2"), &ctx), decimal("0.3"));
+        assert_eq!(decimal("1").sub(&decimal("1"), &ctx), Decimal::zero());
+        assert_eq!(decimal("1").div(&decimal("3"), &context(5, Rounding::HalfEven)), Some(decimal("0.33333")));
+        assert_eq!(decimal("2").div(&decimal("3"), &context(5, Rounding::TowardZero)), Some(decimal("0.66666")));
+        assert_eq!(decimal("1").div(&Decimal::zero(), &ctx), None);
+        // Far below the kept digits, a term still decides which way a tie goes.
+        let tie = decimal("2.5").add(&decimal("1e-1000000"), &context(1, Rounding::HalfEven));
+        assert_eq!(tie, decimal("3"));
+    }
+
+    #[test]
+    fn unrounded_sums_and_products_keep_every_digit() {
+        let a = decimal("123456789.123456789");
+        assert_eq!(a.mul_exact(&a), decimal("15241578780673678.515622620750190521"));
+        assert_eq!(a.add_within(&decimal("1e-20"), 30), decimal("123456789.12345678900000000001"));
+        // Too far below, only the sign survives, one place under the limit.
+        let far = decimal("1").add_within(&decimal("-1e-100"), 10);
+        assert_eq!(far.round(&context(10, Rounding::TowardZero)), decimal("0.9999999999"));
+        assert_eq!(far.round(&context(10, Rounding::HalfEven)), decimal("1"));
+    }
+
+    #[test]
+    fn pi_and_exact_values() {
+        let ctx = context(30, Rounding::HalfEven);
+        assert_eq!(Decimal::pi(&ctx), decimal("3.14159265358979323846264338328"));
+        assert_eq!(Decimal::from_exact(Exact::parse("0.0254").unwrap(), &ctx), decimal("0.0254"));
+        assert_eq!(Decimal::from_exact(Exact::ratio(1, 3).unwrap(), &context(4, Rounding::HalfEven)), decimal("0.3333"));
+        assert_eq!(Decimal::from_exact(Exact::ONE.times_pi(1).unwrap(), &context(5, Rounding::HalfEven)), decimal("3.1416"));
+    }
+}
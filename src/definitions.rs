@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::enums::*;
+use crate::registry::UnitRegistry;
+use crate::{get_conversion_factor_to_base, get_unit_dimension};
+
+// Plain-text unit definitions, one per line:
+//
+//   # comment
+//   pallet, pallets, plt = 1.2 m3          name and aliases, then an expression
+//   shift, shifts = 8 hour                 in terms of existing units
+//   inch_third = 1/3 in
+//   gas_mark = 14 K + 394.26               optional offset, in the referenced unit
+//   widget : length 0.125                  dimension, factor to its base unit
+//   chilly : temperature 1 + 250           ... and optional offset in base units
+//
+// Definitions may refer to units defined later in the same file.
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Definition {
+    pub unit: CustomUnit,
+    pub aliases: Vec<String>, // Every accepted spelling, including the name itself
+    pub line: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DefinitionErrorKind {
+    Syntax(String),
+    UnknownUnit(String),
+    UnknownDimension(String),
+    Redefinition { name: String, previous_line: Option<usize> }, // None for built-in units
+    Cycle(Vec<String>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DefinitionError {
+    pub line: usize,
+    pub kind: DefinitionErrorKind,
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            DefinitionErrorKind::Syntax(msg) => write!(f, "syntax error: {}", msg),
+            DefinitionErrorKind::UnknownUnit(name) => write!(f, "unknown unit {:?}", name),
+            DefinitionErrorKind::UnknownDimension(name) => write!(f, "unknown dimension {:?}", name),
+            DefinitionErrorKind::Redefinition { name, previous_line: Some(previous) } => {
+                write!(f, "{:?} is already defined on line {}", name, previous)
+            }
+            DefinitionErrorKind::Redefinition { name, previous_line: None } => {
+                write!(f, "{:?} is already a built-in unit", name)
+            }
+            DefinitionErrorKind::Cycle(names) => write!(f, "circular definition: {}", names.join(" -> ")),
+        }
+    }
+}
+
+impl std::error::Error for DefinitionError {}
+
+enum Body {
+    Expression { scale: f64, unit: String, offset: f64 },
+    Dimension { dimension: Dimension, factor: f64, offset: f64 },
+}
+
+struct RawDefinition {
+    names: Vec<String>,
+    body: Body,
+    line: usize,
+}
+
+fn syntax(line: usize, msg: &str) -> DefinitionError {
+    DefinitionError { line, kind: DefinitionErrorKind::Syntax(msg.to_string()) }
+}
+
+// A plain float or a fraction such as "1/3".
+fn parse_number(token: &str) -> Option<f64> {
+    match token.split_once('/') {
+        Some((num, den)) => {
+            let den = den.parse::<f64>().ok()?;
+            if den == 0.0 {
+                return None;
+            }
+            Some(num.parse::<f64>().ok()? / den)
+        }
+        None => token.parse::<f64>().ok(),
+    }
+}
+
+// Splits a trailing "+ n" / "- n" offset off the tokens.
+fn split_offset<'a>(tokens: &'a [&'a str], line: usize) -> Result<(&'a [&'a str], f64), DefinitionError> {
+    match tokens {
+        [rest @ .., sign @ ("+" | "-"), value] => {
+            let value = parse_number(value).ok_or_else(|| syntax(line, "offset must be a number"))?;
+            Ok((rest, if *sign == "-" { -value } else { value }))
+        }
+        _ => Ok((tokens, 0.0)),
+    }
+}
+
+// Multiplies out a run of numbers joined by optional "*" and "/" tokens.
+fn parse_scale(tokens: &[&str], line: usize) -> Result<f64, DefinitionError> {
+    let mut scale = 1.0;
+    let mut divide = false;
+    for token in tokens {
+        match *token {
+            "*" => {}
+            "/" => divide = true,
+            _ => {
+                let value = parse_number(token).ok_or_else(|| syntax(line, &format!("expected a number, found {:?}", token)))?;
+                if divide {
+                    if value == 0.0 {
+                        return Err(syntax(line, "division by zero"));
+                    }
+                    scale /= value;
+                    divide = false;
+                } else {
+                    scale *= value;
+                }
+            }
+        }
+    }
+    if divide {
+        return Err(syntax(line, "dangling '/'"));
+    }
+    Ok(scale)
+}
+
+fn parse_line(text: &str, line: usize) -> Result<Option<RawDefinition>, DefinitionError> {
+    let text = text.split('#').next().unwrap_or("").trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let split_at = text.find(['=', ':']).ok_or_else(|| syntax(line, "expected '=' or ':' after the unit name"))?;
+    let (head, body) = text.split_at(split_at);
+    let is_dimension = body.starts_with(':');
+    let body = &body[1..];
+
+    let mut names = vec![];
+    for name in head.split(',') {
+        let name = name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(syntax(line, &format!("invalid unit name {:?}", name)));
+        }
+        names.push(name.to_string());
+    }
+
+    let tokens: Vec<&str> = body.split_whitespace().collect();
+    let (tokens, offset) = split_offset(&tokens, line)?;
+    let body = if is_dimension {
+        let (dimension, rest) = tokens.split_first().ok_or_else(|| syntax(line, "expected a dimension"))?;
+        let dimension = Dimension::from_name(dimension).ok_or_else(|| DefinitionError {
+            line,
+            kind: DefinitionErrorKind::UnknownDimension(dimension.to_string()),
+        })?;
+        Body::Dimension { dimension, factor: parse_scale(rest, line)?, offset }
+    } else {
+        let (unit, numbers) = tokens.split_last().ok_or_else(|| syntax(line, "expected a definition"))?;
+        if parse_number(unit).is_some() {
+            return Err(syntax(line, "definition must end with a unit"));
+        }
+        Body::Expression { scale: parse_scale(numbers, line)?, unit: unit.to_string(), offset }
+    };
+    Ok(Some(RawDefinition { names, body, line }))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Unresolved,
+    Resolving,
+    Resolved,
+}
+
+struct Resolver<'a> {
+    raw: Vec<RawDefinition>,
+    by_name: HashMap<String, usize>,
+    state: Vec<State>,
+    resolved: Vec<Option<CustomUnit>>,
+    registry: &'a UnitRegistry,
+}
+
+impl Resolver<'_> {
+    fn resolve(&mut self, index: usize, stack: &mut Vec<usize>) -> Result<CustomUnit, DefinitionError> {
+        match self.state[index] {
+            State::Resolved => return Ok(self.resolved[index].clone().unwrap()),
+            State::Resolving => {
+                let start = stack.iter().position(|i| *i == index).unwrap_or(0);
+                let mut names: Vec<String> = stack[start..].iter().map(|i| self.raw[*i].names[0].clone()).collect();
+                names.push(self.raw[index].names[0].clone());
+                return Err(DefinitionError { line: self.raw[index].line, kind: DefinitionErrorKind::Cycle(names) });
+            }
+            State::Unresolved => {}
+        }
+        self.state[index] = State::Resolving;
+        stack.push(index);
+
+        let line = self.raw[index].line;
+        let name = self.raw[index].names[0].clone();
+        let unit = match &self.raw[index].body {
+            Body::Dimension { dimension, factor, offset } => {
+                CustomUnit { name, dimension: *dimension, factor: *factor, offset: *offset }
+            }
+            Body::Expression { scale, unit, offset } => {
+                let (scale, offset) = (*scale, *offset);
+                let referenced = match self.by_name.get(&unit.to_lowercase()).copied() {
+                    Some(dependency) => Unit::Custom(self.resolve(dependency, stack)?),
+                    None => self.registry.lookup(unit).ok_or_else(|| DefinitionError {
+                        line,
+                        kind: DefinitionErrorKind::UnknownUnit(unit.clone()),
+                    })?,
+                };
+                let (factor, base_offset) = affine_to_base(&referenced);
+                CustomUnit {
+                    name,
+                    dimension: get_unit_dimension(&referenced),
+                    factor: scale * factor,
+                    offset: offset * factor + base_offset,
+                }
+            }
+        };
+
+        stack.pop();
+        self.state[index] = State::Resolved;
+        self.resolved[index] = Some(unit.clone());
+        Ok(unit)
+    }
+}
+
+// (factor, offset) such that base = value * factor + offset.
+fn affine_to_base(unit: &Unit) -> (f64, f64) {
+    match unit {
+        Unit::Custom(custom) => (custom.factor, custom.offset),
+        Unit::Temperature(_) => {
+            let zero = crate::convert(0.0, unit, &Unit::Temperature(Temperature::Kelvin)).unwrap_or(0.0);
+            let one = crate::convert(1.0, unit, &Unit::Temperature(Temperature::Kelvin)).unwrap_or(1.0);
+            (one - zero, zero)
+        }
+        _ => (get_conversion_factor_to_base(unit), 0.0),
+    }
+}
+
+// Parses and resolves a definitions file against the units already in `registry`.
+pub fn parse_definitions(source: &str, registry: &UnitRegistry) -> Result<Vec<Definition>, DefinitionError> {
+    let mut raw = vec![];
+    let mut by_name: HashMap<String, usize> = HashMap::new();
+    for (i, text) in source.lines().enumerate() {
+        let Some(definition) = parse_line(text, i + 1)? else { continue };
+        for name in &definition.names {
+            if registry.is_defined(name) {
+                return Err(DefinitionError {
+                    line: definition.line,
+                    kind: DefinitionErrorKind::Redefinition { name: name.clone(), previous_line: registry.defined_on_line(name) },
+                });
+            }
+            if let Some(previous) = by_name.get(&name.to_lowercase()) {
+                let previous: &RawDefinition = &raw[*previous];
+                return Err(DefinitionError {
+                    line: definition.line,
+                    kind: DefinitionErrorKind::Redefinition { name: name.clone(), previous_line: Some(previous.line) },
+                });
+            }
+            by_name.insert(name.to_lowercase(), raw.len());
+        }
+        raw.push(definition);
+    }
+
+    let count = raw.len();
+    let mut resolver = Resolver {
+        raw,
+        by_name,
+        state: vec![State::Unresolved; count],
+        resolved: vec![None; count],
+        registry,
+    };
+    let mut definitions = vec![];
+    for index in 0..count {
+        let unit = resolver.resolve(index, &mut vec![])?;
+        definitions.push(Definition {
+            unit,
+            aliases: resolver.raw[index].names.clone(),
+            line: resolver.raw[index].line,
+        });
+    }
+    Ok(definitions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Result<Vec<Definition>, DefinitionError> {
+        parse_definitions(source, UnitRegistry::builtin())
+    }
+
+    fn kind(source: &str) -> DefinitionErrorKind {
+        parse(source).unwrap_err().kind
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn parses_expressions_and_dimensions() {
+        let source = "# pallets\npallet, pallets, plt = 1.2 m3\ninch_third = 1/3 in\nwidget : length 0.125\n\nshift = 8 * 60 min # a work shift\n";
+        let definitions = parse(source).unwrap();
+        let names: Vec<&str> = definitions.iter().map(|definition| definition.unit.name.as_str()).collect();
+        assert_eq!(names, ["pallet", "inch_third", "widget", "shift"]);
+        assert_eq!(definitions[0].aliases, ["pallet", "pallets", "plt"]);
+        assert_eq!((definitions[0].unit.dimension, definitions[0].line), (Dimension::Volume, 2));
+        assert!(close(definitions[1].unit.factor, 0.0254 / 3.0));
+        assert_eq!((definitions[2].unit.dimension, definitions[2].unit.factor), (Dimension::Length, 0.125));
+        assert!(close(definitions[3].unit.factor, 28800.0));
+    }
+
+    #[test]
+    fn offsets_are_in_the_referenced_unit() {
+        let definitions = parse("gas_mark = 14 K + 394.26\nwarm = 1 celsius + 20\nchilly : temperature 1 + 250\n").unwrap();
+        assert_eq!((definitions[0].unit.factor, definitions[0].unit.offset), (14.0, 394.26));
+        assert!(close(definitions[1].unit.offset, 293.15));
+        assert_eq!(definitions[2].unit.offset, 250.0);
+    }
+
+    #[test]
+    fn definitions_refer_forward() {
+        let definitions = parse("crate = 12 box\nbox = 6 bottle\nbottle = 0.75 L\n").unwrap();
+        assert!(close(definitions[0].unit.factor, 0.054));
+    }
+
+    #[test]
+    fn reports_errors_by_line() {
+        assert_eq!(kind("a = 1 b\nb = 1 a\n"), DefinitionErrorKind::Cycle(vec!["a".to_string(), "b".to_string(), "a".to_string()]));
+        assert_eq!(kind("x = 2 nonsense"), DefinitionErrorKind::UnknownUnit("nonsense".to_string()));
+        assert_eq!(kind("x : flavour 1"), DefinitionErrorKind::UnknownDimension("flavour".to_string()));
+        assert_eq!(kind("m = 2 ft"), DefinitionErrorKind::Redefinition { name: "m".to_string(), previous_line: None });
+        let error = parse("thing = 1 m\n\nThing = 2 m").unwrap_err();
+        assert_eq!(error.to_string(), "line 3: \"Thing\" is already defined on line 1");
+        for source in ["just words", "x = 1 / m", "x = 1/0 m", "x = 2", "2x = 1 m", "x = 1 m + y"] {
+            assert!(matches!(kind(source), DefinitionErrorKind::Syntax(_)), "{}", source);
+        }
+    }
+}
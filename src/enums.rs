@@ -17,15 +17,76 @@ pub enum Unit {
     Luminosity(Luminosity),
     Radiation(Radiation),
     MagneticFlux(MagneticFlux),
+    Custom(CustomUnit), // Defined at runtime, e.g. from a units definitions file
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct CustomUnit {
+    pub name: String,
+    pub dimension: Dimension,
+    pub factor: f64, // Multiplier to the dimension's base unit
+    pub offset: f64, // Added after scaling, in base units (non-zero for temperature-like scales)
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Dimension {
     Length, Mass, Area, Volume, Force, Energy, Temperature, Time, Speed, Pressure, Frequency,
     ElectricCurrent, Voltage, Capacitance, Luminosity, Radiation, MagneticFlux,
     Unknown, // Should ideally not be reached if `Unit` enum is exhaustive
 }
 
+impl Dimension {
+    // Accepts the variant name in any case, with or without underscores/spaces
+    // ("length", "ElectricCurrent", "magnetic_flux").
+    pub fn from_name(name: &str) -> Option<Dimension> {
+        let normalized: String = name.chars().filter(|c| *c != '_' && *c != ' ').collect::<String>().to_lowercase();
+        match normalized.as_str() {
+            "length" => Some(Dimension::Length),
+            "mass" => Some(Dimension::Mass),
+            "area" => Some(Dimension::Area),
+            "volume" => Some(Dimension::Volume),
+            "force" => Some(Dimension::Force),
+            "energy" => Some(Dimension::Energy),
+            "temperature" => Some(Dimension::Temperature),
+            "time" => Some(Dimension::Time),
+            "speed" => Some(Dimension::Speed),
+            "pressure" => Some(Dimension::Pressure),
+            "frequency" => Some(Dimension::Frequency),
+            "electriccurrent" | "current" => Some(Dimension::ElectricCurrent),
+            "voltage" => Some(Dimension::Voltage),
+            "capacitance" => Some(Dimension::Capacitance),
+            "luminosity" => Some(Dimension::Luminosity),
+            "radiation" => Some(Dimension::Radiation),
+            "magneticflux" => Some(Dimension::MagneticFlux),
+            _ => None,
+        }
+    }
+
+    // Lowercase name with underscores, as accepted by `from_name`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dimension::Length => "length",
+            Dimension::Mass => "mass",
+            Dimension::Area => "area",
+            Dimension::Volume => "volume",
+            Dimension::Force => "force",
+            Dimension::Energy => "energy",
+            Dimension::Temperature => "temperature",
+            Dimension::Time => "time",
+            Dimension::Speed => "speed",
+            Dimension::Pressure => "pressure",
+            Dimension::Frequency => "frequency",
+            Dimension::ElectricCurrent => "electric_current",
+            Dimension::Voltage => "voltage",
+            Dimension::Capacitance => "capacitance",
+            Dimension::Luminosity => "luminosity",
+            Dimension::Radiation => "radiation",
+            Dimension::MagneticFlux => "magnetic_flux",
+            Dimension::Unknown => "unknown",
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Length {
     // Metric Length
@@ -245,6 +306,10 @@ pub enum Time {
     Shake,          // 10 nanoseconds, used in nuclear physics
     JulianYear,     // Exactly 365.25 days
     LeapYear,       // 366 days
+    CommonYear,     // 365 days
+    GregorianYear,  // Mean Gregorian calendar year, exactly 365.2425 days
+    TropicalYear,   // Mean tropical (solar) year, ~365.24219 days
+    SiderealYear,   // Orbital period relative to the fixed stars, ~365.25636 days
     SiderealDay,    // Time it takes for the Earth to complete one rotation relative to the fixed stars
     PlanckTime,
 }
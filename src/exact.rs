@@ -0,0 +1,349 @@
+use std::fmt;
+
+// Exact numbers of the form num/den * 10^ten * π^pi, for conversion factors that are
+// defined exactly (1 in = 0.0254 m, 1 °F = 5/9 K, 1 rad/s = 1/(2π) Hz). Powers of ten
+// and π are kept symbolic, so SI prefixes don't use up the i128 digits and angles
+// stay exact. Arithmetic returns None on overflow, and for sums that would need π
+// to be expanded.
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Exact {
+    num: i128, // No trailing zeros
+    den: i128, // Positive, coprime with num, and with no factor of 2 or 5
+    ten: i32,
+    pi: i32,
+}
+
+// Written out in full up to this many zeros, otherwise in exponent form.
+const MAX_ZEROS: i32 = 21;
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+fn power_of_ten(exponent: i32) -> Option<i128> {
+    10i128.checked_pow(u32::try_from(exponent).ok()?)
+}
+
+impl Exact {
+    pub const ZERO: Exact = Exact { num: 0, den: 1, ten: 0, pi: 0 };
+    pub const ONE: Exact = Exact { num: 1, den: 1, ten: 0, pi: 0 };
+
+    fn normalize(mut num: i128, mut den: i128, mut ten: i32, pi: i32) -> Option<Exact> {
+        if den == 0 {
+            return None;
+        }
+        if num == 0 {
+            return Some(Exact::ZERO);
+        }
+        if den < 0 {
+            num = num.checked_neg()?;
+            den = den.checked_neg()?;
+        }
+        let divisor = gcd(num, den);
+        num /= divisor;
+        den /= divisor;
+        // Move the 2s and 5s of the denominator into the power of ten: 3/4 = 75e-2.
+        let (mut twos, mut fives) = (0, 0);
+        while den % 2 == 0 {
+            den /= 2;
+            twos += 1;
+        }
+        while den % 5 == 0 {
+            den /= 5;
+            fives += 1;
+        }
+        let shift = twos.max(fives);
+        num = num.checked_mul(2i128.checked_pow(shift - twos)?)?.checked_mul(5i128.checked_pow(shift - fives)?)?;
+        ten = ten.checked_sub(i32::try_from(shift).ok()?)?;
+        while num % 10 == 0 {
+            num /= 10;
+            ten = ten.checked_add(1)?;
+        }
+        Some(Exact { num, den, ten, pi })
+    }
+
+    pub fn integer(n: i128) -> Exact {
+        Exact::normalize(n, 1, 0, 0).unwrap_or(Exact::ZERO)
+    }
+
+    // None if `den` is zero.
+    pub fn ratio(num: i128, den: i128) -> Option<Exact> {
+        Exact::normalize(num, den, 0, 0)
+    }
+
+    // This number times π^power. None if the power of π overflows.
+    pub fn times_pi(self, power: i32) -> Option<Exact> {
+        if self.num == 0 {
+            return Some(self);
+        }
+        Some(Exact { pi: self.pi.checked_add(power)?, ..self })
+    }
+
+    // A decimal literal: "0.0254", "-1.5", "6.02214076e23".
+    pub fn parse(text: &str) -> Option<Exact> {
+        let text = text.trim();
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(at) => (&text[..at], text[at + 1..].parse::<i32>().ok()?),
+            None => (text, 0),
+        };
+        let (negative, mantissa) = match mantissa.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, mantissa.strip_prefix('+').unwrap_or(mantissa)),
+        };
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return None;
+        }
+        let mut num: i128 = 0;
+        for c in whole.chars().chain(fraction.chars()) {
+            let digit = c.to_digit(10)?;
+            num = num.checked_mul(10)?.checked_add(i128::from(digit))?;
+        }
+        if negative {
+            num = -num;
+        }
+        let ten = exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+        Exact::normalize(num, 1, ten, 0)
+    }
+
+    // The shortest decimal that rounds to `value`, which is exact when `value` was
+    // written as a decimal literal.
+    pub fn from_f64(value: f64) -> Option<Exact> {
+        if !value.is_finite() {
+            return None;
+        }
+        Exact::parse(&format!("{:e}", value))
+    }
+
+    // Correctly rounded unless π is involved.
+    pub fn to_f64(&self) -> f64 {
+        // Whole numbers and powers of ten that doubles hold exactly make one
+        // correctly rounded multiply or divide, with no string to parse.
+        const EXACT: u128 = 1 << 53;
+        if self.pi == 0 && self.num.unsigned_abs() <= EXACT && self.den as u128 <= EXACT {
+            match (self.den, self.ten) {
+                (_, 0) => return self.num as f64 / self.den as f64,
+                (1, 1..=22) => return self.num as f64 * 10f64.powi(self.ten),
+                (1, -22..=-1) => return self.num as f64 / 10f64.powi(-self.ten),
+                _ => {}
+            }
+        }
+        // num/den is written out as a decimal, with a sticky last digit when the
+        // division doesn't end, so that parsing it rounds once.
+        let (whole, mut remainder) = (self.num.unsigned_abs() / self.den as u128, self.num.unsigned_abs() % self.den as u128);
+        let mut digits = whole.to_string();
+        let mut significant = if whole == 0 { 0 } else { digits.len() };
+        let mut fraction = String::new();
+        while remainder != 0 && significant < 40 {
+            // Only a denominator near the i128 limit runs out of room here.
+            let Some(scaled) = remainder.checked_mul(10) else { break };
+            remainder = scaled;
+            let digit = remainder / self.den as u128;
+            remainder %= self.den as u128;
+            fraction.push(char::from(b'0' + digit as u8));
+            if significant > 0 || digit != 0 {
+                significant += 1;
+            }
+        }
+        if remainder != 0 {
+            fraction.push('1');
+        }
+        if !fraction.is_empty() {
+            digits = format!("{}.{}", digits, fraction);
+        }
+        let sign = if self.num < 0 { "-" } else { "" };
+        let value = format!("{}{}e{}", sign, digits, self.ten).parse::<f64>().unwrap_or(f64::NAN);
+        value * std::f64::consts::PI.powi(self.pi)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    // (num, den, ten, pi), for num/den * 10^ten * π^pi; den is positive.
+    pub fn parts(&self) -> (i128, i128, i32, i32) {
+        (self.num, self.den, self.ten, self.pi)
+    }
+
+    pub fn checked_neg(self) -> Option<Exact> {
+        Some(Exact { num: self.num.checked_neg()?, ..self })
+    }
+
+    pub fn checked_mul(self, other: Exact) -> Option<Exact> {
+        // Cancel first, to keep the products small.
+        let a = gcd(self.num, other.den).max(1);
+        let b = gcd(other.num, self.den).max(1);
+        Exact::normalize(
+            (self.num / a).checked_mul(other.num / b)?,
+            (self.den / b).checked_mul(other.den / a)?,
+            self.ten.checked_add(other.ten)?,
+            self.pi.checked_add(other.pi)?,
+        )
+    }
+
+    pub fn recip(self) -> Option<Exact> {
+        Exact::normalize(self.den, self.num, self.ten.checked_neg()?, self.pi.checked_neg()?)
+    }
+
+    pub fn checked_div(self, other: Exact) -> Option<Exact> {
+        self.checked_mul(other.recip()?)
+    }
+
+    pub fn checked_add(self, other: Exact) -> Option<Exact> {
+        if self.is_zero() {
+            return Some(other);
+        }
+        if other.is_zero() {
+            return Some(self);
+        }
+        if self.pi != other.pi {
+            return None;
+        }
+        let ten = self.ten.min(other.ten);
+        let a = self.num.checked_mul(power_of_ten(self.ten - ten)?)?;
+        let b = other.num.checked_mul(power_of_ten(other.ten - ten)?)?;
+        let num = a.checked_mul(other.den)?.checked_add(b.checked_mul(self.den)?)?;
+        Exact::normalize(num, self.den.checked_mul(other.den)?, ten, self.pi)
+    }
+
+    pub fn checked_sub(self, other: Exact) -> Option<Exact> {
+        self.checked_add(other.checked_neg()?)
+    }
+}
+
+// `num` * 10^ten written out: "1609.344", "0.0254", or "1.602176634e-19" when it
+// would take more than MAX_ZEROS zeros.
+fn decimal(num: i128, ten: i32) -> String {
+    decimal_string(num < 0, &num.unsigned_abs().to_string(), i64::from(ten))
+}
+
+// The same for a sign and a string of digits, shared with `Decimal`.
+pub(crate) fn decimal_string(negative: bool, digits: &str, ten: i64) -> String {
+    let sign = if negative { "-" } else { "" };
+    let len = digits.len() as i64;
+    let point = len + ten; // Digits before the decimal point
+    let max_zeros = i64::from(MAX_ZEROS);
+    let body = if (0..=max_zeros).contains(&ten) {
+        format!("{}{}", digits, "0".repeat(ten as usize))
+    } else if ten < 0 && point > 0 {
+        format!("{}.{}", &digits[..point as usize], &digits[point as usize..])
+    } else if ten < 0 && -point <= max_zeros {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    } else {
+        let rest = if len > 1 { format!(".{}", &digits[1..]) } else { String::new() };
+        format!("{}{}e{}", &digits[..1], rest, point - 1)
+    };
+    format!("{}{}", sign, body)
+}
+
+// "1609.344", "5/9", "45967/180", "π/180", "1/(2π)", "10000/π".
+impl fmt::Display for Exact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pi = match self.pi.abs() {
+            0 => String::new(),
+            1 => "π".to_string(),
+            power => format!("π^{}", power),
+        };
+        // Fold the power of ten into whichever side keeps both parts whole.
+        let (numerator, denominator) = if self.ten >= 0 || (self.den == 1 && self.pi == 0) {
+            (decimal(self.num, self.ten), self.den.to_string())
+        } else {
+            match power_of_ten(-self.ten).and_then(|scale| self.den.checked_mul(scale)) {
+                Some(den) => {
+                    let divisor = gcd(self.num, den);
+                    ((self.num / divisor).to_string(), (den / divisor).to_string())
+                }
+                None => (decimal(self.num, self.ten), self.den.to_string()),
+            }
+        };
+        let (numerator, denominator) = match self.pi.signum() {
+            0 => (numerator, denominator),
+            1 => match numerator.as_str() {
+                "1" => (pi, denominator),
+                "-1" => (format!("-{}", pi), denominator),
+                _ => (format!("{}{}", numerator, pi), denominator),
+            },
+            _ if denominator == "1" => (numerator, pi),
+            _ => (numerator, format!("({}{})", denominator, pi)),
+        };
+        if denominator == "1" { write!(f, "{}", numerator) } else { write!(f, "{}/{}", numerator, denominator) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exact(text: &str) -> Exact {
+        Exact::parse(text).unwrap()
+    }
+
+    #[test]
+    fn parses_decimal_literals() {
+        assert_eq!(exact("0.0254"), Exact::ratio(254, 10000).unwrap());
+        assert_eq!(exact("-1.5"), Exact::ratio(-3, 2).unwrap());
+        assert_eq!(exact("6.02214076e23").to_string(), "602214076000000000000000");
+        assert_eq!(exact("1e-30").to_string(), "1e-30");
+        assert_eq!(Exact::parse("."), None);
+        assert_eq!(Exact::parse("1.2.3"), None);
+        assert_eq!(Exact::parse("12x"), None);
+    }
+
+    #[test]
+    fn arithmetic_stays_exact() {
+        let inch = exact("0.0254");
+        assert_eq!(inch.checked_mul(Exact::integer(12)).unwrap(), exact("0.3048"));
+        let third = Exact::ratio(1, 3).unwrap();
+        assert_eq!(third.checked_add(third).unwrap().checked_add(third).unwrap(), Exact::ONE);
+        assert_eq!(exact("0.1").checked_add(exact("0.2")).unwrap(), exact("0.3"));
+        assert_eq!(Exact::ratio(5, 9).unwrap().recip().unwrap(), exact("1.8"));
+        assert_eq!(Exact::ONE.checked_div(Exact::ZERO), None);
+    }
+
+    #[test]
+    fn sums_with_pi_need_matching_powers() {
+        let half_turn = Exact::ONE.times_pi(1).unwrap();
+        assert_eq!(half_turn.checked_add(half_turn), Exact::integer(2).times_pi(1));
+        assert_eq!(half_turn.checked_add(Exact::ONE), None);
+        assert_eq!(half_turn.checked_add(Exact::ZERO), Some(half_turn));
+    }
+
+    #[test]
+    fn overflow_is_none() {
+        let big = Exact::integer(i128::MAX / 3);
+        assert_eq!(big.checked_mul(Exact::ratio(7, 3).unwrap().checked_mul(big).unwrap()), None);
+        assert_eq!(Exact::integer(i128::MAX).checked_neg().and_then(|n| n.checked_sub(Exact::integer(7))), None);
+        assert_eq!(Exact::ONE.times_pi(1).and_then(|pi| pi.times_pi(i32::MAX)), None);
+    }
+
+    #[test]
+    fn displays_fractions_and_pi() {
+        assert_eq!(exact("1609.344").to_string(), "1609.344");
+        assert_eq!(Exact::ratio(5, 9).unwrap().to_string(), "5/9");
+        assert_eq!(Exact::ratio(45967, 180).unwrap().to_string(), "45967/180");
+        assert_eq!(Exact::ratio(1, 180).and_then(|ratio| ratio.times_pi(1)).unwrap().to_string(), "π/180");
+        assert_eq!(Exact::ratio(1, 2).and_then(|half| half.times_pi(-1)).unwrap().to_string(), "1/(2π)");
+        assert_eq!(Exact::integer(10000).times_pi(-1).unwrap().to_string(), "10000/π");
+    }
+
+    #[test]
+    fn to_f64_rounds_once() {
+        assert_eq!(Exact::ratio(493, 5).unwrap().to_f64(), 98.6);
+        assert_eq!(Exact::ratio(2095, 9).unwrap().to_f64(), 2095.0 / 9.0);
+        assert_eq!(Exact::ratio(-1, 3).unwrap().to_f64(), -1.0 / 3.0);
+        assert_eq!(exact("1.602176634e-19").to_f64(), 1.602176634e-19);
+        assert_eq!(Exact::ratio(1, 10).and_then(|tenth| tenth.times_pi(1)).unwrap().to_f64(), 0.1 * std::f64::consts::PI);
+    }
+
+    #[test]
+    fn from_f64_reads_the_shortest_decimal() {
+        assert_eq!(Exact::from_f64(0.1), Some(exact("0.1")));
+        assert_eq!(Exact::from_f64(98.6), Some(exact("98.6")));
+        assert_eq!(Exact::from_f64(f64::INFINITY), None);
+        assert_eq!(Exact::from_f64(f64::NAN), None);
+    }
+}
@@ -0,0 +1,293 @@
+use std::fmt;
+
+use crate::enums::*;
+use crate::format::FormatOptions;
+use crate::parse::{ParseError, split_measurement};
+use crate::registry::{Exactness, LookupError, Reference, UnitRegistry};
+use crate::exact::Exact;
+use crate::{Quantity, convert, get_conversion_factor_to_base, get_exact_definition, get_offset_to_base, get_unit_dimension};
+
+// "Show your work": the steps behind a conversion, from the parsed input through
+// the unit definitions to the final arithmetic.
+
+// How a spelling was matched to a unit.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Match {
+    Symbol,
+    Name,
+    Plural,
+    Alias,
+    CaseInsensitive,                                    // e.g. "KM" for "km"
+    Prefix { prefix: String, scale: f64, of: String }, // A registered prefix on another unit
+}
+
+// Where a unit's factor and offset come from.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Origin {
+    Builtin { exactness: Exactness, reference: Option<Reference> },
+    Definition { line: usize }, // A definitions file or imported database
+    Prefix,
+    Runtime, // Constructed in code, not registered
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Step {
+    Parsed { input: String, value: f64, spelling: String },
+    Resolved { spelling: String, name: String, symbol: String, matched: Match },
+    Dimension(Dimension),
+    // base = factor * unit + offset
+    Definition { symbol: String, factor: f64, offset: f64, base: String, origin: Origin },
+    ToBase { value: f64, symbol: String, factor: f64, offset: f64, result: f64, base: String },
+    FromBase { value: f64, base: String, factor: f64, offset: f64, result: f64, symbol: String },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Explanation {
+    pub steps: Vec<Step>,
+    pub result: Quantity,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExplainError {
+    Parse(ParseError),
+    Unit(LookupError),
+    DimensionMismatch { from: Unit, to: Unit },
+    MissingFactor(Unit), // A unit whose factor to base is the 0.0 placeholder
+}
+
+impl fmt::Display for ExplainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registry = UnitRegistry::builtin();
+        match self {
+            ExplainError::Parse(err) => write!(f, "{}", err),
+            ExplainError::Unit(err) => write!(f, "{}", err),
+            ExplainError::DimensionMismatch { from, to } => write!(
+                f,
+                "cannot convert {} ({}) to {} ({})",
+                registry.name_of(from),
+                get_unit_dimension(from).name(),
+                registry.name_of(to),
+                get_unit_dimension(to).name()
+            ),
+            ExplainError::MissingFactor(unit) => write!(f, "no conversion factor is defined for {}", registry.name_of(unit)),
+        }
+    }
+}
+
+impl std::error::Error for ExplainError {}
+
+// Converts `input` ("20 mph") to `target`, recording each step.
+pub fn explain(input: &str, target: &str, registry: &UnitRegistry) -> Result<Explanation, ExplainError> {
+    let (value, uncertainty, from_spelling) = split_measurement(input).map_err(ExplainError::Parse)?;
+    let from = registry.resolve(from_spelling).map_err(|err| ExplainError::Parse(ParseError::Unit(err)))?;
+    let dimension = get_unit_dimension(&from);
+    let to = registry.resolve_in(target.trim(), Some(dimension)).map_err(ExplainError::Unit)?;
+    let mut steps = vec![
+        Step::Parsed { input: input.trim().to_string(), value, spelling: from_spelling.to_string() },
+        resolved(registry, from_spelling, &from),
+        resolved(registry, target.trim(), &to),
+    ];
+    if get_unit_dimension(&to) != dimension {
+        return Err(ExplainError::DimensionMismatch { from, to });
+    }
+    steps.push(Step::Dimension(dimension));
+
+    let base = registry.base_unit(dimension).map_or_else(|| format!("{} base unit", dimension.name()), |info| info.symbol.clone());
+    let (from_factor, from_offset) = (get_conversion_factor_to_base(&from), get_offset_to_base(&from));
+    let (to_factor, to_offset) = (get_conversion_factor_to_base(&to), get_offset_to_base(&to));
+    for (unit, factor, offset) in [(&from, from_factor, from_offset), (&to, to_factor, to_offset)] {
+        if factor == 0.0 {
+            return Err(ExplainError::MissingFactor(unit.clone()));
+        }
+        let origin = origin(registry, unit);
+        steps.push(Step::Definition { symbol: registry.symbol_of(unit), factor, offset, base: base.clone(), origin });
+    }
+
+    // The same arithmetic as `convert`: exact, and rounded once, where the units are
+    // defined exactly.
+    let exact_base = Exact::from_f64(value)
+        .zip(get_exact_definition(&from))
+        .and_then(|(value, (factor, offset))| value.checked_mul(factor)?.checked_add(offset));
+    let base_value = exact_base.map_or(value * from_factor + from_offset, |base| base.to_f64());
+    let result = convert(value, &from, &to).unwrap_or((base_value - to_offset) / to_factor);
+    steps.push(Step::ToBase {
+        value,
+        symbol: registry.symbol_of(&from),
+        factor: from_factor,
+        offset: from_offset,
+        result: base_value,
+        base: base.clone(),
+    });
+    steps.push(Step::FromBase {
+        value: base_value,
+        base,
+        factor: to_factor,
+        offset: to_offset,
+        result,
+        symbol: registry.symbol_of(&to),
+    });
+    let result = Quantity { value: result, unit: to, uncertainty: uncertainty.map(|u| (u * from_factor / to_factor).abs()) };
+    Ok(Explanation { steps, result })
+}
+
+fn resolved(registry: &UnitRegistry, spelling: &str, unit: &Unit) -> Step {
+    let matched = match registry.info(unit) {
+        Some(info) if spelling == info.symbol => Match::Symbol,
+        Some(info) if spelling == info.name => Match::Name,
+        Some(info) if spelling == info.plural => Match::Plural,
+        Some(info) if info.aliases.iter().any(|alias| alias == spelling) => Match::Alias,
+        Some(_) => Match::CaseInsensitive,
+        None => match registry.split_prefix(spelling) {
+            Some((prefix, scale, info)) => Match::Prefix { prefix: prefix.to_string(), scale, of: info.name.clone() },
+            None => Match::Name,
+        },
+    };
+    Step::Resolved { spelling: spelling.to_string(), name: registry.name_of(unit), symbol: registry.symbol_of(unit), matched }
+}
+
+fn origin(registry: &UnitRegistry, unit: &Unit) -> Origin {
+    match unit {
+        Unit::Custom(custom) => match (registry.info(unit), registry.defined_on_line(&custom.name)) {
+            (Some(_), Some(line)) => Origin::Definition { line },
+            (Some(_), None) => Origin::Runtime,
+            (None, _) if registry.split_prefix(&custom.name).is_some() => Origin::Prefix,
+            (None, _) => Origin::Runtime,
+        },
+        _ => match registry.info(unit) {
+            Some(info) => Origin::Builtin { exactness: info.exactness, reference: info.reference },
+            None => Origin::Runtime,
+        },
+    }
+}
+
+fn number(value: f64) -> String {
+    FormatOptions::default().format(value)
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parsed { input, value, spelling } => write!(f, "input: {:?} is {} of {:?}", input, number(*value), spelling),
+            Step::Resolved { spelling, name, symbol, matched } => {
+                write!(f, "unit: {:?} is {} ({}), ", spelling, name, symbol)?;
+                match matched {
+                    Match::Symbol => write!(f, "by its symbol"),
+                    Match::Name => write!(f, "by its name"),
+                    Match::Plural => write!(f, "by its plural"),
+                    Match::Alias => write!(f, "by an alias"),
+                    Match::CaseInsensitive => write!(f, "ignoring case"),
+                    Match::Prefix { prefix, scale, of } => write!(f, "as the prefix {:?} ({}) on {}", prefix, number(*scale), of),
+                }
+            }
+            Step::Dimension(dimension) => write!(f, "dimension: both units measure {}", dimension.name()),
+            Step::Definition { symbol, factor, offset, base, origin } => {
+                if *offset == 0.0 {
+                    write!(f, "definition: 1 {} = {} {}", symbol, number(*factor), base)?;
+                } else {
+                    write!(f, "definition: {} = {} * {} + {}", base, number(*factor), symbol, number(*offset))?;
+                }
+                match origin {
+                    Origin::Builtin { exactness, reference } => {
+                        let exactness = match exactness {
+                            Exactness::Exact => "exact",
+                            Exactness::Rounded => "rounded, not the exact value",
+                            Exactness::Measured => "measured, so only as good as the measurement",
+                            Exactness::Placeholder => "a placeholder, not a real conversion factor",
+                        };
+                        let reference = reference.map_or("no reference", |reference| reference.name());
+                        write!(f, " ({}; {})", exactness, reference)
+                    }
+                    Origin::Definition { line } => write!(f, " (definitions file, line {})", line),
+                    Origin::Prefix => write!(f, " (prefix applied to the unit's factor)"),
+                    Origin::Runtime => write!(f, " (defined at runtime)"),
+                }
+            }
+            Step::ToBase { value, symbol, factor, offset, result, base } => {
+                if *offset == 0.0 {
+                    write!(f, "to base: {} {} * {} = {} {}", number(*value), symbol, number(*factor), number(*result), base)
+                } else {
+                    write!(f, "to base: {} {} * {} + {} = {} {}", number(*value), symbol, number(*factor), number(*offset), number(*result), base)
+                }
+            }
+            Step::FromBase { value, base, factor, offset, result, symbol } => {
+                if *offset == 0.0 {
+                    write!(f, "from base: {} {} / {} = {} {}", number(*value), base, number(*factor), number(*result), symbol)
+                } else {
+                    write!(f, "from base: ({} {} - {}) / {} = {} {}", number(*value), base, number(*offset), number(*factor), number(*result), symbol)
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            writeln!(f, "{}. {}", i + 1, step)?;
+        }
+        let registry = UnitRegistry::builtin();
+        write!(f, "result: {}", FormatOptions::default().format_uncertain_quantity(self.result.value, self.result.uncertainty, &registry.symbol_of(&self.result.unit)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definitions(explanation: &Explanation) -> Vec<String> {
+        let definitions = explanation.steps.iter().filter(|step| matches!(step, Step::Definition { .. }));
+        definitions.map(Step::to_string).collect()
+    }
+
+    #[test]
+    fn records_each_step() {
+        let explanation = explain("20 mph", "km/h", UnitRegistry::builtin()).unwrap();
+        let steps: Vec<String> = explanation.steps.iter().map(Step::to_string).collect();
+        assert_eq!(steps[0], "input: \"20 mph\" is 20 of \"mph\"");
+        assert_eq!(steps[1], "unit: \"mph\" is mile_per_hour (mph), by its symbol");
+        assert_eq!(steps[3], "dimension: both units measure speed");
+        assert_eq!(steps.len(), 8);
+        assert!((explanation.result.value - 32.18688).abs() < 1e-12);
+    }
+
+    #[test]
+    fn definitions_say_how_far_a_factor_goes() {
+        let registry = UnitRegistry::builtin();
+        let explanation = explain("3 beaufort_scale", "m/s", registry).unwrap();
+        assert_eq!(
+            definitions(&explanation),
+            [
+                "definition: 1 beaufort_scale = 0.5 m/s (a placeholder, not a real conversion factor; no reference)",
+                "definition: 1 m/s = 1 m/s (exact; BIPM SI Brochure)",
+            ]
+        );
+        let explanation = explain("1 ly", "km", registry).unwrap();
+        assert_eq!(definitions(&explanation)[0], "definition: 1 ly = 9.461e15 m (rounded, not the exact value; NIST SP 811)");
+        let explanation = explain("1 hartree", "J", registry).unwrap();
+        assert!(definitions(&explanation)[0].ends_with("(measured, so only as good as the measurement; CODATA 2022)"));
+    }
+
+    #[test]
+    fn agrees_with_convert() {
+        let explanation = explain("37 celsius", "fahrenheit", UnitRegistry::builtin()).unwrap();
+        assert_eq!(explanation.result.value, 98.6);
+        assert!(explanation.to_string().ends_with("result: 98.6°F"));
+    }
+
+    #[test]
+    fn names_the_case_and_prefix_matches() {
+        let mut registry = UnitRegistry::new();
+        registry.define_prefix("kilo", 1000.0);
+        let explanation = explain("2 KM", "kilofeet", &registry).unwrap();
+        assert!(matches!(&explanation.steps[1], Step::Resolved { matched: Match::CaseInsensitive, .. }));
+        assert!(matches!(&explanation.steps[2], Step::Resolved { matched: Match::Prefix { prefix, .. }, .. } if prefix == "kilo"));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let registry = UnitRegistry::builtin();
+        assert!(matches!(explain("1 m", "kg", registry), Err(ExplainError::DimensionMismatch { .. })));
+        assert!(matches!(explain("1 m", "zzz", registry), Err(ExplainError::Unit(_))));
+        assert!(matches!(explain("one m", "ft", registry), Err(ExplainError::Parse(_))));
+    }
+}
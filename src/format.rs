@@ -0,0 +1,318 @@
+// Number formatting for conversion results.
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Precision {
+    Shortest,           // Fewest digits that still round-trip to the same f64
+    Decimals(usize),    // Fixed number of digits after the decimal point
+    Significant(usize), // Fixed number of significant figures, trailing zeros kept
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Notation {
+    Auto,        // Plain, switching to scientific for very large or small magnitudes
+    Plain,       // 1234.5
+    Scientific,  // 1.2345e3
+    Engineering, // 1.2345e3, 12.345e3, 123.45e3: exponent is a multiple of three
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum UncertaintyStyle {
+    PlusMinus, // (12.3 ± 0.2) mm
+    Concise,   // 12.3(2) mm
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FormatOptions {
+    pub precision: Precision,
+    pub notation: Notation,
+    pub group_digits: bool, // 1,234,567.5
+    pub space: bool,        // "20 km" rather than "20km"
+    pub uncertainty: UncertaintyStyle,
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions {
+            precision: Precision::Shortest,
+            notation: Notation::Auto,
+            group_digits: false,
+            space: false,
+            uncertainty: UncertaintyStyle::PlusMinus,
+        }
+    }
+}
+
+// Auto notation stays plain for exponents in this range.
+const AUTO_MIN_EXPONENT: i32 = -4;
+const AUTO_MAX_EXPONENT: i32 = 15;
+
+// A finite value as sign, significant digits d1 d2 d3 ... and exponent, meaning
+// d1.d2d3... * 10^exponent.
+struct Digits {
+    negative: bool,
+    digits: String,
+    exponent: i32,
+}
+
+impl Digits {
+    // `significant` of None gives the shortest round-trip digits.
+    fn new(value: f64, significant: Option<usize>) -> Digits {
+        let formatted = match significant {
+            Some(significant) => format!("{:.*e}", significant.max(1) - 1, value.abs()),
+            None => format!("{:e}", value.abs()),
+        };
+        let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+        Digits {
+            negative: value.is_sign_negative(),
+            digits: mantissa.replace('.', ""),
+            exponent: exponent.parse().unwrap_or(0),
+        }
+    }
+
+    // Digits with the decimal point moved `shift` places left of d1's position
+    // (i.e. the value divided by 10^shift), as integer and fraction parts.
+    fn split(&self, shift: i32) -> (String, String) {
+        let point = self.exponent - shift + 1; // Number of digits before the point
+        if point <= 0 {
+            let zeros = "0".repeat((-point) as usize);
+            ("0".to_string(), format!("{}{}", zeros, self.digits))
+        } else if point as usize >= self.digits.len() {
+            let zeros = "0".repeat(point as usize - self.digits.len());
+            (format!("{}{}", self.digits, zeros), String::new())
+        } else {
+            let (int, frac) = self.digits.split_at(point as usize);
+            (int.to_string(), frac.to_string())
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return format!("{}", value);
+        }
+        let notation = match self.notation {
+            Notation::Auto => {
+                let exponent = Digits::new(value, None).exponent;
+                let plain = value == 0.0
+                    || matches!(self.precision, Precision::Decimals(_))
+                    || (AUTO_MIN_EXPONENT..AUTO_MAX_EXPONENT).contains(&exponent);
+                if plain { Notation::Plain } else { Notation::Scientific }
+            }
+            notation => notation,
+        };
+        match notation {
+            Notation::Plain | Notation::Auto => self.format_plain(value),
+            Notation::Scientific => self.format_exponent(value, 1),
+            Notation::Engineering => self.format_exponent(value, 3),
+        }
+    }
+
+    // The value followed by the unit symbol, e.g. "2,000 cm".
+    pub fn format_quantity(&self, value: f64, symbol: &str) -> String {
+        let separator = if self.space { " " } else { "" };
+        format!("{}{}{}", self.format(value), separator, symbol)
+    }
+
+    // A value with its standard uncertainty. The uncertainty is given to two
+    // significant digits (one if the second is zero) and the value is rounded to the
+    // same place, so `precision` doesn't apply: "12.35 ± 0.12", "12.35(12)".
+    pub fn format_uncertain(&self, value: f64, uncertainty: f64) -> String {
+        if uncertainty == 0.0 || !uncertainty.is_finite() || !value.is_finite() {
+            return self.format(value);
+        }
+        let mut digits = Digits::new(uncertainty, Some(2));
+        if digits.digits.ends_with('0') {
+            digits.digits.pop();
+        }
+        // The uncertainty's last digit is in the 10^last place.
+        let last = digits.exponent - digits.digits.len() as i32 + 1;
+        let leading = if value.abs() > uncertainty { Digits::new(value, None).exponent } else { digits.exponent };
+        let exponent = match self.notation {
+            Notation::Plain => 0,
+            Notation::Auto if (AUTO_MIN_EXPONENT..AUTO_MAX_EXPONENT).contains(&leading) => 0,
+            Notation::Auto | Notation::Scientific => leading,
+            Notation::Engineering => leading.div_euclid(3) * 3,
+        };
+        let places = exponent - last; // Digits after the point, once scaled by 10^-exponent
+        let scale = 10f64.powi(exponent);
+        let value = self.fixed(value / scale, places);
+        let suffix = if exponent == 0 { String::new() } else { format!("e{}", exponent) };
+        match self.uncertainty {
+            UncertaintyStyle::Concise if places > 0 => format!("{}({}){}", value, digits.digits, suffix),
+            UncertaintyStyle::Concise => format!("{}({}){}", value, self.fixed(uncertainty / scale, places), suffix),
+            UncertaintyStyle::PlusMinus if exponent == 0 => {
+                format!("{} ± {}", value, self.fixed(uncertainty / scale, places))
+            }
+            UncertaintyStyle::PlusMinus => format!("({} ± {}){}", value, self.fixed(uncertainty / scale, places), suffix),
+        }
+    }
+
+    // The quantity with its uncertainty, if any: "(12.3 ± 0.2) mm" or "12.3(2) mm".
+    pub fn format_uncertain_quantity(&self, value: f64, uncertainty: Option<f64>, symbol: &str) -> String {
+        let Some(uncertainty) = uncertainty.filter(|&u| u != 0.0 && u.is_finite()) else {
+            return self.format_quantity(value, symbol);
+        };
+        let number = self.format_uncertain(value, uncertainty);
+        match self.uncertainty {
+            UncertaintyStyle::PlusMinus if !number.starts_with('(') => format!("({}) {}", number, symbol),
+            UncertaintyStyle::PlusMinus => format!("{} {}", number, symbol),
+            UncertaintyStyle::Concise => {
+                let separator = if self.space { " " } else { "" };
+                format!("{}{}{}", number, separator, symbol)
+            }
+        }
+    }
+
+    // A range of values: "10–12 bar". Always spaced, as "10–12bar" reads badly.
+    pub fn format_interval(&self, low: f64, high: f64, symbol: &str) -> String {
+        format!("{}–{} {}", self.format(low), self.format(high), symbol)
+    }
+
+    // `value` rounded to `places` digits after the point; a negative count rounds to
+    // tens, hundreds, ...
+    fn fixed(&self, value: f64, places: i32) -> String {
+        let formatted = if places >= 0 {
+            format!("{:.*}", places as usize, value.abs())
+        } else {
+            let unit = 10f64.powi(-places);
+            format!("{:.0}", (value.abs() / unit).round() * unit)
+        };
+        let (int, frac) = formatted.split_once('.').unwrap_or((&formatted, ""));
+        self.assemble(value.is_sign_negative() && formatted.contains(|c: char| ('1'..='9').contains(&c)), int, frac, None)
+    }
+
+    fn format_plain(&self, value: f64) -> String {
+        let digits = match self.precision {
+            Precision::Decimals(decimals) => {
+                let formatted = format!("{:.*}", decimals, value.abs());
+                let (int, frac) = formatted.split_once('.').unwrap_or((&formatted, ""));
+                return self.assemble(value.is_sign_negative(), int, frac, None);
+            }
+            Precision::Shortest => Digits::new(value, None),
+            Precision::Significant(significant) => Digits::new(value, Some(significant)),
+        };
+        let (int, frac) = digits.split(0);
+        self.assemble(digits.negative, &int, &frac, None)
+    }
+
+    // Scientific notation when `step` is 1, engineering when it is 3.
+    fn format_exponent(&self, value: f64, step: i32) -> String {
+        let digits = match self.precision {
+            Precision::Shortest => Digits::new(value, None),
+            Precision::Significant(significant) => Digits::new(value, Some(significant)),
+            Precision::Decimals(decimals) => {
+                // The number of integer digits depends on the exponent, which rounding
+                // can still bump (999.96e3 -> 1.00e6), so settle it in two passes.
+                let mut digits = Digits::new(value, Some(decimals + 1));
+                for _ in 0..2 {
+                    let integer_digits = (digits.exponent.rem_euclid(step) + 1) as usize;
+                    digits = Digits::new(value, Some(decimals + integer_digits));
+                }
+                digits
+            }
+        };
+        let exponent = if value == 0.0 { 0 } else { digits.exponent.div_euclid(step) * step };
+        let (int, frac) = digits.split(exponent);
+        self.assemble(digits.negative, &int, &frac, Some(exponent))
+    }
+
+    fn assemble(&self, negative: bool, int: &str, frac: &str, exponent: Option<i32>) -> String {
+        let mut out = String::new();
+        if negative {
+            out.push('-');
+        }
+        if self.group_digits {
+            out.push_str(&group(int));
+        } else {
+            out.push_str(int);
+        }
+        if !frac.is_empty() {
+            out.push('.');
+            out.push_str(frac);
+        }
+        if let Some(exponent) = exponent {
+            out.push_str(&format!("e{}", exponent));
+        }
+        out
+    }
+}
+
+// "1234567" -> "1,234,567"
+fn group(int: &str) -> String {
+    let mut grouped = String::new();
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(precision: Precision, notation: Notation) -> FormatOptions {
+        FormatOptions { precision, notation, ..FormatOptions::default() }
+    }
+
+    #[test]
+    fn shortest_round_trips() {
+        let plain = FormatOptions::default();
+        assert_eq!(plain.format(0.1 + 0.2), "0.30000000000000004");
+        assert_eq!(plain.format(1609.344), "1609.344");
+        assert_eq!(plain.format(-0.5), "-0.5");
+        assert_eq!(plain.format(0.0), "0");
+        assert_eq!(plain.format(f64::INFINITY), "inf");
+        // Auto notation goes scientific outside 1e-4 to 1e15.
+        assert_eq!(plain.format(149597870700.0), "149597870700");
+        assert_eq!(plain.format(6.02214076e23), "6.02214076e23");
+        assert_eq!(plain.format(0.00001), "1e-5");
+    }
+
+    #[test]
+    fn precision_and_notation() {
+        let cases = [
+            (Precision::Decimals(2), Notation::Auto, 1.23456, "1.23"),
+            (Precision::Decimals(2), Notation::Auto, 1e20, "100000000000000000000.00"),
+            (Precision::Significant(3), Notation::Plain, 1234.5, "1230"),
+            (Precision::Significant(4), Notation::Plain, 0.5, "0.5000"),
+            (Precision::Shortest, Notation::Scientific, 1234.5, "1.2345e3"),
+            (Precision::Shortest, Notation::Engineering, 12345.0, "12.345e3"),
+            (Precision::Shortest, Notation::Engineering, 0.00012, "120e-6"),
+            (Precision::Significant(3), Notation::Scientific, 0.000123456, "1.23e-4"),
+            (Precision::Decimals(2), Notation::Engineering, 999960.0, "1.00e6"),
+            (Precision::Shortest, Notation::Scientific, 0.0, "0e0"),
+        ];
+        for (precision, notation, value, expected) in cases {
+            assert_eq!(options(precision, notation).format(value), expected, "{:?} {:?} {}", precision, notation, value);
+        }
+    }
+
+    #[test]
+    fn quantities_group_and_space() {
+        let grouped = FormatOptions { group_digits: true, space: true, ..FormatOptions::default() };
+        assert_eq!(grouped.format_quantity(1234567.5, "m"), "1,234,567.5 m");
+        assert_eq!(grouped.format(-123.0), "-123");
+        assert_eq!(FormatOptions::default().format_quantity(20.0, "km"), "20km");
+        assert_eq!(FormatOptions::default().format_interval(10.0, 12.0, "bar"), "10–12 bar");
+    }
+
+    #[test]
+    fn uncertainties_set_the_last_place() {
+        let plus_minus = FormatOptions::default();
+        let concise = FormatOptions { uncertainty: UncertaintyStyle::Concise, ..FormatOptions::default() };
+        assert_eq!(plus_minus.format_uncertain(12.3456, 0.123), "12.35 ± 0.12");
+        assert_eq!(concise.format_uncertain(12.3456, 0.123), "12.35(12)");
+        // A second digit of zero is dropped.
+        assert_eq!(plus_minus.format_uncertain(12.34, 0.2), "12.3 ± 0.2");
+        assert_eq!(concise.format_uncertain(1234.0, 25.0), "1234(25)");
+        assert_eq!(plus_minus.format_uncertain(6.02214076e23, 1.2e20), "(6.0221 ± 0.0012)e23");
+        assert_eq!(plus_minus.format_uncertain(5.0, 0.0), "5");
+        assert_eq!(plus_minus.format_uncertain_quantity(12.34, Some(0.2), "mm"), "(12.3 ± 0.2) mm");
+        assert_eq!(concise.format_uncertain_quantity(12.34, Some(0.2), "mm"), "12.3(2)mm");
+        assert_eq!(plus_minus.format_uncertain_quantity(12.34, None, "mm"), "12.34mm");
+    }
+}
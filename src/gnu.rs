@@ -0,0 +1,972 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::enums::*;
+use crate::registry::{LoadError, UnitRegistry};
+
+// Importer for the GNU units database format (definitions.units).
+//
+// The whole database is read first, since definitions freely refer to units defined
+// further down, then every unit is evaluated to a factor over the primitive units
+// (those defined as "!"). Units whose dimension matches one of `Dimension` are added
+// to the registry. Functions and tables that are affine in their argument, such as
+// tempF(x), become units with an offset; everything else (nonlinear functions and
+// tables, dimensions this crate has no counterpart for, ...) is listed in the report.
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Unsupported {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub name: String,
+    pub reason: String,
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}: {}: {}", file.display(), self.line, self.name, self.reason),
+            None => write!(f, "line {}: {}: {}", self.line, self.name, self.reason),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct GnuImportReport {
+    pub units: usize,
+    pub prefixes: usize,
+    pub unsupported: Vec<Unsupported>,
+}
+
+#[derive(Debug, Clone)]
+struct Location {
+    file: Option<PathBuf>,
+    line: usize,
+}
+
+struct Entry {
+    definition: String,
+    location: Location,
+}
+
+// name(param) [units=[input;output]] [domain=...] [range=...] forward ; inverse
+struct Function {
+    param: String,
+    input: Option<String>, // Unit of the argument; a plain number if absent
+    forward: String,
+    location: Location,
+}
+
+// name[unit] x1 y1, x2 y2, ..., interpolated linearly between the points
+struct Table {
+    unit: String,
+    points: Vec<(f64, f64)>,
+    location: Location,
+}
+
+#[derive(Default)]
+struct Database {
+    units: HashMap<String, Entry>,
+    unit_order: Vec<String>,
+    prefixes: HashMap<String, Entry>,
+    prefix_order: Vec<String>,
+    functions: HashMap<String, Function>, // e.g. tempF
+    function_order: Vec<String>,
+    tables: HashMap<String, Table>, // e.g. wiregauge
+    table_order: Vec<String>,
+    vars: HashMap<String, String>,
+    unsupported: Vec<Unsupported>,
+}
+
+// Locale used to pick `!locale` blocks, matching the GNU default for US English.
+const LOCALE: &str = "en_US";
+
+impl Database {
+    fn report(&mut self, location: &Location, name: &str, reason: &str) {
+        self.unsupported.push(Unsupported {
+            file: location.file.clone(),
+            line: location.line,
+            name: name.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    fn var(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok().or_else(|| self.vars.get(name).cloned())
+    }
+
+    fn read(&mut self, source: &str, file: Option<&Path>, depth: usize) {
+        // Each open block records whether it is active.
+        let mut blocks: Vec<(&str, bool)> = vec![];
+        let mut lines = source.lines().enumerate();
+        while let Some((index, raw)) = lines.next() {
+            let location = Location { file: file.map(Path::to_path_buf), line: index + 1 };
+            let mut text = strip_comment(raw).to_string();
+            while text.trim_end().ends_with('\\') {
+                let trimmed = text.trim_end();
+                text = trimmed[..trimmed.len() - 1].to_string();
+                match lines.next() {
+                    Some((_, next)) => text.push_str(strip_comment(next)),
+                    None => break,
+                }
+            }
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let active = blocks.iter().all(|(_, active)| *active);
+
+            if let Some(directive) = text.strip_prefix('!') {
+                let mut words = directive.split_whitespace();
+                let command = words.next().unwrap_or("");
+                let args: Vec<&str> = words.collect();
+                match command {
+                    "locale" => blocks.push(("locale", args.first() == Some(&LOCALE))),
+                    "endlocale" => close_block(&mut blocks, "locale"),
+                    "utf8" => blocks.push(("utf8", true)),
+                    "endutf8" => close_block(&mut blocks, "utf8"),
+                    "var" | "varnot" => {
+                        let value = args.first().and_then(|name| self.var(name));
+                        let matches = value.is_some_and(|value| args[1..].contains(&value.as_str()));
+                        blocks.push(("var", matches == (command == "var")));
+                    }
+                    "endvar" => close_block(&mut blocks, "var"),
+                    _ if !active => {}
+                    "set" => {
+                        if let [name, value, ..] = args[..] {
+                            self.vars.entry(name.to_string()).or_insert_with(|| value.to_string());
+                        }
+                    }
+                    "include" => self.include(&args, file, depth, &location),
+                    _ => self.report(&location, &format!("!{}", command), "unsupported directive"),
+                }
+                continue;
+            }
+            if !active {
+                continue;
+            }
+
+            let (name, definition) = match text.split_once(char::is_whitespace) {
+                Some((name, definition)) => (name, definition.trim()),
+                None => (text, ""),
+            };
+            if let Some((function, param)) = name.split_once('(') {
+                self.function(function, param, definition, location);
+            } else if let Some((table, unit)) = name.split_once('[') {
+                self.table(table, unit, definition, location);
+            } else if definition.is_empty() {
+                self.report(&location, name, "missing definition");
+            } else if let Some(prefix) = name.strip_suffix('-') {
+                let entry = Entry { definition: definition.to_string(), location };
+                if self.prefixes.insert(prefix.to_string(), entry).is_none() {
+                    self.prefix_order.push(prefix.to_string());
+                }
+            } else {
+                let entry = Entry { definition: definition.to_string(), location: location.clone() };
+                match self.units.insert(name.to_string(), entry) {
+                    Some(_) => self.report(&location, name, "redefinition; the later definition is used"),
+                    None => self.unit_order.push(name.to_string()),
+                }
+            }
+        }
+    }
+
+    fn function(&mut self, name: &str, param: &str, definition: &str, location: Location) {
+        let Some(param) = param.strip_suffix(')').filter(|param| !param.is_empty()) else {
+            self.report(&location, name, "malformed function parameter");
+            return;
+        };
+        let mut input = None;
+        let mut rest = definition;
+        loop {
+            let (word, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if let Some(units) = word.strip_prefix("units=") {
+                let units = units.trim_start_matches('[').split(';').next().unwrap_or("").trim();
+                input = Some(units.to_string()).filter(|units| !units.is_empty());
+            } else if !(word.starts_with("domain=") || word.starts_with("range=") || word == "noerror") {
+                break;
+            }
+            rest = tail.trim_start();
+        }
+        // Only the forward definition is needed; the inverse follows the ';'.
+        let forward = rest.split(';').next().unwrap_or("").trim();
+        if forward.is_empty() {
+            self.report(&location, name, "missing definition");
+            return;
+        }
+        let function = Function { param: param.to_string(), input, forward: forward.to_string(), location: location.clone() };
+        match self.functions.insert(name.to_string(), function) {
+            Some(_) => self.report(&location, name, "redefinition; the later definition is used"),
+            None => self.function_order.push(name.to_string()),
+        }
+    }
+
+    fn table(&mut self, name: &str, unit: &str, definition: &str, location: Location) {
+        let Some(unit) = unit.strip_suffix(']').filter(|unit| !unit.is_empty()) else {
+            self.report(&location, name, "malformed table unit");
+            return;
+        };
+        let definition = definition.strip_prefix("noerror").unwrap_or(definition);
+        let point = |pair: &str| match pair.split_whitespace().collect::<Vec<_>>()[..] {
+            [x, y] => Some((x.parse::<f64>().ok()?, y.parse::<f64>().ok()?)),
+            _ => None,
+        };
+        let Some(mut points) = definition.split(',').map(point).collect::<Option<Vec<_>>>() else {
+            self.report(&location, name, "malformed table entries");
+            return;
+        };
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let table = Table { unit: unit.to_string(), points, location: location.clone() };
+        match self.tables.insert(name.to_string(), table) {
+            Some(_) => self.report(&location, name, "redefinition; the later definition is used"),
+            None => self.table_order.push(name.to_string()),
+        }
+    }
+
+    fn include(&mut self, args: &[&str], file: Option<&Path>, depth: usize, location: &Location) {
+        let (Some(file), Some(name)) = (file, args.first()) else {
+            self.report(location, "!include", "includes can only be followed when loading from a file");
+            return;
+        };
+        if depth >= 16 {
+            self.report(location, name, "include nesting is too deep");
+            return;
+        }
+        let path = file.parent().unwrap_or(Path::new(".")).join(name);
+        match std::fs::read_to_string(&path) {
+            Ok(source) => self.read(&source, Some(&path), depth + 1),
+            Err(err) => self.report(location, name, &format!("cannot include {}: {}", path.display(), err)),
+        }
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    line.split('#').next().unwrap_or("")
+}
+
+fn close_block(blocks: &mut Vec<(&str, bool)>, kind: &str) {
+    if let Some(index) = blocks.iter().rposition(|(open, _)| *open == kind) {
+        blocks.truncate(index);
+    }
+}
+
+// A factor together with exponents of the primitive units.
+#[derive(Debug, Clone, PartialEq)]
+struct Value {
+    factor: f64,
+    dims: BTreeMap<String, i32>,
+}
+
+impl Value {
+    fn number(factor: f64) -> Value {
+        Value { factor, dims: BTreeMap::new() }
+    }
+
+    fn is_dimensionless(&self) -> bool {
+        self.dims.is_empty()
+    }
+
+    fn mul(mut self, other: Value, sign: i32) -> Value {
+        self.factor = if sign > 0 { self.factor * other.factor } else { self.factor / other.factor };
+        for (dim, exp) in other.dims {
+            let total = self.dims.get(&dim).copied().unwrap_or(0) + sign * exp;
+            if total == 0 {
+                self.dims.remove(&dim);
+            } else {
+                self.dims.insert(dim, total);
+            }
+        }
+        self
+    }
+
+    fn pow(self, exponent: f64) -> Result<Value, String> {
+        let mut dims = BTreeMap::new();
+        for (dim, exp) in self.dims {
+            let scaled = exp as f64 * exponent;
+            if scaled.fract() != 0.0 {
+                return Err(format!("{} raised to a non-integral power", dim));
+            }
+            dims.insert(dim, scaled as i32);
+        }
+        Ok(Value { factor: self.factor.powf(exponent), dims })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String, i32), // Unit name with an attached exponent, as in "cm3"
+    Op(char),          // + - * / | ^ ( )
+}
+
+const OPERATORS: &str = "+-*/|^()";
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // Only treat 'e' as an exponent when digits follow, since "e" is also a unit.
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let number: String = chars[start..i].iter().collect();
+            tokens.push(Token::Number(number.parse().map_err(|_| format!("invalid number {:?}", number))?));
+        } else if c == '*' && chars.get(i + 1) == Some(&'*') {
+            tokens.push(Token::Op('^'));
+            i += 2;
+        } else if OPERATORS.contains(c) {
+            tokens.push(Token::Op(c));
+            i += 1;
+        } else {
+            let start = i;
+            while i < chars.len() && !chars[i].is_whitespace() && !OPERATORS.contains(chars[i]) {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let digits = word.len() - word.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+            let stem = &word[..word.len() - digits];
+            if digits > 0 && !stem.is_empty() && !stem.ends_with('_') {
+                tokens.push(Token::Name(stem.to_string(), word[stem.len()..].parse().map_err(|_| format!("invalid exponent in {:?}", word))?));
+            } else if word == "per" {
+                tokens.push(Token::Op('/'));
+            } else {
+                tokens.push(Token::Name(word, 1));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum EvalError {
+    Unknown(String),
+    Function(String),
+    Table(String),
+    Cycle(String),
+    Invalid(String),
+}
+
+impl EvalError {
+    fn reason(&self) -> String {
+        match self {
+            EvalError::Unknown(name) => format!("depends on unknown unit {:?}", name),
+            EvalError::Function(name) => format!("uses function {:?} without an argument", name),
+            EvalError::Table(name) => format!("uses table {:?} without an argument", name),
+            EvalError::Cycle(name) => format!("circular definition through {:?}", name),
+            EvalError::Invalid(msg) => msg.clone(),
+        }
+    }
+}
+
+struct Evaluator<'a> {
+    db: &'a Database,
+    units: HashMap<String, Result<Value, EvalError>>,
+    prefixes: HashMap<String, Result<Value, EvalError>>,
+    resolving: HashSet<String>,
+    bindings: HashMap<String, Value>, // The parameter while a function body is evaluated
+}
+
+impl Evaluator<'_> {
+    fn unit(&mut self, name: &str) -> Result<Value, EvalError> {
+        if let Some(value) = self.units.get(name) {
+            return value.clone();
+        }
+        let definition = self.db.units[name].definition.clone();
+        let value = self.definition(name, &definition);
+        self.units.insert(name.to_string(), value.clone());
+        value
+    }
+
+    fn prefix(&mut self, name: &str) -> Result<Value, EvalError> {
+        if let Some(value) = self.prefixes.get(name) {
+            return value.clone();
+        }
+        let definition = self.db.prefixes[name].definition.clone();
+        let value = self.definition(&format!("{}-", name), &definition);
+        self.prefixes.insert(name.to_string(), value.clone());
+        value
+    }
+
+    fn definition(&mut self, key: &str, definition: &str) -> Result<Value, EvalError> {
+        if !self.resolving.insert(key.to_string()) {
+            return Err(EvalError::Cycle(key.to_string()));
+        }
+        let value = match definition {
+            "!" => Ok(Value { factor: 1.0, dims: BTreeMap::from([(key.to_string(), 1)]) }),
+            "!dimensionless" => Ok(Value::number(1.0)),
+            _ => self.detached(definition),
+        };
+        self.resolving.remove(key);
+        value
+    }
+
+    // An expression outside any function body, so a parameter doesn't shadow a unit.
+    fn detached(&mut self, text: &str) -> Result<Value, EvalError> {
+        let bindings = std::mem::take(&mut self.bindings);
+        let value = self.expression(text);
+        self.bindings = bindings;
+        value
+    }
+
+    fn call(&mut self, name: &str, argument: Value) -> Result<Value, EvalError> {
+        let db = self.db;
+        if let Some(function) = db.functions.get(name) {
+            let key = format!("{}()", name);
+            if !self.resolving.insert(key.clone()) {
+                return Err(EvalError::Cycle(key));
+            }
+            let value = self.apply(name, function, argument);
+            self.resolving.remove(&key);
+            value
+        } else if let Some(table) = db.tables.get(name) {
+            self.interpolate(name, table, argument)
+        } else {
+            apply_function(name, argument)
+        }
+    }
+
+    fn apply(&mut self, name: &str, function: &Function, argument: Value) -> Result<Value, EvalError> {
+        if let Some(units) = &function.input
+            && self.detached(units)?.dims != argument.dims
+        {
+            return Err(EvalError::Invalid(format!("{}() needs an argument in {}", name, units)));
+        }
+        let bindings = std::mem::replace(&mut self.bindings, HashMap::from([(function.param.clone(), argument)]));
+        let value = self.expression(&function.forward);
+        self.bindings = bindings;
+        value
+    }
+
+    fn interpolate(&mut self, name: &str, table: &Table, argument: Value) -> Result<Value, EvalError> {
+        if !argument.is_dimensionless() {
+            return Err(EvalError::Invalid(format!("{}[] needs a dimensionless argument", name)));
+        }
+        let x = argument.factor;
+        let y = table.points.windows(2).find(|pair| pair[0].0 <= x && x <= pair[1].0).map(|pair| {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            if x1 == x0 { y0 } else { y0 + (y1 - y0) * (x - x0) / (x1 - x0) }
+        });
+        let y = match (y, &table.points[..]) {
+            (Some(y), _) => y,
+            (None, [(x0, y0)]) if *x0 == x => *y0,
+            _ => return Err(EvalError::Invalid(format!("{} is outside the table {}", x, name))),
+        };
+        Ok(Value::number(y).mul(self.detached(&table.unit)?, 1))
+    }
+
+    // A function or table as `slope * x + offset` over the primitive units, from
+    // its values at `xs`. Invalid unless every value lies on that line.
+    fn affine(&mut self, name: &str, xs: &[f64], input: &Value) -> Result<(Value, f64), EvalError> {
+        let mut points = vec![];
+        for &x in xs {
+            let argument = Value { factor: x * input.factor, dims: input.dims.clone() };
+            points.push((x, self.call(name, argument)?));
+        }
+        let nonlinear = || EvalError::Invalid("not linear in its argument".to_string());
+        // The widest pair of points, so that a large offset costs the slope little.
+        let [(x0, first), .., (x1, last)] = &points[..] else {
+            return Err(nonlinear());
+        };
+        let slope = (last.factor - first.factor) / (x1 - x0);
+        let offset = first.factor - slope * x0;
+        let on_line = |(x, value): &(f64, Value)| {
+            let expected = slope * x + offset;
+            value.dims == first.dims && (value.factor - expected).abs() <= 1e-9 * value.factor.abs().max(expected.abs()).max(offset.abs())
+        };
+        if slope == 0.0 || !slope.is_finite() || !offset.is_finite() || !points.iter().all(on_line) {
+            return Err(nonlinear());
+        }
+        Ok((Value { factor: slope, dims: first.dims.clone() }, offset))
+    }
+
+    fn expression(&mut self, text: &str) -> Result<Value, EvalError> {
+        let tokens = tokenize(text).map_err(EvalError::Invalid)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let value = parser.sum(self)?;
+        if parser.pos != parser.tokens.len() {
+            return Err(EvalError::Invalid(format!("unexpected {:?} in {:?}", parser.tokens[parser.pos], text)));
+        }
+        Ok(value)
+    }
+
+    // Exact names first, then prefixes on their own, plurals, and prefixed units,
+    // in the same order as GNU units.
+    fn name(&mut self, name: &str) -> Result<Value, EvalError> {
+        if let Some(value) = self.bindings.get(name) {
+            return Ok(value.clone());
+        }
+        if let Some(value) = self.plain_name(name) {
+            return value;
+        }
+        for singular in singulars(name) {
+            if let Some(value) = self.plain_name(&singular) {
+                return value;
+            }
+        }
+        let prefix = self
+            .db
+            .prefix_order
+            .iter()
+            .filter(|prefix| name.len() > prefix.len() && name.starts_with(prefix.as_str()))
+            .max_by_key(|prefix| prefix.len())
+            .cloned();
+        if let Some(prefix) = prefix {
+            let rest = &name[prefix.len()..];
+            let unit = std::iter::once(rest.to_string())
+                .chain(singulars(rest))
+                .find_map(|candidate| self.plain_name(&candidate));
+            if let Some(unit) = unit {
+                return Ok(self.prefix(&prefix)?.mul(unit?, 1));
+            }
+        }
+        Err(EvalError::Unknown(name.to_string()))
+    }
+
+    fn plain_name(&mut self, name: &str) -> Option<Result<Value, EvalError>> {
+        if self.db.units.contains_key(name) {
+            Some(self.unit(name))
+        } else if self.db.prefixes.contains_key(name) {
+            Some(self.prefix(name))
+        } else if self.db.functions.contains_key(name) {
+            Some(Err(EvalError::Function(name.to_string())))
+        } else if self.db.tables.contains_key(name) {
+            Some(Err(EvalError::Table(name.to_string())))
+        } else {
+            None
+        }
+    }
+}
+
+fn singulars(name: &str) -> Vec<String> {
+    let mut forms = vec![];
+    if let Some(stem) = name.strip_suffix("ies") {
+        forms.push(format!("{}y", stem));
+    }
+    if let Some(stem) = name.strip_suffix("es") {
+        forms.push(stem.to_string());
+    }
+    if let Some(stem) = name.strip_suffix('s') {
+        forms.push(stem.to_string());
+    }
+    forms.retain(|form| !form.is_empty());
+    forms
+}
+
+// Precedence, loosest first: + -, then * / per, then juxtaposition, then ^, then |.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, op: char) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn sum(&mut self, eval: &mut Evaluator) -> Result<Value, EvalError> {
+        let negate = self.eat('-');
+        if !negate {
+            self.eat('+');
+        }
+        let mut value = self.product(eval)?;
+        if negate {
+            value.factor = -value.factor;
+        }
+        loop {
+            let sign = if self.eat('+') {
+                1.0
+            } else if self.eat('-') {
+                -1.0
+            } else {
+                return Ok(value);
+            };
+            let rhs = self.product(eval)?;
+            if rhs.dims != value.dims {
+                return Err(EvalError::Invalid("sum of values with different dimensions".to_string()));
+            }
+            value.factor += sign * rhs.factor;
+        }
+    }
+
+    fn product(&mut self, eval: &mut Evaluator) -> Result<Value, EvalError> {
+        let mut value = self.juxtaposition(eval)?;
+        loop {
+            if self.eat('*') {
+                value = value.mul(self.juxtaposition(eval)?, 1);
+            } else if self.eat('/') {
+                value = value.mul(self.juxtaposition(eval)?, -1);
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn juxtaposition(&mut self, eval: &mut Evaluator) -> Result<Value, EvalError> {
+        let mut value = self.power(eval)?;
+        while matches!(self.peek(), Some(Token::Number(_) | Token::Name(..) | Token::Op('('))) {
+            value = value.mul(self.power(eval)?, 1);
+        }
+        Ok(value)
+    }
+
+    fn power(&mut self, eval: &mut Evaluator) -> Result<Value, EvalError> {
+        let base = self.atom(eval)?;
+        if !self.eat('^') {
+            return Ok(base);
+        }
+        let negate = self.eat('-');
+        let exponent = self.power(eval)?;
+        if !exponent.is_dimensionless() {
+            return Err(EvalError::Invalid("exponent must be dimensionless".to_string()));
+        }
+        base.pow(if negate { -exponent.factor } else { exponent.factor }).map_err(EvalError::Invalid)
+    }
+
+    fn atom(&mut self, eval: &mut Evaluator) -> Result<Value, EvalError> {
+        let token = self.peek().cloned().ok_or_else(|| EvalError::Invalid("unexpected end of definition".to_string()))?;
+        self.pos += 1;
+        match token {
+            Token::Number(number) => {
+                if self.eat('|') {
+                    match self.peek().cloned() {
+                        Some(Token::Number(denominator)) => {
+                            self.pos += 1;
+                            return Ok(Value::number(number / denominator));
+                        }
+                        _ => return Err(EvalError::Invalid("'|' must be followed by a number".to_string())),
+                    }
+                }
+                Ok(Value::number(number))
+            }
+            Token::Op('(') => {
+                let value = self.sum(eval)?;
+                if !self.eat(')') {
+                    return Err(EvalError::Invalid("missing ')'".to_string()));
+                }
+                Ok(value)
+            }
+            Token::Name(name, exponent) => {
+                if exponent == 1 && self.peek() == Some(&Token::Op('(')) && is_function(&name, eval) {
+                    self.pos += 1;
+                    let argument = self.sum(eval)?;
+                    if !self.eat(')') {
+                        return Err(EvalError::Invalid("missing ')'".to_string()));
+                    }
+                    return eval.call(&name, argument);
+                }
+                eval.name(&name)?.pow(exponent as f64).map_err(EvalError::Invalid)
+            }
+            Token::Op(op) => Err(EvalError::Invalid(format!("unexpected '{}'", op))),
+        }
+    }
+}
+
+const MATH_FUNCTIONS: [&str; 16] = [
+    "sqrt", "cuberoot", "exp", "ln", "log", "log2", "sin", "cos", "tan", "asin", "acos", "atan", "sinh", "cosh", "tanh", "abs",
+];
+
+fn is_function(name: &str, eval: &Evaluator) -> bool {
+    MATH_FUNCTIONS.contains(&name) || eval.db.functions.contains_key(name) || eval.db.tables.contains_key(name)
+}
+
+fn apply_function(name: &str, argument: Value) -> Result<Value, EvalError> {
+    let root = |value: Value, n: f64| value.pow(1.0 / n).map_err(EvalError::Invalid);
+    match name {
+        "sqrt" => return root(argument, 2.0),
+        "cuberoot" => return root(argument, 3.0),
+        "abs" => return Ok(Value { factor: argument.factor.abs(), dims: argument.dims }),
+        _ => {}
+    }
+    if !argument.is_dimensionless() {
+        return Err(EvalError::Invalid(format!("{}() needs a dimensionless argument", name)));
+    }
+    let x = argument.factor;
+    Ok(Value::number(match name {
+        "exp" => x.exp(),
+        "ln" => x.ln(),
+        "log" => x.log10(),
+        "log2" => x.log2(),
+        "sin" => x.sin(),
+        "cos" => x.cos(),
+        "tan" => x.tan(),
+        "asin" => x.asin(),
+        "acos" => x.acos(),
+        "atan" => x.atan(),
+        "sinh" => x.sinh(),
+        "cosh" => x.cosh(),
+        _ => x.tanh(),
+    }))
+}
+
+// Exponents of the GNU primitive units m, kg, s, A, K and cd for each dimension.
+fn dimension_of(dims: &BTreeMap<String, i32>) -> Option<Dimension> {
+    let exponents: Vec<(&str, i32)> = dims.iter().map(|(dim, exp)| (dim.as_str(), *exp)).collect();
+    let matches = |mut wanted: Vec<(&str, i32)>| {
+        wanted.sort();
+        wanted == exponents
+    };
+    [
+        (Dimension::Length, vec![("m", 1)]),
+        (Dimension::Mass, vec![("kg", 1)]),
+        (Dimension::Area, vec![("m", 2)]),
+        (Dimension::Volume, vec![("m", 3)]),
+        (Dimension::Force, vec![("kg", 1), ("m", 1), ("s", -2)]),
+        (Dimension::Energy, vec![("kg", 1), ("m", 2), ("s", -2)]),
+        (Dimension::Temperature, vec![("K", 1)]),
+        (Dimension::Time, vec![("s", 1)]),
+        (Dimension::Speed, vec![("m", 1), ("s", -1)]),
+        (Dimension::Pressure, vec![("kg", 1), ("m", -1), ("s", -2)]),
+        (Dimension::Frequency, vec![("s", -1)]),
+        (Dimension::ElectricCurrent, vec![("A", 1)]),
+        (Dimension::Voltage, vec![("A", -1), ("kg", 1), ("m", 2), ("s", -3)]),
+        (Dimension::Capacitance, vec![("A", 2), ("kg", -1), ("m", -2), ("s", 4)]),
+        (Dimension::Luminosity, vec![("cd", 1)]),
+        (Dimension::Radiation, vec![("m", 2), ("s", -2)]),
+        (Dimension::MagneticFlux, vec![("A", -1), ("kg", 1), ("m", 2), ("s", -2)]),
+    ]
+    .into_iter()
+    .find(|(_, wanted)| matches(wanted.clone()))
+    .map(|(dimension, _)| dimension)
+}
+
+fn describe(dims: &BTreeMap<String, i32>) -> String {
+    if dims.is_empty() {
+        return "dimensionless".to_string();
+    }
+    let parts: Vec<String> = dims
+        .iter()
+        .map(|(dim, exp)| if *exp == 1 { dim.clone() } else { format!("{}^{}", dim, exp) })
+        .collect();
+    parts.join(" ")
+}
+
+fn import(registry: &mut UnitRegistry, mut db: Database) -> GnuImportReport {
+    let mut report = GnuImportReport::default();
+    let mut eval = Evaluator {
+        db: &db,
+        units: HashMap::new(),
+        prefixes: HashMap::new(),
+        resolving: HashSet::new(),
+        bindings: HashMap::new(),
+    };
+    let mut unsupported = vec![];
+
+    for prefix in &db.prefix_order {
+        let entry = &db.prefixes[prefix];
+        match eval.prefix(prefix) {
+            Ok(value) if value.is_dimensionless() => {
+                registry.define_prefix(prefix, value.factor);
+                report.prefixes += 1;
+            }
+            Ok(value) => unsupported.push((entry.location.clone(), prefix.clone(), format!("prefix has dimension {}", describe(&value.dims)))),
+            Err(err) => unsupported.push((entry.location.clone(), prefix.clone(), err.reason())),
+        }
+    }
+
+    for name in &db.unit_order {
+        let entry = &db.units[name];
+        let location = entry.location.clone();
+        let value = match eval.unit(name) {
+            Ok(value) => value,
+            Err(err) => {
+                unsupported.push((location, name.clone(), err.reason()));
+                continue;
+            }
+        };
+        match define(registry, name, &value, 0.0, &location) {
+            Ok(()) => report.units += 1,
+            Err(reason) => unsupported.push((location, name.clone(), reason)),
+        }
+    }
+
+    // Affine functions and tables become units with an offset: tempF(x) is x °F
+    // in kelvin, so "1 tempF" is -17.2 °C.
+    let mut lines = vec![];
+    for name in &db.function_order {
+        let function = &db.functions[name];
+        let input = eval.detached(function.input.as_deref().unwrap_or("1"));
+        lines.push((name, &function.location, input.and_then(|input| eval.affine(name, &[0.0, 1.0, 2.0, 1024.0], &input))));
+    }
+    for name in &db.table_order {
+        let table = &db.tables[name];
+        let xs: Vec<f64> = table.points.iter().map(|(x, _)| *x).collect();
+        lines.push((name, &table.location, eval.affine(name, &xs, &Value::number(1.0))));
+    }
+    for (name, location, line) in lines {
+        let defined = line.map_err(|err| err.reason()).and_then(|(slope, offset)| define(registry, name, &slope, offset, location));
+        match defined {
+            Ok(()) => report.units += 1,
+            Err(reason) => unsupported.push((location.clone(), name.clone(), reason)),
+        }
+    }
+
+    for (location, name, reason) in unsupported {
+        db.report(&location, &name, &reason);
+    }
+    report.unsupported = db.unsupported;
+    report
+}
+
+// Adds `name` as `value` plus `offset` in base units, or gives the reason it can't be. GNU's
+// linear temperature units (degC, degF, ...) are temperature differences, so they
+// come out as kelvin multiples like K itself.
+fn define(registry: &mut UnitRegistry, name: &str, value: &Value, offset: f64, location: &Location) -> Result<(), String> {
+    let dimension = dimension_of(&value.dims).ok_or_else(|| format!("no matching dimension for {}", describe(&value.dims)))?;
+    let unit = CustomUnit { name: name.to_string(), dimension, factor: value.factor, offset };
+    registry.define(unit, vec![name.to_string()], location.line).map_err(|taken| format!("{:?} is already defined", taken))
+}
+
+pub fn import_str(registry: &mut UnitRegistry, source: &str) -> GnuImportReport {
+    let mut db = Database::default();
+    db.read(source, None, 0);
+    import(registry, db)
+}
+
+pub fn import_file(registry: &mut UnitRegistry, path: &Path) -> Result<GnuImportReport, LoadError> {
+    let source = std::fs::read_to_string(path).map_err(|err| LoadError::Io(path.to_path_buf(), err))?;
+    let mut db = Database::default();
+    db.read(&source, Some(path), 0);
+    Ok(import(registry, db))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Primitive units and inch and pound collide with built-in spellings and are
+    // reported as such; the rest use names of their own.
+    const DATABASE: &str = "m !
+kg !
+s !
+K !
+A !
+cd !
+k- 1000
+c- 1|100
+inch 2.54 cm
+nail 2.25 inch
+stick 2 nail
+pound 0.45359237 kg
+grav 9.80665 m/s^2
+shortforce 2000 pound grav
+degC K
+degF 5|9 degC
+stdtemp 273.15 K
+tempC(x) units=[1;K] domain=[-273.15,) range=[0,) \\
+    x K + stdtemp ; (tempC +(-stdtemp))/K
+tempF(x) units=[1;K] domain=[-459.67,) range=[0,) \\
+    (x+(-32)) degF + stdtemp ; (tempF+(-stdtemp))/degF + 32
+decibel(x) units=[1;1] 10^(x/10) ; 10 log(decibel)
+shoe[inch] 0 8, 10 9.5, 20 11.5
+twice[m] 0 0, 1 2, 5 10
+wiggle(x) x mol
+";
+
+    fn imported() -> (UnitRegistry, GnuImportReport) {
+        let mut registry = UnitRegistry::new();
+        let report = import_str(&mut registry, DATABASE);
+        (registry, report)
+    }
+
+    fn base(registry: &UnitRegistry, value: f64, name: &str) -> f64 {
+        match registry.lookup(name) {
+            Some(Unit::Custom(unit)) => value * unit.factor + unit.offset,
+            other => panic!("{} resolved to {:?}", name, other),
+        }
+    }
+
+    fn skipped(report: &GnuImportReport) -> Vec<(usize, &str, &str)> {
+        let skipped = report.unsupported.iter().map(|entry| (entry.line, entry.name.as_str(), entry.reason.as_str()));
+        skipped.filter(|(.., reason)| !reason.ends_with("is already defined")).collect()
+    }
+
+    #[test]
+    fn evaluates_prefixed_and_derived_units() {
+        let (registry, _) = imported();
+        assert!((base(&registry, 1.0, "stick") - 0.1143).abs() < 1e-15);
+        assert!((base(&registry, 1.0, "shortforce") - 8896.443230521).abs() < 1e-9);
+        assert_eq!(registry.lookup("shortforce").map(|unit| crate::get_unit_dimension(&unit)), Some(Dimension::Force));
+    }
+
+    #[test]
+    fn kelvin_multiples_are_temperatures() {
+        let (registry, _) = imported();
+        let degf = registry.lookup("degF").unwrap();
+        assert_eq!(crate::get_unit_dimension(&degf), Dimension::Temperature);
+        assert!((base(&registry, 9.0, "degF") - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn affine_functions_become_offset_units() {
+        let (registry, _) = imported();
+        assert!((base(&registry, 100.0, "tempC") - 373.15).abs() < 1e-12);
+        assert!((base(&registry, 212.0, "tempF") - 373.15).abs() < 1e-12);
+        assert!((base(&registry, -40.0, "tempF") - base(&registry, -40.0, "tempC")).abs() < 1e-12);
+    }
+
+    #[test]
+    fn linear_tables_are_units_and_others_are_reported() {
+        let (registry, report) = imported();
+        assert!((base(&registry, 3.0, "twice") - 6.0).abs() < 1e-15);
+        assert!(registry.lookup("shoe").is_none());
+        assert!(skipped(&report).contains(&(23, "shoe", "not linear in its argument")));
+    }
+
+    #[test]
+    fn reports_each_skipped_entry_with_its_line() {
+        let (_, report) = imported();
+        let expected = [
+            (13, "grav", "no matching dimension for m s^-2"),
+            (22, "decibel", "not linear in its argument"),
+            (25, "wiggle", "depends on unknown unit \"mol\""),
+            (23, "shoe", "not linear in its argument"),
+        ];
+        assert_eq!(skipped(&report), expected);
+        let inch = report.unsupported.iter().find(|entry| entry.name == "inch").unwrap();
+        assert_eq!(inch.to_string(), "line 9: inch: \"inch\" is already defined");
+    }
+
+    #[test]
+    fn tables_interpolate_inside_definitions() {
+        let mut registry = UnitRegistry::new();
+        let report = import_str(&mut registry, "m !\nsize[m] 0 1, 10 2, 20 4\nbig size(5)\nhuge size(21)\n");
+        assert!((base(&registry, 1.0, "big") - 1.5).abs() < 1e-15);
+        assert_eq!(skipped(&report), [(4, "huge", "21 is outside the table size"), (2, "size", "not linear in its argument")]);
+    }
+
+    #[test]
+    fn parameters_do_not_leak_into_units() {
+        let mut registry = UnitRegistry::new();
+        let report = import_str(&mut registry, "m !\nx 2 m\ndbl(x) units=[m;m] x + x\ntrip 3 dbl(x)\nbad dbl(2)\n");
+        assert!((base(&registry, 1.0, "trip") - 12.0).abs() < 1e-15);
+        assert!((base(&registry, 1.0, "dbl") - 2.0).abs() < 1e-15);
+        assert_eq!(skipped(&report), [(5, "bad", "dbl() needs an argument in m")]);
+    }
+}
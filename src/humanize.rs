@@ -0,0 +1,271 @@
+use crate::enums::*;
+use crate::registry::{System, UnitRegistry};
+use crate::{Quantity, get_unit_dimension};
+
+// Picks the most readable unit for a quantity: 0.000003 s -> 3 µs,
+// 15000000 m -> 15000 km, 86400 s -> 1 day.
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum UnitSystem {
+    Si,
+    Imperial, // Imperial and US customary units
+    Mixed,    // Either of the above
+}
+
+// Units considered for display, smallest first. Dimensions without imperial
+// units share the SI ladder; temperatures are never rescaled.
+fn ladder(dimension: Dimension, system: UnitSystem) -> Vec<Unit> {
+    use Dimension as D;
+    match (dimension, system) {
+        (D::Length, UnitSystem::Imperial) => vec![
+            Unit::Length(Length::Inch),
+            Unit::Length(Length::Foot),
+            Unit::Length(Length::Mile),
+        ],
+        (D::Mass, UnitSystem::Imperial) => vec![
+            Unit::Mass(Mass::Ounce),
+            Unit::Mass(Mass::Pounds),
+            Unit::Mass(Mass::Ton),
+        ],
+        (D::Area, UnitSystem::Imperial) => vec![
+            Unit::Area(Area::SquareInch),
+            Unit::Area(Area::SquareFoot),
+            Unit::Area(Area::Acre),
+            Unit::Area(Area::SquareMile),
+        ],
+        (D::Volume, UnitSystem::Imperial) => vec![
+            Unit::Volume(Volume::FluidOunce),
+            Unit::Volume(Volume::Gallon),
+        ],
+        (D::Speed, UnitSystem::Imperial) => vec![
+            Unit::Speed(Speed::InchPerSecond),
+            Unit::Speed(Speed::FootPerSecond),
+            Unit::Speed(Speed::MilePerHour),
+        ],
+        (D::Force, UnitSystem::Imperial) => vec![
+            Unit::Force(Force::OunceForce),
+            Unit::Force(Force::PoundForce),
+            Unit::Force(Force::Kip),
+        ],
+        (D::Energy, UnitSystem::Imperial) => vec![
+            Unit::Energy(Energy::FootPound),
+            Unit::Energy(Energy::BritishThermalUnit),
+            Unit::Energy(Energy::Therm),
+        ],
+        (D::Pressure, UnitSystem::Imperial) => vec![
+            Unit::Pressure(Pressure::PoundPerSquareInch),
+            Unit::Pressure(Pressure::KipPerSquareInch),
+        ],
+        (dimension, UnitSystem::Mixed) => {
+            let mut units = ladder(dimension, UnitSystem::Si);
+            for unit in ladder(dimension, UnitSystem::Imperial) {
+                if !units.contains(&unit) {
+                    units.push(unit);
+                }
+            }
+            units
+        }
+        (D::Length, _) => vec![
+            Unit::Length(Length::Attometer),
+            Unit::Length(Length::Femtometer),
+            Unit::Length(Length::Picometer),
+            Unit::Length(Length::Nanometer),
+            Unit::Length(Length::Micron),
+            Unit::Length(Length::Millimeter),
+            Unit::Length(Length::Meter),
+            Unit::Length(Length::Kilometer),
+        ],
+        (D::Mass, _) => vec![
+            Unit::Mass(Mass::Attogram),
+            Unit::Mass(Mass::Femtogram),
+            Unit::Mass(Mass::Picogram),
+            Unit::Mass(Mass::Nanogram),
+            Unit::Mass(Mass::Microgram),
+            Unit::Mass(Mass::Milligram),
+            Unit::Mass(Mass::Gram),
+            Unit::Mass(Mass::Kilogram),
+            Unit::Mass(Mass::MetricTon),
+        ],
+        (D::Area, _) => vec![
+            Unit::Area(Area::SquareMillimeter),
+            Unit::Area(Area::SquareCentimeter),
+            Unit::Area(Area::SquareMeter),
+            Unit::Area(Area::Hectare),
+            Unit::Area(Area::SquareKilometer),
+        ],
+        (D::Volume, _) => vec![
+            Unit::Volume(Volume::CubicMillimeter),
+            Unit::Volume(Volume::Milliliter),
+            Unit::Volume(Volume::Liter),
+            Unit::Volume(Volume::CubicMeter),
+        ],
+        (D::Force, _) => vec![
+            Unit::Force(Force::Millinewton),
+            Unit::Force(Force::Newton),
+            Unit::Force(Force::Kilonewton),
+            Unit::Force(Force::Meganewton),
+        ],
+        (D::Energy, _) => vec![
+            Unit::Energy(Energy::Electronvolt),
+            Unit::Energy(Energy::Joule),
+            Unit::Energy(Energy::KilowattHour),
+        ],
+        (D::Time, _) => vec![
+            Unit::Time(Time::Picosecond),
+            Unit::Time(Time::Nanosecond),
+            Unit::Time(Time::Microsecond),
+            Unit::Time(Time::Millisecond),
+            Unit::Time(Time::Second),
+            Unit::Time(Time::Minute),
+            Unit::Time(Time::Hour),
+            Unit::Time(Time::Day),
+            Unit::Time(Time::GregorianYear),
+        ],
+        (D::Speed, _) => vec![
+            Unit::Speed(Speed::MeterPerSecond),
+            Unit::Speed(Speed::KilometerPerSecond),
+        ],
+        (D::Pressure, _) => vec![
+            Unit::Pressure(Pressure::Pascal),
+            Unit::Pressure(Pressure::Kilopascal),
+            Unit::Pressure(Pressure::Megapascal),
+        ],
+        (D::Frequency, _) => vec![
+            Unit::Frequency(Frequency::Nanohertz),
+            Unit::Frequency(Frequency::Microhertz),
+            Unit::Frequency(Frequency::Millihertz),
+            Unit::Frequency(Frequency::Hertz),
+            Unit::Frequency(Frequency::Kilohertz),
+            Unit::Frequency(Frequency::Megahertz),
+            Unit::Frequency(Frequency::Gigahertz),
+            Unit::Frequency(Frequency::Terahertz),
+        ],
+        (D::ElectricCurrent, _) => vec![
+            Unit::ElectricCurrent(ElectricCurrent::Picoampere),
+            Unit::ElectricCurrent(ElectricCurrent::Nanoampere),
+            Unit::ElectricCurrent(ElectricCurrent::Microampere),
+            Unit::ElectricCurrent(ElectricCurrent::Milliampere),
+            Unit::ElectricCurrent(ElectricCurrent::Ampere),
+            Unit::ElectricCurrent(ElectricCurrent::Kiloampere),
+            Unit::ElectricCurrent(ElectricCurrent::Megaampere),
+        ],
+        (D::Voltage, _) => vec![
+            Unit::Voltage(Voltage::Picovolt),
+            Unit::Voltage(Voltage::Nanovolt),
+            Unit::Voltage(Voltage::Microvolt),
+            Unit::Voltage(Voltage::Millivolt),
+            Unit::Voltage(Voltage::Volt),
+            Unit::Voltage(Voltage::Kilovolt),
+            Unit::Voltage(Voltage::Megavolt),
+            Unit::Voltage(Voltage::Gigavolt),
+            Unit::Voltage(Voltage::Teravolt),
+        ],
+        (D::Capacitance, _) => vec![
+            Unit::Capacitance(Capacitance::Attofarad),
+            Unit::Capacitance(Capacitance::Femtofarad),
+            Unit::Capacitance(Capacitance::Picofarad),
+            Unit::Capacitance(Capacitance::Nanofarad),
+            Unit::Capacitance(Capacitance::Microfarad),
+            Unit::Capacitance(Capacitance::Millifarad),
+            Unit::Capacitance(Capacitance::Farad),
+            Unit::Capacitance(Capacitance::Kilofarad),
+            Unit::Capacitance(Capacitance::Megafarad),
+            Unit::Capacitance(Capacitance::Gigafarad),
+            Unit::Capacitance(Capacitance::Terafarad),
+        ],
+        (D::Radiation, _) => vec![
+            Unit::Radiation(Radiation::Picogray),
+            Unit::Radiation(Radiation::Nanogray),
+            Unit::Radiation(Radiation::Microgray),
+            Unit::Radiation(Radiation::Milligray),
+            Unit::Radiation(Radiation::Gray),
+        ],
+        (D::MagneticFlux, _) => vec![
+            Unit::MagneticFlux(MagneticFlux::Attoweber),
+            Unit::MagneticFlux(MagneticFlux::Femtoweber),
+            Unit::MagneticFlux(MagneticFlux::Picoweber),
+            Unit::MagneticFlux(MagneticFlux::Nanoweber),
+            Unit::MagneticFlux(MagneticFlux::Microweber),
+            Unit::MagneticFlux(MagneticFlux::Weber),
+        ],
+        _ => vec![],
+    }
+}
+
+// Imperial input stays imperial; everything else is shown in SI.
+fn system_of(unit: &Unit) -> UnitSystem {
+    match UnitRegistry::builtin().info(unit).map(|info| info.system) {
+        Some(System::Imperial | System::UsCustomary) => UnitSystem::Imperial,
+        _ => UnitSystem::Si,
+    }
+}
+
+// Converts to the largest unit that keeps the magnitude at or above 1, or to the
+// smallest available unit if none does. The quantity is returned unchanged if
+// there is nothing to choose from.
+pub fn humanize(quantity: &Quantity, system: Option<UnitSystem>) -> Quantity {
+    let dimension = get_unit_dimension(&quantity.unit);
+    if dimension == Dimension::Temperature || quantity.value == 0.0 || !quantity.value.is_finite() {
+        return quantity.clone();
+    }
+    let system = system.unwrap_or_else(|| system_of(&quantity.unit));
+    let mut best: Option<Quantity> = None;
+    for unit in ladder(dimension, system) {
+        let Some(candidate) = quantity.convert_to(&unit) else { continue };
+        let better = match &best {
+            None => true,
+            Some(best) => {
+                let (magnitude, best_magnitude) = (candidate.value.abs(), best.value.abs());
+                if best_magnitude >= 1.0 {
+                    magnitude >= 1.0 && magnitude < best_magnitude
+                } else {
+                    magnitude > best_magnitude
+                }
+            }
+        };
+        if better {
+            best = Some(candidate);
+        }
+    }
+    match best {
+        Some(best) => best,
+        None => quantity.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn humanized(value: f64, unit: Unit, system: Option<UnitSystem>) -> (f64, Unit) {
+        let quantity = humanize(&Quantity::new(value, unit), system);
+        (quantity.value, quantity.unit)
+    }
+
+    #[test]
+    fn picks_the_largest_unit_at_or_above_one() {
+        assert_eq!(humanized(0.000003, Unit::Time(Time::Second), None), (3.0, Unit::Time(Time::Microsecond)));
+        assert_eq!(humanized(15000000.0, Unit::Length(Length::Meter), None), (15000.0, Unit::Length(Length::Kilometer)));
+        assert_eq!(humanized(86400.0, Unit::Time(Time::Second), None), (1.0, Unit::Time(Time::Day)));
+        assert_eq!(humanized(-2500.0, Unit::Mass(Mass::Gram), None), (-2.5, Unit::Mass(Mass::Kilogram)));
+        // Below the whole ladder, the smallest unit.
+        assert_eq!(humanized(1e-30, Unit::Length(Length::Meter), None).1, Unit::Length(Length::Attometer));
+    }
+
+    #[test]
+    fn imperial_input_stays_imperial() {
+        assert_eq!(humanized(36.0, Unit::Length(Length::Inch), None), (3.0, Unit::Length(Length::Foot)));
+        assert_eq!(humanized(5280.0, Unit::Length(Length::Foot), None), (1.0, Unit::Length(Length::Mile)));
+        assert_eq!(humanized(1000.0, Unit::Length(Length::Meter), Some(UnitSystem::Imperial)).1, Unit::Length(Length::Foot));
+        assert_eq!(humanized(12.0, Unit::Length(Length::Inch), Some(UnitSystem::Si)), (304.8, Unit::Length(Length::Millimeter)));
+    }
+
+    #[test]
+    fn some_quantities_are_left_alone() {
+        let warm = Unit::Temperature(Temperature::Celsius);
+        assert_eq!(humanized(3000.0, warm.clone(), None), (3000.0, warm));
+        assert_eq!(humanized(0.0, Unit::Length(Length::Kilometer), None), (0.0, Unit::Length(Length::Kilometer)));
+        let luminosity = Unit::Luminosity(Luminosity::Candela);
+        assert_eq!(humanized(5000.0, luminosity.clone(), None), (5000.0, luminosity));
+    }
+}
@@ -0,0 +1,256 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::enums::Unit;
+use crate::exact::Exact;
+use crate::{convert, convert_exact, get_unit_dimension};
+
+// Closed intervals, for tolerances and ranges ("10–12 bar"). Arithmetic rounds
+// outward: a bound that isn't exact moves one step away from the interval, so the
+// result contains every value the exact computation could give. Bounds that are
+// computed exactly (1 + 2 = 3) are left alone.
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Interval {
+    pub low: f64,
+    pub high: f64,
+}
+
+// `value` rounded down or up, given `error` = exact result - value.
+fn directed(value: f64, error: f64, up: bool) -> f64 {
+    if !value.is_finite() {
+        value
+    } else if up && error > 0.0 {
+        value.next_up()
+    } else if !up && error < 0.0 {
+        value.next_down()
+    } else {
+        value
+    }
+}
+
+// a + b rounded down or up; the error term is exact (Knuth's TwoSum).
+fn sum(a: f64, b: f64, up: bool) -> f64 {
+    let s = a + b;
+    let b_part = s - a;
+    let error = (a - (s - b_part)) + (b - b_part);
+    directed(s, error, up)
+}
+
+fn product(a: f64, b: f64, up: bool) -> f64 {
+    let p = a * b;
+    directed(p, a.mul_add(b, -p), up)
+}
+
+fn quotient(a: f64, b: f64, up: bool) -> f64 {
+    let q = a / b;
+    // a - q*b is exact; dividing it by b gives the sign of the error.
+    let remainder = (-q).mul_add(b, a);
+    directed(q, remainder * b.signum(), up)
+}
+
+// `value` of `from` in `to`, rounded down or up. Where the units are defined exactly
+// the exact result tells which way `convert` rounded; otherwise the bound moves
+// one step out regardless.
+fn convert_bound(value: f64, from: &Unit, to: &Unit, up: bool) -> Option<f64> {
+    let rounded = convert(value, from, to)?;
+    let error = Exact::from_f64(value)
+        .and_then(|value| convert_exact(value, from, to))
+        .zip(Exact::from_f64(rounded))
+        .and_then(|(exact, rounded)| exact.checked_sub(rounded))
+        .map_or(if up { 1.0 } else { -1.0 }, |error| error.parts().0.signum() as f64);
+    Some(directed(rounded, error, up))
+}
+
+impl Interval {
+    // The bounds may be given in either order. Adding zero turns -0 into 0.
+    pub fn new(a: f64, b: f64) -> Interval {
+        Interval { low: a.min(b) + 0.0, high: a.max(b) + 0.0 }
+    }
+
+    pub fn point(value: f64) -> Interval {
+        Interval { low: value, high: value }
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.low <= value && value <= self.high
+    }
+
+    pub fn width(&self) -> f64 {
+        self.high - self.low
+    }
+
+    pub fn midpoint(&self) -> f64 {
+        self.low + (self.high - self.low) / 2.0
+    }
+
+    // None if `other` contains zero.
+    pub fn checked_div(self, other: Interval) -> Option<Interval> {
+        if other.contains(0.0) {
+            return None;
+        }
+        Some(self.corners(other, quotient))
+    }
+
+    // The smallest and largest of op(a, b) over the bounds, for * and /.
+    fn corners(self, other: Interval, op: fn(f64, f64, bool) -> f64) -> Interval {
+        let pairs = [(self.low, other.low), (self.low, other.high), (self.high, other.low), (self.high, other.high)];
+        let low = pairs.iter().map(|&(a, b)| op(a, b, false)).fold(f64::INFINITY, f64::min);
+        let high = pairs.iter().map(|&(a, b)| op(a, b, true)).fold(f64::NEG_INFINITY, f64::max);
+        Interval { low, high }
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+
+    fn add(self, other: Interval) -> Interval {
+        Interval { low: sum(self.low, other.low, false), high: sum(self.high, other.high, true) }
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+
+    fn sub(self, other: Interval) -> Interval {
+        self + -other
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+
+    fn neg(self) -> Interval {
+        Interval { low: -self.high, high: -self.low }
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+
+    fn mul(self, other: Interval) -> Interval {
+        self.corners(other, product)
+    }
+}
+
+// An interval of values of one unit: "5.8–6.0 mm".
+#[derive(Debug, PartialEq, Clone)]
+pub struct IntervalQuantity {
+    pub interval: Interval,
+    pub unit: Unit,
+}
+
+impl IntervalQuantity {
+    pub fn new(interval: Interval, unit: Unit) -> IntervalQuantity {
+        IntervalQuantity { interval, unit }
+    }
+
+    // Both bounds are converted, rounding outward; they swap over on scales that run
+    // backwards, like Delisle (0 °De is hotter than 100 °De).
+    pub fn convert_to(&self, unit: &Unit) -> Option<IntervalQuantity> {
+        let Interval { low, high } = self.interval;
+        let reversed = convert(1.0, &self.unit, unit)? < convert(0.0, &self.unit, unit)?;
+        let (low, high) = if reversed { (high, low) } else { (low, high) };
+        let low = convert_bound(low, &self.unit, unit, false)?;
+        let high = convert_bound(high, &self.unit, unit, true)?;
+        Some(IntervalQuantity::new(Interval::new(low, high), unit.clone()))
+    }
+
+    // Sum and difference in this quantity's unit. None if the dimensions differ.
+    pub fn checked_add(&self, other: &IntervalQuantity) -> Option<IntervalQuantity> {
+        let other = self.same_unit(other)?;
+        Some(IntervalQuantity::new(self.interval + other.interval, self.unit.clone()))
+    }
+
+    pub fn checked_sub(&self, other: &IntervalQuantity) -> Option<IntervalQuantity> {
+        let other = self.same_unit(other)?;
+        Some(IntervalQuantity::new(self.interval - other.interval, self.unit.clone()))
+    }
+
+    pub fn scale(&self, factor: Interval) -> IntervalQuantity {
+        IntervalQuantity::new(self.interval * factor, self.unit.clone())
+    }
+
+    fn same_unit(&self, other: &IntervalQuantity) -> Option<IntervalQuantity> {
+        if get_unit_dimension(&self.unit) != get_unit_dimension(&other.unit) {
+            return None;
+        }
+        other.convert_to(&self.unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::*;
+
+    const METER: Unit = Unit::Length(Length::Meter);
+
+    fn quantity(low: f64, high: f64, unit: Unit) -> IntervalQuantity {
+        IntervalQuantity::new(Interval::new(low, high), unit)
+    }
+
+    #[test]
+    fn exact_arithmetic_is_left_alone() {
+        assert_eq!(Interval::point(1.0) + Interval::point(2.0), Interval::point(3.0));
+        assert_eq!(Interval::new(-1.0, 2.0) * Interval::new(3.0, 4.0), Interval::new(-4.0, 8.0));
+        assert_eq!(Interval::new(5.0, 1.0) - Interval::new(1.0, 1.0), Interval::new(0.0, 4.0));
+        assert_eq!(Interval::new(-0.0, 1.0).low.to_bits(), 0.0f64.to_bits());
+    }
+
+    #[test]
+    fn inexact_arithmetic_rounds_outward() {
+        // 0.1 + 0.2 in binary lies just below 0.30000000000000004.
+        let sum = Interval::point(0.1) + Interval::point(0.2);
+        assert_eq!((sum.low, sum.high), (0.3, 0.30000000000000004));
+        let third = Interval::point(1.0).checked_div(Interval::point(3.0)).unwrap();
+        assert_eq!(third.high, third.low.next_up());
+        assert!(third.contains(1.0 / 3.0));
+        assert_eq!(Interval::point(1.0).checked_div(Interval::new(-1.0, 1.0)), None);
+    }
+
+    #[test]
+    fn conversions_round_outward() {
+        // 10..11 ft is 3.333...–3.666... yd, which no f64 holds exactly.
+        let (foot, yard) = (Unit::Length(Length::Foot), Unit::Length(Length::Yard));
+        let converted = quantity(10.0, 11.0, foot.clone()).convert_to(&yard).unwrap().interval;
+        let (low, high) = (convert(10.0, &foot, &yard).unwrap(), convert(11.0, &foot, &yard).unwrap());
+        assert!(converted.low <= low && low.next_down() <= converted.low);
+        assert!(converted.high >= high && high.next_up() >= converted.high);
+        let exact = |value| convert_exact(Exact::integer(value), &foot, &yard).unwrap();
+        let sign = |a: Exact, b: f64| a.checked_sub(Exact::from_f64(b).unwrap()).unwrap().parts().0.signum();
+        assert_eq!((sign(exact(10), converted.low), sign(exact(11), converted.high)), (1, -1));
+    }
+
+    #[test]
+    fn exactly_representable_bounds_stay_put() {
+        let converted = quantity(1.0, 2.5, Unit::Length(Length::Kilometer)).convert_to(&METER).unwrap();
+        assert_eq!(converted.interval, Interval::new(1000.0, 2500.0));
+        let converted = quantity(0.0, 100.0, Unit::Temperature(Temperature::Celsius)).convert_to(&Unit::Temperature(Temperature::Fahrenheit)).unwrap();
+        assert_eq!(converted.interval, Interval::new(32.0, 212.0));
+    }
+
+    #[test]
+    fn inexact_factors_widen_both_bounds() {
+        let light_year = Unit::Length(Length::LightYear);
+        let converted = quantity(1.0, 2.0, light_year.clone()).convert_to(&METER).unwrap();
+        assert_eq!(converted.interval.low, convert(1.0, &light_year, &METER).unwrap().next_down());
+        assert_eq!(converted.interval.high, convert(2.0, &light_year, &METER).unwrap().next_up());
+    }
+
+    #[test]
+    fn reversed_scales_swap_the_bounds() {
+        let delisle = quantity(0.0, 100.0, Unit::Temperature(Temperature::Delisle));
+        let celsius = delisle.convert_to(&Unit::Temperature(Temperature::Celsius)).unwrap();
+        assert!(celsius.interval.low <= 100.0 / 3.0 && 100.0 / 3.0 - 1e-12 < celsius.interval.low);
+        assert_eq!(celsius.interval.high, 100.0);
+    }
+
+    #[test]
+    fn sums_convert_to_the_first_unit() {
+        let sum = quantity(1.0, 2.0, METER).checked_add(&quantity(10.0, 20.0, Unit::Length(Length::Centimeter))).unwrap();
+        assert_eq!(sum.unit, METER);
+        assert!(sum.interval.contains(1.1) && sum.interval.contains(2.2));
+        assert!(sum.interval.width() < 1.1 + 1e-12);
+        assert_eq!(quantity(1.0, 2.0, METER).checked_sub(&quantity(1.0, 2.0, Unit::Mass(Mass::Kilogram))), None);
+    }
+}
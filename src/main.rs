@@ -1,70 +1,689 @@
-use un::convert;
-use un::string_to_target;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rustyline::DefaultEditor;
+use rustyline::error::ReadlineError;
+
+use un::csv::{ColumnSpec, CsvError, convert_csv};
+use un::decimal::{Context, Rounding};
+use un::enums::{Dimension, Unit};
+use un::explain::{ExplainError, explain};
+use un::format::{FormatOptions, Notation, Precision, UncertaintyStyle};
+use un::humanize::humanize;
+use un::output::{Conversion, OutputFormat, header, render};
+use un::parse::{ParseError, is_interval, parse_decimal_quantity, parse_exact_quantity, parse_interval, parse_quantity, split_interval, split_measurement};
+use un::repl::{Response, Session, default_history_path};
+use un::registry::{Exactness, LoadError, Reference, System, UnitFilter, UnitInfo, UnitRegistry, default_definitions_path};
+use un::{Quantity, convert_decimal, convert_exact, get_exact_definition, get_unit_dimension};
+
+// Exit statuses, so scripts can tell failures apart. Success is 0.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Status {
+    Usage = 1,
+    UnknownUnit = 2,
+    DimensionMismatch = 3,
+    Parse = 4,
+}
+
+impl Status {
+    fn of_parse(err: &ParseError) -> Status {
+        match err {
+            ParseError::Unit(_) => Status::UnknownUnit,
+            _ => Status::Parse,
+        }
+    }
+}
+
+// Set by --quiet: diagnostics are dropped, results and exit statuses are not.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+fn warn(message: impl fmt::Display) {
+    if !QUIET.load(Ordering::Relaxed) {
+        eprintln!("{}", message);
+    }
+}
+
+fn fail(status: Status, message: impl fmt::Display) -> ! {
+    warn(format_args!("Error: {}", message));
+    std::process::exit(status as i32);
+}
 
 fn main() {
-    let mut args = std::env::args();
-    // println!("{:?}", args);
-    // println!("{}", args.len());
-    if args.len() < 3 {
-        usage();      
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let quiet = take_flag(&mut args, "--quiet") | take_flag(&mut args, "-q");
+    QUIET.store(quiet, Ordering::Relaxed);
+    let verbose = take_flag(&mut args, "--verbose");
+    let units_file = take_option(&mut args, "--units-file");
+    let gnu_units_file = take_option(&mut args, "--gnu-units");
+    let options = format_options(&mut args);
+    let output = output_format(&mut args);
+    let stdin = take_flag(&mut args, "--stdin");
+    let on_error = on_error(&mut args);
+    let list = take_flag(&mut args, "--list");
+    let search = take_option(&mut args, "--search");
+    let filter = unit_filter(&mut args);
+    let info = take_option(&mut args, "--info");
+    let explain = take_flag(&mut args, "--explain");
+    let exact = take_flag(&mut args, "--exact");
+    let context = decimal_context(&mut args);
+    if exact && context.is_some() {
+        fail(Status::Usage, "--exact and --digits cannot be combined");
+    }
+    if context.is_some() && output != OutputFormat::Human {
+        fail(Status::Usage, "--digits only supports human output");
+    }
+    let registry = load_registry(units_file, gnu_units_file, verbose);
+
+    if list {
+        finish_listing(list_units(&registry, args.first().map(String::as_str), filter));
+        return;
+    }
+    if let Some(query) = search {
+        finish_listing(search_units(&registry, &query, filter));
+        return;
+    }
+    if filter != UnitFilter::default() {
+        fail(Status::Usage, "--system, --exactness and --reference only apply to --list and --search");
+    }
+    if let Some(name) = info {
+        finish_listing(print_info(&registry, &name));
+        return;
+    }
 
-        std::process::exit(0);
+    // Batch and CSV conversions print floats only, one line or cell at a time.
+    let csv = args.first().map(String::as_str) == Some("csv");
+    if csv || stdin {
+        let mode = if csv { "csv" } else { "--stdin" };
+        for (set, flag) in [(exact, "--exact"), (context.is_some(), "--digits"), (explain, "--explain")] {
+            if set {
+                fail(Status::Usage, format_args!("{} cannot be combined with {}", flag, mode));
+            }
+        }
+    }
+    if csv {
+        run_csv(&registry, args, &options);
+        return;
+    }
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        usage();
+        return;
+    }
+    if let Some(option) = args.iter().find(|arg| arg.starts_with("--")) {
+        fail(Status::Usage, format_args!("unknown option {} (see --help)", option));
+    }
+    if stdin {
+        let targets: Vec<Unit> = args.iter().map(|target| resolve(&registry, target, None)).collect();
+        run_batch(&registry, &targets, output, &options, on_error);
+        return;
     }
-    let Some(arg1) = args.nth(1) else {unreachable!()};
-    let (quantity, unit_str) = if let Ok(quantity) = arg1.parse::<f64>() {
-        if args.len() < 2 {
-            usage();
-            std::process::exit(0);
+    if args.is_empty() {
+        run_repl(&registry, &options, context);
+        return;
+    }
+    if is_interval(&args.join(" ")) {
+        if explain || exact || context.is_some() || output != OutputFormat::Human {
+            fail(Status::Usage, "ranges only support human output");
         }
-        (quantity, args.nth(0).unwrap())
-    } else {
-        let mut found_unit = false;
-        let mut val_buffer = "".to_string();
-        let mut unit_buffer = "".to_string();
-
-        for c in arg1.chars() {
-            if !(c.is_alphanumeric() || c=='.'){
-                println!("invalid char {:?}", c);
-                invalid_input(&arg1);
-                std::process::exit(0);
+        convert_interval(&registry, &args, &options);
+        return;
+    }
+    // The quantity may span several arguments ("20 km", "12.3 ± 0.2 mm"): take the
+    // fewest that read as one.
+    let expression_len = (1..=args.len())
+        .find(|&len| split_measurement(&args[..len].join(" ")).is_ok())
+        .unwrap_or(if args.len() >= 2 && args[0].parse::<f64>().is_ok() { 2 } else { 1 });
+    // Exact and decimal numbers take any exponent, so they are read before the float.
+    if exact && !explain {
+        print_exact(&registry, &args[..expression_len].join(" "), &args[expression_len..]);
+        return;
+    }
+    if let Some(context) = context.filter(|_| !explain) {
+        print_decimal(&registry, &args[..expression_len].join(" "), &args[expression_len..], &context, &options);
+        return;
+    }
+    let input = match parse_quantity(&args[..expression_len].join(" "), &registry) {
+        Ok(input) => input,
+        Err(err) => fail(Status::of_parse(&err), err),
+    };
+    if explain {
+        explain_conversion(&registry, &args[..expression_len].join(" "), &input, &args[expression_len..]);
+        return;
+    }
+    let dimension = Some(get_unit_dimension(&input.unit));
+    let targets: Vec<Unit> = args[expression_len..].iter().map(|target| resolve(&registry, target, dimension)).collect();
+    let conversion = match convert_all(&registry, input, &targets) {
+        Ok(conversion) => conversion,
+        Err((status, message)) => fail(status, message),
+    };
+    if let Some(header) = header(output) {
+        println!("{}", header);
+    }
+    println!("{}", render(output, &registry, &conversion, &options));
+}
+
+// "10..12 bar psi": both bounds converted to each target, or with no targets to
+// the unit that reads best for the larger bound.
+fn convert_interval(registry: &UnitRegistry, args: &[String], options: &FormatOptions) {
+    let expression_len = (1..=args.len()).find(|&len| split_interval(&args[..len].join(" ")).is_ok()).unwrap_or(args.len());
+    let input = match parse_interval(&args[..expression_len].join(" "), registry) {
+        Ok(input) => input,
+        Err(err) => fail(Status::of_parse(&err), err),
+    };
+    let dimension = Some(get_unit_dimension(&input.unit));
+    let targets: Vec<Unit> = args[expression_len..].iter().map(|target| resolve(registry, target, dimension)).collect();
+    let larger = input.interval.low.abs().max(input.interval.high.abs());
+    let targets = match convert_all(registry, Quantity::new(larger, input.unit.clone()), &targets) {
+        Ok(conversion) => conversion.results.into_iter().map(|result| result.unit),
+        Err((status, message)) => fail(status, message),
+    };
+    let results: Vec<String> = targets
+        .map(|target| match input.convert_to(&target) {
+            Some(result) if result.interval.low.is_finite() && result.interval.high.is_finite() => {
+                options.format_interval(result.interval.low, result.interval.high, &registry.symbol_of(&target))
             }
+            Some(_) => fail(Status::Parse, format_args!("the result in {} is out of range", registry.name_of(&target))),
+            None => fail(Status::DimensionMismatch, format_args!("cannot convert to {}", registry.name_of(&target))),
+        })
+        .collect();
+    println!("{}", results.join(" "));
+}
 
-            if found_unit {
-                if c.is_alphabetic() {
-                    unit_buffer.push(c);
-                }else{
-                    invalid_input(&arg1);
-                    std::process::exit(0);
-                }
-            }else {
-                if c.is_alphabetic(){
-                    found_unit = true;
-                    unit_buffer.push(c);
-                } else {
-                    val_buffer.push(c);
+// Why the first of `units` without an exact definition has none, e.g. " (the light_year
+// factor is rounded)".
+fn inexact_reason(registry: &UnitRegistry, units: &[&Unit]) -> String {
+    let inexact = units.iter().find(|unit| get_exact_definition(unit).is_none());
+    match inexact.map(|unit| (registry.name_of(unit), registry.info(unit))) {
+        Some((name, Some(info))) if info.exactness != Exactness::Exact => format!(" (the {} factor is {})", name, info.exactness.name()),
+        Some((name, _)) => format!(" ({} has no exact definition)", name),
+        None => String::new(),
+    }
+}
+
+fn check_dimensions(registry: &UnitRegistry, from: &Unit, to: &Unit) {
+    if get_unit_dimension(from) != get_unit_dimension(to) {
+        let describe = |unit: &Unit| format!("{} ({})", registry.name_of(unit), get_unit_dimension(unit).name());
+        fail(Status::DimensionMismatch, format_args!("cannot convert {} to {}", describe(from), describe(to)));
+    }
+}
+
+// The units an exact or decimal result is given in: the targets, or with none, the
+// one that reads best for the value as a float.
+fn precise_targets(registry: &UnitRegistry, from: &Unit, approximate: f64, targets: &[String]) -> Vec<Unit> {
+    let dimension = Some(get_unit_dimension(from));
+    let targets: Vec<Unit> = targets.iter().map(|target| resolve(registry, target, dimension)).collect();
+    for target in &targets {
+        check_dimensions(registry, from, target);
+    }
+    if targets.is_empty() { vec![humanize(&Quantity::new(approximate, from.clone()), None).unit] } else { targets }
+}
+
+// "1 mi = 1609.344 m", one line per result, with no rounding anywhere.
+fn print_exact(registry: &UnitRegistry, expression: &str, targets: &[String]) {
+    let (value, from) = match parse_exact_quantity(expression, registry) {
+        Ok(input) => input,
+        Err(err) => fail(Status::of_parse(&err), err),
+    };
+    for target in precise_targets(registry, &from, value.to_f64(), targets) {
+        let Some(exact) = convert_exact(value, &from, &target) else {
+            fail(Status::Usage, format_args!("no exact conversion from {} to {}{}", registry.name_of(&from), registry.name_of(&target), inexact_reason(registry, &[&from, &target])));
+        };
+        println!("{} {} = {} {}", value, registry.symbol_of(&from), exact, registry.symbol_of(&target));
+    }
+}
+
+// Spellings other than the symbol and name, e.g. "meters" for the meter.
+fn other_spellings(info: &UnitInfo) -> Vec<&str> {
+    let mut spellings: Vec<&str> = vec![];
+    for spelling in info.spellings().skip(2) {
+        if spelling != info.symbol && spelling != info.name && !spellings.contains(&spelling) {
+            spellings.push(spelling);
+        }
+    }
+    spellings
+}
+
+fn unit_row(info: &UnitInfo) -> String {
+    format!("  {:<10} {:<30} {:<13} {}", info.symbol, info.name, info.system.name(), other_spellings(info).join(", "))
+}
+
+// --list, --search and --info stop quietly when the reader goes away (e.g. `| head`).
+fn finish_listing(written: std::io::Result<()>) {
+    match written {
+        Err(err) if err.kind() != std::io::ErrorKind::BrokenPipe => fail(Status::Usage, err),
+        _ => {}
+    }
+}
+
+fn list_units(registry: &UnitRegistry, dimension: Option<&str>, filter: UnitFilter) -> std::io::Result<()> {
+    let dimensions: Vec<Dimension> = match dimension {
+        Some(name) => match Dimension::from_name(name) {
+            Some(dimension) => vec![dimension],
+            None => fail(Status::Usage, format_args!("unknown dimension {:?}", name)),
+        },
+        None => {
+            let mut dimensions = vec![];
+            for info in registry.iter() {
+                if !dimensions.contains(&info.dimension) {
+                    dimensions.push(info.dimension);
                 }
             }
+            dimensions
+        }
+    };
+    let mut stdout = std::io::stdout().lock();
+    let mut listed = false;
+    for dimension in dimensions {
+        let mut units = registry.query(UnitFilter { dimension: Some(dimension), ..filter }).peekable();
+        if units.peek().is_none() {
+            continue;
+        }
+        writeln!(stdout, "{}:", dimension.name())?;
+        for info in units {
+            writeln!(stdout, "{}", unit_row(info))?;
+        }
+        listed = true;
+    }
+    if !listed {
+        fail(Status::UnknownUnit, "no units match the filters");
+    }
+    Ok(())
+}
+
+fn search_units(registry: &UnitRegistry, query: &str, filter: UnitFilter) -> std::io::Result<()> {
+    let mut matches = registry.search(query);
+    matches.retain(|info| filter.matches(info));
+    if matches.is_empty() {
+        fail(Status::UnknownUnit, format_args!("no units match {:?}", query));
+    }
+    let mut stdout = std::io::stdout().lock();
+    for info in matches {
+        writeln!(stdout, "{:<17}{}", info.dimension.name(), unit_row(info))?;
+    }
+    Ok(())
+}
+
+fn print_info(registry: &UnitRegistry, name: &str) -> std::io::Result<()> {
+    let unit = resolve(registry, name, None);
+    let mut stdout = std::io::stdout().lock();
+    let Some(info) = registry.info(&unit) else {
+        // Prefixed runtime units ("kfoo") are not stored in the registry.
+        if let Unit::Custom(custom) = &unit {
+            let dimension = custom.dimension.name();
+            writeln!(stdout, "{} ({}), factor {:?} to the {} base unit", custom.name, dimension, custom.factor, dimension)?;
+        }
+        return Ok(());
+    };
+    let base = registry.base_unit(info.dimension).map_or("base unit", |base| base.symbol.as_str());
+    writeln!(stdout, "{} ({})", info.name, info.symbol)?;
+    writeln!(stdout, "  dimension: {}", info.dimension.name())?;
+    writeln!(stdout, "  system:    {}", info.system.name())?;
+    writeln!(stdout, "  exactness: {}", info.exactness.name())?;
+    writeln!(stdout, "  reference: {}", info.reference.map_or("none", |reference| reference.name()))?;
+    if info.offset == 0.0 {
+        writeln!(stdout, "  factor:    1 {} = {:?} {}", info.symbol, info.factor, base)?;
+    } else {
+        writeln!(stdout, "  factor:    {} = {:?} * {} + {:?}", base, info.factor, info.symbol, info.offset)?;
+    }
+    let mut spellings: Vec<&str> = vec![];
+    for spelling in info.spellings() {
+        if !spellings.contains(&spelling) {
+            spellings.push(spelling);
+        }
+    }
+    writeln!(stdout, "  accepts:   {}", spellings.join(", "))
+}
+
+// Prints the steps of each conversion; with no targets, of the humanized one.
+fn explain_conversion(registry: &UnitRegistry, expression: &str, input: &Quantity, targets: &[String]) {
+    let humanized = match targets {
+        [] => match Conversion::new(input.clone(), &[]) {
+            Ok(conversion) => conversion.results.iter().map(|result| registry.symbol_of(&result.unit)).collect(),
+            Err(_) => vec![],
+        },
+        _ => targets.to_vec(),
+    };
+    for (i, target) in humanized.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match explain(expression, target, registry) {
+            Ok(explanation) => println!("{}", explanation),
+            Err(err) => {
+                let status = match &err {
+                    ExplainError::Parse(err) => Status::of_parse(err),
+                    ExplainError::Unit(_) => Status::UnknownUnit,
+                    ExplainError::DimensionMismatch { .. } => Status::DimensionMismatch,
+                    ExplainError::MissingFactor(_) => Status::Parse,
+                };
+                fail(status, err);
+            }
+        }
+    }
+}
+
+// The human format, with as many digits as `context` asks for.
+fn print_decimal(registry: &UnitRegistry, expression: &str, targets: &[String], context: &Context, options: &FormatOptions) {
+    let (value, from) = match parse_decimal_quantity(expression, registry) {
+        Ok(input) => input,
+        Err(err) => fail(Status::of_parse(&err), err),
+    };
+    let separator = if options.space { " " } else { "" };
+    let mut results = vec![];
+    for target in precise_targets(registry, &from, value.to_f64(), targets) {
+        let Some(converted) = convert_decimal(&value, &from, &target, context) else {
+            fail(Status::Usage, format_args!("no exact conversion from {} to {}{}", registry.name_of(&from), registry.name_of(&target), inexact_reason(registry, &[&from, &target])));
+        };
+        results.push(format!("{}{}{}", converted, separator, registry.symbol_of(&target)));
+    }
+    println!("{}", results.join(" "));
+}
+
+// un csv --column <name>[:<from>]=<to> ... [file]
+fn run_csv(registry: &UnitRegistry, mut args: Vec<String>, options: &FormatOptions) {
+    args.remove(0);
+    let mut specs = vec![];
+    while let Some(spec) = take_option(&mut args, "--column") {
+        match ColumnSpec::parse(&spec, registry) {
+            Ok(spec) => specs.push(spec),
+            Err(err) => fail(csv_status(&err), err),
+        }
+    }
+    let delimiter = match take_option(&mut args, "--delimiter").as_deref() {
+        None => ',',
+        Some("\\t" | "tab") => '\t',
+        Some(other) if other.chars().count() == 1 => other.chars().next().unwrap_or(','),
+        Some(other) => fail(Status::Usage, format_args!("invalid --delimiter {:?}", other)),
+    };
+    if specs.is_empty() || args.len() > 1 {
+        fail(Status::Usage, "usage: un csv --column <name>[:<from>]=<to> ... [--delimiter <c>] [file]");
+    }
+    let stdout = std::io::stdout().lock();
+    let result = match args.first() {
+        Some(path) => match std::fs::File::open(path) {
+            Ok(file) => convert_csv(std::io::BufReader::new(file), stdout, &specs, registry, options, delimiter),
+            Err(err) => fail(Status::Usage, format_args!("{}: {}", path, err)),
+        },
+        None => convert_csv(std::io::stdin().lock(), stdout, &specs, registry, options, delimiter),
+    };
+    match result {
+        Ok(()) => {}
+        Err(CsvError::Io(err)) if err.kind() == std::io::ErrorKind::BrokenPipe => {}
+        Err(err) => fail(csv_status(&err), err),
+    }
+}
+
+fn csv_status(err: &CsvError) -> Status {
+    match err {
+        CsvError::Io(_) | CsvError::InvalidSpec(_) | CsvError::MissingColumn(_) => Status::Usage,
+        CsvError::Unit(_) => Status::UnknownUnit,
+        CsvError::DimensionMismatch(_) => Status::DimensionMismatch,
+        CsvError::UnterminatedQuote { .. } | CsvError::Cell { .. } => Status::Parse,
+    }
+}
 
+// Interactive prompt; history is kept in the user's data directory.
+fn run_repl(registry: &UnitRegistry, options: &FormatOptions, context: Option<Context>) {
+    let mut editor = match DefaultEditor::new() {
+        Ok(editor) => editor,
+        Err(err) => fail(Status::Usage, err),
+    };
+    let history = default_history_path();
+    if let Some(path) = &history {
+        // A missing history file just means a first run.
+        let _ = editor.load_history(path);
+    }
+    println!("Type :help for help, :quit to leave.");
+    let mut session = match context {
+        Some(context) => Session::decimal(registry, context),
+        None => Session::new(registry),
+    };
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => {
+                warn(format_args!("Error: {}", err));
+                break;
+            }
+        };
+        if !line.trim().is_empty() {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+        match session.eval(&line) {
+            Ok(Response::Value(value)) => println!("{}", value.display(registry, options)),
+            Ok(Response::Text(text)) => println!("{}", text),
+            Ok(Response::Quit) => break,
+            Ok(Response::Nothing) => {}
+            Err(err) => warn(format_args!("Error: {}", err)),
         }
+    }
+    if let Some(path) = &history {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Err(err) = editor.save_history(path) {
+            warn(format_args!("Could not save history to {}: {}", path.display(), err));
+        }
+    }
+}
 
-        let val_buffer_f64 = val_buffer.parse::<f64>();
-        if val_buffer_f64.is_err() {
-            invalid_input(&val_buffer);
-            std::process::exit(0);
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum OnError {
+    Skip,
+    Echo, // Copy the line to the output unchanged
+    Fail,
+}
+
+fn on_error(args: &mut Vec<String>) -> OnError {
+    match take_option(args, "--on-error").as_deref() {
+        None | Some("fail") => OnError::Fail,
+        Some("skip") => OnError::Skip,
+        Some("echo") => OnError::Echo,
+        Some(other) => fail(Status::Usage, format_args!("unknown --on-error mode {:?} (expected skip, echo or fail)", other)),
+    }
+}
+
+// Converts one quantity per line of stdin, streaming the results. Blank lines are ignored.
+fn run_batch(registry: &UnitRegistry, targets: &[Unit], output: OutputFormat, options: &FormatOptions, on_error: OnError) {
+    let mut stdout = std::io::stdout().lock();
+    if let Some(header) = header(output)
+        && writeln!(stdout, "{}", header).is_err()
+    {
+        return;
+    }
+    for (i, line) in std::io::stdin().lock().lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => fail(Status::Parse, format_args!("line {}: {}", i + 1, err)),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let conversion = parse_quantity(&line, registry)
+            .map_err(|err| (Status::of_parse(&err), err.to_string()))
+            .and_then(|input| convert_all(registry, input, targets));
+        let written = match (conversion, on_error) {
+            (Ok(conversion), _) => writeln!(stdout, "{}", render(output, registry, &conversion, options)),
+            (Err(_), OnError::Skip) => Ok(()),
+            (Err(_), OnError::Echo) => writeln!(stdout, "{}", line),
+            (Err((status, message)), OnError::Fail) => fail(status, format_args!("line {}: {}", i + 1, message)),
+        };
+        // The reader went away (e.g. `| head`).
+        if written.is_err() {
+            return;
         }
-        (val_buffer_f64.unwrap(), unit_buffer)
+    }
+}
+
+// Converts `input` to each target, or says why it cannot be. A result too large
+// for an f64 counts as a parse error, like the input "1e400".
+fn convert_all(registry: &UnitRegistry, input: Quantity, targets: &[Unit]) -> Result<Conversion, (Status, String)> {
+    let from = input.unit.clone();
+    let conversion = Conversion::new(input, targets).map_err(|to| {
+        let describe = |unit: &Unit| format!("{} ({})", registry.name_of(unit), get_unit_dimension(unit).name());
+        (Status::DimensionMismatch, format!("cannot convert {} to {}", describe(&from), describe(&to)))
+    })?;
+    match conversion.results.iter().find(|result| !result.value.is_finite()) {
+        Some(result) => Err((Status::Parse, format!("the result in {} is out of range", registry.name_of(&result.unit)))),
+        None => Ok(conversion),
+    }
+}
+
+// Suggestions for a misspelled name are limited to `dimension` when it is known.
+fn resolve(registry: &UnitRegistry, name: &str, dimension: Option<Dimension>) -> Unit {
+    match registry.resolve_in(name, dimension) {
+        Ok(unit) => unit,
+        Err(err) => fail(Status::UnknownUnit, err),
+    }
+}
+
+// --system, --exactness and --reference, which narrow --list and --search.
+fn unit_filter(args: &mut Vec<String>) -> UnitFilter {
+    fn parse<T>(args: &mut Vec<String>, option: &str, from_name: fn(&str) -> Option<T>, names: Vec<&str>) -> Option<T> {
+        let name = take_option(args, option)?;
+        match from_name(&name) {
+            Some(value) => Some(value),
+            None => fail(Status::Usage, format_args!("unknown {} {:?} (one of: {})", &option[2..], name, names.join(", "))),
+        }
+    }
+    UnitFilter {
+        dimension: None,
+        system: parse(args, "--system", System::from_name, System::ALL.iter().map(System::name).collect()),
+        exactness: parse(args, "--exactness", Exactness::from_name, Exactness::ALL.iter().map(Exactness::name).collect()),
+        reference: parse(args, "--reference", Reference::from_name, Reference::ALL.iter().map(Reference::name).collect()),
+    }
+}
+
+// Removes `--name value` / `--name=value` from the arguments and returns the value.
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let prefix = format!("{}=", name);
+    let index = args.iter().position(|arg| arg == name || arg.starts_with(&prefix))?;
+    let arg = args.remove(index);
+    if let Some(value) = arg.strip_prefix(&prefix) {
+        return Some(value.to_string());
+    }
+    if index < args.len() {
+        return Some(args.remove(index));
+    }
+    fail(Status::Usage, format_args!("missing value for {}", name));
+}
+
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let Some(index) = args.iter().position(|arg| arg == name) else { return false };
+    args.remove(index);
+    true
+}
+
+fn take_count(args: &mut Vec<String>, name: &str) -> Option<usize> {
+    let value = take_option(args, name)?;
+    match value.parse::<usize>() {
+        Ok(count) => Some(count),
+        Err(_) => fail(Status::Usage, format_args!("invalid value for {}: {}", name, value)),
+    }
+}
+
+fn format_options(args: &mut Vec<String>) -> FormatOptions {
+    let mut options = FormatOptions::default();
+    match (take_count(args, "--precision"), take_count(args, "--sig")) {
+        (Some(_), Some(_)) => fail(Status::Usage, "--precision and --sig cannot be combined"),
+        (Some(decimals), None) => options.precision = Precision::Decimals(decimals),
+        (None, Some(0)) => fail(Status::Usage, "--sig needs at least one significant figure"),
+        (None, Some(significant)) => options.precision = Precision::Significant(significant),
+        (None, None) => {}
+    }
+    match (take_flag(args, "--sci"), take_flag(args, "--eng")) {
+        (true, true) => fail(Status::Usage, "--sci and --eng cannot be combined"),
+        (true, false) => options.notation = Notation::Scientific,
+        (false, true) => options.notation = Notation::Engineering,
+        (false, false) => {}
+    }
+    options.group_digits = take_flag(args, "--group");
+    options.space = take_flag(args, "--space");
+    if take_flag(args, "--concise") {
+        options.uncertainty = UncertaintyStyle::Concise;
+    }
+    options
+}
+
+// --digits N [--rounding half-even|half-up|toward-zero] for decimal arithmetic.
+fn decimal_context(args: &mut Vec<String>) -> Option<Context> {
+    let digits = take_count(args, "--digits");
+    let rounding = match take_option(args, "--rounding") {
+        None => Rounding::HalfEven,
+        Some(name) => match Rounding::from_name(&name) {
+            Some(rounding) => rounding,
+            None => fail(Status::Usage, format_args!("unknown --rounding mode {:?} (expected half-even, half-up or toward-zero)", name)),
+        },
     };
-    let unit = string_to_target(unit_str.clone());
-    // let mut targets = vec![];
-    while let Some(target) = args.nth(0) {
-        print!("{:?}{} ", convert(quantity, &unit.clone().unwrap(), &string_to_target(target.clone()).unwrap()).unwrap(), target);
-        // targets.push(string_to_target(target).unwrap());
+    match digits {
+        Some(0) => fail(Status::Usage, "--digits needs at least one significant digit"),
+        Some(digits) => Some(Context::new(digits, rounding)),
+        None if rounding != Rounding::HalfEven => fail(Status::Usage, "--rounding needs --digits"),
+        None => None,
+    }
+}
+
+fn output_format(args: &mut Vec<String>) -> OutputFormat {
+    let Some(name) = take_option(args, "--output") else { return OutputFormat::Human };
+    match OutputFormat::from_name(&name) {
+        Some(format) => format,
+        None => fail(Status::Usage, format_args!("unknown output format {:?} (expected human, json, csv or tsv)", name)),
+    }
+}
+
+// User definitions from the config directory are optional; an explicit --units-file is not.
+// A GNU import says how many definitions it skipped, and lists them with --verbose.
+fn load_registry(units_file: Option<String>, gnu_units_file: Option<String>, verbose: bool) -> UnitRegistry {
+    let mut registry = UnitRegistry::new();
+    if let Some(path) = gnu_units_file {
+        match registry.load_gnu_file(&PathBuf::from(path)) {
+            Ok(report) if !report.unsupported.is_empty() => {
+                let (units, prefixes, skipped) = (report.units, report.prefixes, report.unsupported.len());
+                let more = if verbose { ":" } else { " (--verbose lists them)" };
+                warn(format_args!("Imported {} units and {} prefixes; skipped {} definitions{}", units, prefixes, skipped, more));
+                if verbose {
+                    for skipped in &report.unsupported {
+                        warn(format_args!("  {}", skipped));
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(err) => fail(load_status(&err), err),
+        }
+    }
+    let default_path = default_definitions_path().filter(|path| path.is_file());
+    for path in default_path.into_iter().chain(units_file.map(PathBuf::from)) {
+        if let Err(err) = registry.load_file(&path) {
+            fail(load_status(&err), err);
+        }
+    }
+    registry
+}
+
+fn load_status(err: &LoadError) -> Status {
+    match err {
+        LoadError::Io(..) => Status::Usage,
+        LoadError::Definition(..) => Status::Parse,
     }
-    println!()
 }
 
 fn usage() {
-    println!("Usage: ark <quantity><unit> <target-unit> ...\n");
+    println!("Usage: ark                        (interactive prompt)");
+    println!("       ark [--units-file <path>] [--gnu-units <definitions.units> [--verbose]] <quantity><unit> [<target-unit> ...]\n");
+    println!("Formatting: [--precision <decimals> | --sig <figures>] [--sci | --eng] [--group] [--space] [--concise]");
+    println!("Ranges: ark 10..12 bar psi   (also 10–12 bar; bounds stay in order on reversed scales)");
+    println!("Uncertainty: ark '12.3 ± 0.2 mm' in   (also +/-, or 12.3(2) mm; --concise prints 0.4843(79) in)");
+    println!("Output: [--output human|json|csv|tsv] [--exact]   (--exact: rational results, e.g. 1 mi = 1609.344 m)");
+    println!("Precision: [--digits <n> [--rounding half-even|half-up|toward-zero]]   (decimal arithmetic, also at the prompt)");
+    println!("Explain: ark --explain <quantity><unit> [<target-unit> ...]   (show how each result is computed)");
+    println!("Discover: ark --list [dimension] | --search <text> | --info <unit>");
+    println!("Filters: [--system <system>] [--exactness exact|rounded|measured|placeholder] [--reference nist|bipm|codata]   (with --list or --search)");
+    println!("CSV: ark csv --column <name>[:<from>]=<to> ... [--delimiter <c>] [file]");
+    println!("Batch: ark --stdin [--on-error skip|echo|fail] [<target-unit> ...]   (one quantity per line)");
+    println!("Diagnostics: [--quiet | -q]   (exit status 1 bad usage, 2 unknown unit, 3 dimension mismatch, 4 parse error)\n");
     // println!("OR\n");
     // println!("Usage: ark <quantity> <unit> <target-unit> ...\n");
     
@@ -75,9 +694,8 @@ fn usage() {
     println!("Output: 2000000cm 20000m");
 
     println!("Input: ark 20 km cm m");
-    println!("Output: 2000000cm 20000m");    
-}
+    println!("Output: 2000000cm 20000m");
 
-fn invalid_input(arg: &str) {
-    println!("Invalid input {}", arg);
+    println!("Input: ark 86400s");
+    println!("Output: 1d");    
 }
@@ -0,0 +1,182 @@
+use crate::decimal::{Context, Decimal};
+use crate::enums::Unit;
+use crate::exact::Exact;
+use crate::{get_conversion_factor_to_base, get_exact_definition, get_offset_to_base, get_unit_dimension};
+
+// Number types that conversions can be computed in: f32 for embedded use, f64,
+// exact rationals, arbitrary-precision decimals, or a caller's own big-number type.
+// Every backend reads the unit tables' factors as `Exact` values, whose `parts()`
+// (num/den * 10^ten * π^pi, all integers) can be rebuilt without loss in any of
+// them; floats are rounded when the factor is built.
+
+pub trait Numeric: Sized {
+    // None if the value can't be represented, e.g. π in a rational type.
+    fn from_exact(exact: &Exact) -> Option<Self>;
+
+    // None on overflow, or on division by zero.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    fn checked_div(&self, other: &Self) -> Option<Self>;
+}
+
+// A unit's factor and offset to its base unit. Runtime-defined units, and built-in
+// ones whose factor is rounded or measured, only have floats, read back as the
+// shortest decimal that gives the same float.
+pub fn factors(unit: &Unit) -> Option<(Exact, Exact)> {
+    if let Some(definition) = get_exact_definition(unit) {
+        return Some(definition);
+    }
+    Some((Exact::from_f64(get_conversion_factor_to_base(unit))?, Exact::from_f64(get_offset_to_base(unit))?))
+}
+
+// `convert` for any backend. None if the dimensions differ or a step fails.
+pub fn convert<N: Numeric>(quantity: &N, from_unit: &Unit, to_unit: &Unit) -> Option<N> {
+    if get_unit_dimension(from_unit) != get_unit_dimension(to_unit) {
+        return None;
+    }
+    let (from_factor, from_offset) = factors(from_unit)?;
+    let (to_factor, to_offset) = factors(to_unit)?;
+    let base_quantity = quantity.checked_mul(&N::from_exact(&from_factor)?)?.checked_add(&N::from_exact(&from_offset)?)?;
+    base_quantity.checked_sub(&N::from_exact(&to_offset)?)?.checked_div(&N::from_exact(&to_factor)?)
+}
+
+// Results are kept even when they overflow to infinity, as with `convert`.
+impl Numeric for f64 {
+    fn from_exact(exact: &Exact) -> Option<f64> {
+        Some(exact.to_f64())
+    }
+
+    fn checked_add(&self, other: &f64) -> Option<f64> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &f64) -> Option<f64> {
+        Some(self - other)
+    }
+
+    fn checked_mul(&self, other: &f64) -> Option<f64> {
+        Some(self * other)
+    }
+
+    fn checked_div(&self, other: &f64) -> Option<f64> {
+        if *other == 0.0 { None } else { Some(self / other) }
+    }
+}
+
+impl Numeric for f32 {
+    fn from_exact(exact: &Exact) -> Option<f32> {
+        // The correctly rounded f64, narrowed. Rounding twice can only land one ulp off
+        // when the f64 falls exactly on an f32 tie, which the first rounding created.
+        Some(exact.to_f64() as f32)
+    }
+
+    fn checked_add(&self, other: &f32) -> Option<f32> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &f32) -> Option<f32> {
+        Some(self - other)
+    }
+
+    fn checked_mul(&self, other: &f32) -> Option<f32> {
+        Some(self * other)
+    }
+
+    fn checked_div(&self, other: &f32) -> Option<f32> {
+        if *other == 0.0 { None } else { Some(self / other) }
+    }
+}
+
+impl Numeric for Exact {
+    fn from_exact(exact: &Exact) -> Option<Exact> {
+        Some(*exact)
+    }
+
+    fn checked_add(&self, other: &Exact) -> Option<Exact> {
+        Exact::checked_add(*self, *other)
+    }
+
+    fn checked_sub(&self, other: &Exact) -> Option<Exact> {
+        Exact::checked_sub(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Exact) -> Option<Exact> {
+        Exact::checked_mul(*self, *other)
+    }
+
+    fn checked_div(&self, other: &Exact) -> Option<Exact> {
+        Exact::checked_div(*self, *other)
+    }
+}
+
+// At the default precision, rounding at every step; `convert_decimal` takes a
+// context and rounds once.
+impl Numeric for Decimal {
+    fn from_exact(exact: &Exact) -> Option<Decimal> {
+        Some(Decimal::from_exact(*exact, &Context::default()))
+    }
+
+    fn checked_add(&self, other: &Decimal) -> Option<Decimal> {
+        Some(self.add(other, &Context::default()))
+    }
+
+    fn checked_sub(&self, other: &Decimal) -> Option<Decimal> {
+        Some(self.sub(other, &Context::default()))
+    }
+
+    fn checked_mul(&self, other: &Decimal) -> Option<Decimal> {
+        Some(self.mul(other, &Context::default()))
+    }
+
+    fn checked_div(&self, other: &Decimal) -> Option<Decimal> {
+        self.div(other, &Context::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Frequency, Length, Mass, Temperature};
+
+    const INCH: Unit = Unit::Length(Length::Inch);
+
+    #[test]
+    fn f32_factors_are_the_nearest_f32() {
+        assert_eq!(<f32 as Numeric>::from_exact(&Exact::parse("0.0254").unwrap()), Some(0.0254f32));
+        assert_eq!(<f32 as Numeric>::from_exact(&Exact::ratio(1, 3).unwrap()), Some(1.0f32 / 3.0));
+        assert_eq!(<f32 as Numeric>::from_exact(&Exact::ratio(5, 9).unwrap()), Some(5.0f32 / 9.0));
+        assert_eq!(<f32 as Numeric>::from_exact(&Exact::ONE.times_pi(1).unwrap()), Some(std::f32::consts::PI));
+        assert_eq!(<f32 as Numeric>::from_exact(&Exact::parse("1e-30").unwrap()), Some(1e-30f32));
+    }
+
+    #[test]
+    fn every_backend_converts() {
+        let meter = Unit::Length(Length::Meter);
+        assert_eq!(convert(&12.0f32, &INCH, &meter), Some(0.3048f32));
+        // Floats round at every step, where the exact backends don't.
+        assert_eq!(convert(&12.0f64, &INCH, &meter), Some(12.0 * 0.0254));
+        assert_eq!(convert(&Exact::integer(12), &INCH, &meter), Exact::parse("0.3048"));
+        assert_eq!(convert(&Decimal::integer(12), &INCH, &meter), Decimal::parse("0.3048"));
+        let (celsius, fahrenheit) = (Unit::Temperature(Temperature::Celsius), Unit::Temperature(Temperature::Fahrenheit));
+        assert_eq!(convert(&Exact::integer(37), &celsius, &fahrenheit), Exact::parse("98.6"));
+        assert_eq!(convert(&Decimal::integer(0), &celsius, &Unit::Temperature(Temperature::Kelvin)), Decimal::parse("273.15"));
+    }
+
+    #[test]
+    fn inexact_factors_fall_back_to_their_floats() {
+        let light_year = Unit::Length(Length::LightYear);
+        let (factor, offset) = factors(&light_year).unwrap();
+        assert_eq!((factor.to_f64(), offset), (get_conversion_factor_to_base(&light_year), Exact::ZERO));
+        // Powers of π carry through exactly.
+        let radians = Unit::Frequency(Frequency::RadianPerSecond);
+        assert_eq!(convert(&Exact::ONE, &radians, &Unit::Frequency(Frequency::Hertz)), Exact::ratio(1, 2).and_then(|half| half.times_pi(-1)));
+    }
+
+    #[test]
+    fn mismatched_dimensions_and_zero_divisors_fail() {
+        assert_eq!(convert(&1.0f32, &INCH, &Unit::Mass(Mass::Kilogram)), None);
+        assert_eq!(Numeric::checked_div(&1.0f64, &0.0), None);
+        assert_eq!(Numeric::checked_div(&Exact::ONE, &Exact::ZERO), None);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::enums::Unit;
+use crate::format::FormatOptions;
+use crate::humanize::humanize;
+use crate::registry::UnitRegistry;
+use crate::{Quantity, get_unit_dimension};
+
+// Renderings of conversion results: the human format, and machine-readable ones
+// for scripts and notebooks.
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OutputFormat {
+    Human, // "2000000cm 20000m"
+    Json,  // One object per input
+    Csv,   // One row per result, with a header row
+    Tsv,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<OutputFormat> {
+        match name.to_lowercase().as_str() {
+            "human" | "text" => Some(OutputFormat::Human),
+            "json" => Some(OutputFormat::Json),
+            "csv" => Some(OutputFormat::Csv),
+            "tsv" => Some(OutputFormat::Tsv),
+            _ => None,
+        }
+    }
+
+    fn separator(&self) -> Option<char> {
+        match self {
+            OutputFormat::Csv => Some(','),
+            OutputFormat::Tsv => Some('\t'),
+            _ => None,
+        }
+    }
+}
+
+// An input quantity and what it was converted to.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Conversion {
+    pub input: Quantity,
+    pub results: Vec<Quantity>,
+}
+
+impl Conversion {
+    // Converts `input` to each target; with no targets, to the most readable unit.
+    // Fails with the first target `input` cannot be converted to.
+    pub fn new(input: Quantity, targets: &[Unit]) -> Result<Conversion, Unit> {
+        let dimension = get_unit_dimension(&input.unit);
+        if let Some(target) = targets.iter().find(|target| get_unit_dimension(target) != dimension) {
+            return Err(target.clone());
+        }
+        let results = if targets.is_empty() {
+            vec![humanize(&input, None)]
+        } else {
+            targets
+                .iter()
+                .map(|target| input.convert_to(target).ok_or_else(|| target.clone()))
+                .collect::<Result<Vec<Quantity>, Unit>>()?
+        };
+        Ok(Conversion { input, results })
+    }
+}
+
+// The uncertainty columns are empty for exact values.
+const COLUMNS: [&str; 8] = ["input_value", "input_unit", "dimension", "value", "unit", "symbol", "input_uncertainty", "uncertainty"];
+
+// Header row for CSV and TSV; other formats have none.
+pub fn header(format: OutputFormat) -> Option<String> {
+    let separator = format.separator()?;
+    Some(COLUMNS.join(&separator.to_string()))
+}
+
+// Renders one conversion. CSV and TSV produce one line per result, JSON a single
+// line, so several conversions can be streamed one after another.
+pub fn render(format: OutputFormat, registry: &UnitRegistry, conversion: &Conversion, options: &FormatOptions) -> String {
+    match format {
+        OutputFormat::Human => {
+            let results: Vec<String> = conversion
+                .results
+                .iter()
+                .map(|result| options.format_uncertain_quantity(result.value, result.uncertainty, &registry.symbol_of(&result.unit)))
+                .collect();
+            results.join(" ")
+        }
+        OutputFormat::Json => render_json(registry, conversion),
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let separator = format.separator().unwrap_or(',');
+            let input = &conversion.input;
+            let rows: Vec<String> = conversion
+                .results
+                .iter()
+                .map(|result| {
+                    let fields = [
+                        number(input.value),
+                        registry.name_of(&input.unit),
+                        get_unit_dimension(&input.unit).name().to_string(),
+                        number(result.value),
+                        registry.name_of(&result.unit),
+                        registry.symbol_of(&result.unit),
+                        input.uncertainty.map_or_else(String::new, number),
+                        result.uncertainty.map_or_else(String::new, number),
+                    ];
+                    let fields: Vec<String> = fields.iter().map(|field| delimited_field(field, separator)).collect();
+                    fields.join(&separator.to_string())
+                })
+                .collect();
+            rows.join("\n")
+        }
+    }
+}
+
+fn render_json(registry: &UnitRegistry, conversion: &Conversion) -> String {
+    let quantity = |quantity: &Quantity| {
+        let uncertainty = quantity.uncertainty.map_or_else(String::new, |u| format!(",\"uncertainty\":{}", json_number(u)));
+        format!(
+            "{{\"value\":{}{},\"unit\":{},\"symbol\":{}}}",
+            json_number(quantity.value),
+            uncertainty,
+            json_string(&registry.name_of(&quantity.unit)),
+            json_string(&registry.symbol_of(&quantity.unit)),
+        )
+    };
+    let results: Vec<String> = conversion.results.iter().map(quantity).collect();
+    format!(
+        "{{\"input\":{},\"dimension\":{},\"results\":[{}]}}",
+        quantity(&conversion.input),
+        json_string(get_unit_dimension(&conversion.input.unit).name()),
+        results.join(","),
+    )
+}
+
+// Shortest round-trip digits, switching to exponent form for extreme magnitudes.
+fn number(value: f64) -> String {
+    FormatOptions::default().format(value)
+}
+
+// JSON has no representation for NaN or infinities.
+fn json_number(value: f64) -> String {
+    if value.is_finite() { number(value) } else { "null".to_string() }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Quotes a field that contains the separator, a quote or a line break.
+pub fn delimited_field(field: &str, separator: char) -> String {
+    if field.contains([separator, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Length, Mass};
+
+    fn km_to_cm_and_m() -> Conversion {
+        let targets = [Unit::Length(Length::Centimeter), Unit::Length(Length::Meter)];
+        Conversion::new(Quantity::new(20.0, Unit::Length(Length::Kilometer)), &targets).unwrap()
+    }
+
+    #[test]
+    fn conversions_check_every_target() {
+        let conversion = km_to_cm_and_m();
+        let values: Vec<f64> = conversion.results.iter().map(|result| result.value).collect();
+        assert_eq!(values, [2000000.0, 20000.0]);
+        let kilogram = Unit::Mass(Mass::Kilogram);
+        let mixed = Conversion::new(Quantity::new(1.0, Unit::Length(Length::Meter)), &[Unit::Length(Length::Foot), kilogram.clone()]);
+        assert_eq!(mixed, Err(kilogram));
+        let humanized = Conversion::new(Quantity::new(1500.0, Unit::Length(Length::Meter)), &[]).unwrap();
+        assert_eq!(humanized.results, [Quantity::new(1.5, Unit::Length(Length::Kilometer))]);
+    }
+
+    #[test]
+    fn renders_each_format() {
+        let registry = UnitRegistry::builtin();
+        let options = FormatOptions::default();
+        let conversion = km_to_cm_and_m();
+        assert_eq!(render(OutputFormat::Human, registry, &conversion, &options), "2000000cm 20000m");
+        let json = render(OutputFormat::Json, registry, &conversion, &options);
+        assert!(json.starts_with("{\"input\":{\"value\":20,\"unit\":\"kilometer\",\"symbol\":\"km\"},\"dimension\":\"length\""), "{}", json);
+        assert!(json.ends_with("{\"value\":20000,\"unit\":\"meter\",\"symbol\":\"m\"}]}"), "{}", json);
+        assert_eq!(header(OutputFormat::Tsv).unwrap().split('\t').count(), COLUMNS.len());
+        assert_eq!(header(OutputFormat::Human), None);
+        let csv = render(OutputFormat::Csv, registry, &conversion, &options);
+        assert_eq!(csv, "20,kilometer,length,2000000,centimeter,cm,,\n20,kilometer,length,20000,meter,m,,");
+    }
+
+    #[test]
+    fn uncertainties_fill_their_columns() {
+        let registry = UnitRegistry::builtin();
+        let input = Quantity::new(1.0, Unit::Length(Length::Meter)).with_uncertainty(0.01);
+        let conversion = Conversion::new(input, &[Unit::Length(Length::Centimeter)]).unwrap();
+        let csv = render(OutputFormat::Csv, registry, &conversion, &FormatOptions::default());
+        assert_eq!(csv, "1,meter,length,100,centimeter,cm,0.01,1");
+        let json = render(OutputFormat::Json, registry, &conversion, &FormatOptions::default());
+        assert!(json.contains("{\"value\":100,\"uncertainty\":1,\"unit\":\"centimeter\""), "{}", json);
+    }
+
+    #[test]
+    fn escapes_strings_and_fields() {
+        assert_eq!(json_string("a \"b\"\\\n\u{1}"), "\"a \\\"b\\\"\\\\\\n\\u0001\"");
+        assert_eq!(json_number(f64::NAN), "null");
+        assert_eq!(delimited_field("plain", ','), "plain");
+        assert_eq!(delimited_field("a,b", ','), "\"a,b\"");
+        assert_eq!(delimited_field("a,b", '\t'), "a,b");
+        assert_eq!(delimited_field("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+        assert_eq!(OutputFormat::from_name("JSON"), Some(OutputFormat::Json));
+        assert_eq!(OutputFormat::from_name("xml"), None);
+    }
+}
@@ -0,0 +1,348 @@
+use std::fmt;
+
+use crate::Quantity;
+use crate::decimal::Decimal;
+use crate::enums::Unit;
+use crate::exact::Exact;
+use crate::interval::{Interval, IntervalQuantity};
+use crate::registry::{LookupError, UnitRegistry};
+
+// Quantity expressions: a number followed by a unit, with or without a space
+// between them ("20km", "20 km", "-1.5e3 mL", ".5 mi"). The number may carry a
+// standard uncertainty, written "12.3 ± 0.2 mm" (or "+/-", "+-"), "(12.3 ± 0.2) mm",
+// or concisely as "12.3(2) mm", where the digits in parentheses count in the last
+// place of the number. Ranges are written "10..12 bar" or "10–12 bar".
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseError {
+    Empty,
+    InvalidNumber(String),
+    MissingUnit,
+    InvalidUncertainty(String),
+    UnexpectedUncertainty, // Where only an exact number will do
+    OutOfRange(String),    // Too large for an f64, e.g. "1e400"
+    Unit(LookupError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty input"),
+            ParseError::InvalidNumber(text) => write!(f, "invalid number {:?}", text),
+            ParseError::MissingUnit => write!(f, "missing unit"),
+            ParseError::InvalidUncertainty(text) => write!(f, "invalid uncertainty {:?}", text),
+            ParseError::UnexpectedUncertainty => write!(f, "an uncertainty is not allowed here"),
+            ParseError::OutOfRange(text) => write!(f, "{} is out of range", text),
+            ParseError::Unit(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Length of the numeric literal at the start of `input`: sign, digits, decimal
+// point, and an exponent if one follows ("1e3m" is 1000 m, "2em" is 2 em).
+pub(crate) fn number_len(input: &str) -> usize {
+    let bytes = input.as_bytes();
+    let digits_from = |mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    };
+    let mut i = 0;
+    if i < bytes.len() && (bytes[i] == b'+' || bytes[i] == b'-') {
+        i += 1;
+    }
+    i = digits_from(i);
+    if i < bytes.len() && bytes[i] == b'.' {
+        i = digits_from(i + 1);
+    }
+    i + exponent_len(&input[i..])
+}
+
+// Length of an exponent ("e3", "E-6") at the start of `input`, or 0 if there is none.
+pub(crate) fn exponent_len(input: &str) -> usize {
+    let bytes = input.as_bytes();
+    if bytes.first().is_none_or(|&b| b != b'e' && b != b'E') {
+        return 0;
+    }
+    let mut i = 1;
+    if i < bytes.len() && (bytes[i] == b'+' || bytes[i] == b'-') {
+        i += 1;
+    }
+    let digits = bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+    if digits == 0 { 0 } else { i + digits }
+}
+
+// A literal as a finite f64; "1e400" would otherwise read as infinity.
+fn parse_float(number: &str, invalid: impl FnOnce() -> ParseError) -> Result<f64, ParseError> {
+    match number.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        Ok(_) => Err(ParseError::OutOfRange(number.to_string())),
+        Err(_) => Err(invalid()),
+    }
+}
+
+const PLUS_MINUS: [&str; 3] = ["±", "+/-", "+-"];
+
+// Place value of a literal's last digit: 0.1 for "12.3", 100 for "1.5e3".
+fn last_place(number: &str) -> f64 {
+    let (mantissa, exponent) = match number.find(['e', 'E']) {
+        Some(at) => (&number[..at], number[at + 1..].parse::<i32>().unwrap_or(0)),
+        None => (number, 0),
+    };
+    let fraction = mantissa.split_once('.').map_or(0, |(_, fraction)| fraction.len() as i32);
+    10f64.powi(exponent - fraction)
+}
+
+// The number at the start of `input`, its uncertainty if one is given, and the
+// length they take up.
+fn leading_number(input: &str) -> Result<(f64, Option<f64>, usize), ParseError> {
+    let invalid = || ParseError::InvalidUncertainty(input.to_string());
+    if let Some(inner) = input.strip_prefix('(') {
+        // "(12.3 ± 0.2) mm", "(1.23 ± 0.02)e3 mm"
+        let close = inner.find(')').ok_or_else(invalid)?;
+        let (value, uncertainty) = parse_number(&inner[..close]).filter(|(_, u)| u.is_some()).ok_or_else(invalid)?;
+        let after = &inner[close + 1..];
+        let exponent = exponent_len(after);
+        let scale = if exponent > 0 { 10f64.powi(after[1..exponent].parse().map_err(|_| invalid())?) } else { 1.0 };
+        if !(value * scale).is_finite() {
+            return Err(ParseError::OutOfRange(input[..close + 2 + exponent].to_string()));
+        }
+        return Ok((value * scale, uncertainty.map(|u| u * scale), close + 2 + exponent));
+    }
+    let len = number_len(input);
+    let number = &input[..len];
+    let value = parse_float(number, || {
+        let word = input.split_whitespace().next().unwrap_or(input);
+        ParseError::InvalidNumber(word.to_string())
+    })?;
+    let rest = &input[len..];
+    if let Some(inner) = rest.strip_prefix('(') {
+        // "12.3(2)", "12.3(0.2)", "1.23(2)e3"
+        let close = inner.find(')').ok_or_else(invalid)?;
+        let digits = &inner[..close];
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return Err(invalid());
+        }
+        let mut uncertainty = digits.parse::<f64>().map_err(|_| invalid())?;
+        if !digits.contains('.') {
+            uncertainty *= last_place(number);
+        }
+        let after = &inner[close + 1..];
+        let exponent = exponent_len(after);
+        let scale = if exponent > 0 { 10f64.powi(after[1..exponent].parse().map_err(|_| invalid())?) } else { 1.0 };
+        if !(value * scale).is_finite() {
+            return Err(ParseError::OutOfRange(input[..len + close + 2 + exponent].to_string()));
+        }
+        return Ok((value * scale, Some(uncertainty * scale), len + close + 2 + exponent));
+    }
+    let trimmed = rest.trim_start();
+    for sign in PLUS_MINUS {
+        if let Some(after) = trimmed.strip_prefix(sign) {
+            let after = after.trim_start();
+            let uncertainty_len = number_len(after);
+            let uncertainty = after[..uncertainty_len].parse::<f64>().ok().filter(|u| *u >= 0.0 && u.is_finite()).ok_or_else(invalid)?;
+            return Ok((value, Some(uncertainty), input.len() - after.len() + uncertainty_len));
+        }
+    }
+    Ok((value, None, len))
+}
+
+// A whole string as a number with an optional uncertainty: "12.3", "12.3(2)",
+// "12.3 ± 0.2".
+pub(crate) fn parse_number(text: &str) -> Option<(f64, Option<f64>)> {
+    let text = text.trim();
+    match leading_number(text) {
+        Ok((value, uncertainty, len)) if len == text.len() => Some((value, uncertainty)),
+        _ => None,
+    }
+}
+
+// Splits "20 km" into (20.0, "km") without looking the unit up.
+pub fn split_quantity(input: &str) -> Result<(f64, &str), ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let (number, unit) = input.split_at(number_len(input));
+    let value = parse_float(number, || {
+        let word = input.split_whitespace().next().unwrap_or(input);
+        ParseError::InvalidNumber(word.to_string())
+    })?;
+    let unit = unit.trim();
+    if unit.is_empty() {
+        return Err(ParseError::MissingUnit);
+    }
+    Ok((value, unit))
+}
+
+// Splits "12.3 ± 0.2 mm" into (12.3, Some(0.2), "mm") without looking the unit up.
+pub fn split_measurement(input: &str) -> Result<(f64, Option<f64>, &str), ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let (value, uncertainty, len) = leading_number(input)?;
+    let unit = input[len..].trim();
+    if unit.is_empty() {
+        return Err(ParseError::MissingUnit);
+    }
+    Ok((value, uncertainty, unit))
+}
+
+pub fn parse_quantity(input: &str, registry: &UnitRegistry) -> Result<Quantity, ParseError> {
+    let (value, uncertainty, unit) = split_measurement(input)?;
+    let unit = registry.resolve(unit).map_err(ParseError::Unit)?;
+    let quantity = Quantity::new(value, unit);
+    Ok(match uncertainty {
+        Some(uncertainty) => quantity.with_uncertainty(uncertainty),
+        None => quantity,
+    })
+}
+
+const INTERVAL_SEPARATORS: [&str; 2] = ["..", "–"];
+
+// Whether `input` is written as a range rather than a single quantity.
+pub fn is_interval(input: &str) -> bool {
+    INTERVAL_SEPARATORS.iter().any(|separator| input.contains(separator))
+}
+
+// Splits "10..12 bar" into (10.0, 12.0, "bar") without looking the unit up.
+pub fn split_interval(input: &str) -> Result<(f64, f64, &str), ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let Some((at, separator)) = INTERVAL_SEPARATORS.iter().filter_map(|separator| Some((input.find(separator)?, separator))).min() else {
+        return Err(ParseError::InvalidNumber(input.to_string()));
+    };
+    let first = input[..at].trim();
+    let low = parse_float(first, || ParseError::InvalidNumber(first.to_string()))?;
+    let (high, unit) = split_quantity(&input[at + separator.len()..])?;
+    Ok((low, high, unit))
+}
+
+pub fn parse_interval(input: &str, registry: &UnitRegistry) -> Result<IntervalQuantity, ParseError> {
+    let (low, high, unit) = split_interval(input)?;
+    let unit = registry.resolve(unit).map_err(ParseError::Unit)?;
+    Ok(IntervalQuantity::new(Interval::new(low, high), unit))
+}
+
+// Exact and decimal quantities have no uncertainty.
+fn reject_uncertainty(input: &str) -> Result<(), ParseError> {
+    match split_measurement(input) {
+        Ok((_, Some(_), _)) => Err(ParseError::UnexpectedUncertainty),
+        _ => Ok(()),
+    }
+}
+
+// Splits "1e400 m" into ("1e400", "m"), leaving the number's range to the caller.
+fn split_literal(input: &str) -> Result<(&str, &str), ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let (number, unit) = input.split_at(number_len(input));
+    if number.is_empty() {
+        let word = input.split_whitespace().next().unwrap_or(input);
+        return Err(ParseError::InvalidNumber(word.to_string()));
+    }
+    let unit = unit.trim();
+    if unit.is_empty() {
+        return Err(ParseError::MissingUnit);
+    }
+    Ok((number, unit))
+}
+
+// Like `parse_quantity`, keeping the number exactly as written.
+pub fn parse_exact_quantity(input: &str, registry: &UnitRegistry) -> Result<(Exact, Unit), ParseError> {
+    reject_uncertainty(input)?;
+    let (number, unit) = split_literal(input)?;
+    let value = Exact::parse(number).ok_or_else(|| ParseError::InvalidNumber(number.to_string()))?;
+    let unit = registry.resolve(unit).map_err(ParseError::Unit)?;
+    Ok((value, unit))
+}
+
+// Like `parse_quantity`, with a number of any length.
+pub fn parse_decimal_quantity(input: &str, registry: &UnitRegistry) -> Result<(Decimal, Unit), ParseError> {
+    reject_uncertainty(input)?;
+    let (number, unit) = split_literal(input)?;
+    let value = Decimal::parse(number).ok_or_else(|| ParseError::InvalidNumber(number.to_string()))?;
+    let unit = registry.resolve(unit).map_err(ParseError::Unit)?;
+    Ok((value, unit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Length, Pressure, Volume};
+
+    fn measurement(input: &str) -> Result<(f64, Option<f64>, &str), ParseError> {
+        split_measurement(input)
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn splits_numbers_from_units() {
+        assert_eq!(split_quantity("20km"), Ok((20.0, "km")));
+        assert_eq!(split_quantity("  20 km "), Ok((20.0, "km")));
+        assert_eq!(split_quantity("-1.5e3 mL"), Ok((-1500.0, "mL")));
+        assert_eq!(split_quantity(".5 mi"), Ok((0.5, "mi")));
+        // An exponent needs digits: "2em" is 2 em.
+        assert_eq!(split_quantity("1e3m"), Ok((1000.0, "m")));
+        assert_eq!(split_quantity("2em"), Ok((2.0, "em")));
+        assert_eq!(split_quantity("furlongs per fortnight 3"), Err(ParseError::InvalidNumber("furlongs".to_string())));
+        assert_eq!(split_quantity("20"), Err(ParseError::MissingUnit));
+        assert_eq!(split_quantity("   "), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn rejects_numbers_beyond_f64() {
+        assert_eq!(split_quantity("1e400 m"), Err(ParseError::OutOfRange("1e400".to_string())));
+        assert_eq!(split_quantity("1e400 m").unwrap_err().to_string(), "1e400 is out of range");
+        assert_eq!(measurement("(1 ± 0.1)e400 m"), Err(ParseError::OutOfRange("(1 ± 0.1)e400".to_string())));
+        assert_eq!(measurement("1.5(2)e400 m"), Err(ParseError::OutOfRange("1.5(2)e400".to_string())));
+        assert_eq!(split_interval("1 .. 1e400 bar"), Err(ParseError::OutOfRange("1e400".to_string())));
+        // Exact and decimal numbers take any exponent.
+        let registry = UnitRegistry::builtin();
+        assert_eq!(parse_decimal_quantity("1e400 m", registry), Ok((Decimal::parse("1e400").unwrap(), Unit::Length(Length::Meter))));
+        assert_eq!(parse_exact_quantity("1e400 m", registry).map(|(exact, _)| exact.to_string()), Ok("1e400".to_string()));
+    }
+
+    #[test]
+    fn reads_each_uncertainty_form() {
+        for input in ["12.3 ± 0.2 mm", "12.3 +/- 0.2 mm", "12.3+-0.2 mm", "(12.3 ± 0.2) mm", "12.3(2) mm", "12.3(0.2) mm"] {
+            let (value, uncertainty, unit) = measurement(input).unwrap();
+            assert_eq!((value, unit), (12.3, "mm"), "{}", input);
+            assert!(close(uncertainty.unwrap(), 0.2), "{}", input);
+        }
+        let (value, uncertainty, _) = measurement("1.23(2)e3 mm").unwrap();
+        assert!(close(value, 1230.0) && close(uncertainty.unwrap(), 20.0));
+        let (value, uncertainty, _) = measurement("(1.23 ± 0.02)e3 mm").unwrap();
+        assert!(close(value, 1230.0) && close(uncertainty.unwrap(), 20.0));
+        assert_eq!(measurement("12.3 mm"), Ok((12.3, None, "mm")));
+        for input in ["12.3 ± -1 mm", "12.3(x) mm", "(12.3) mm", "12.3 ± inf mm"] {
+            assert!(matches!(measurement(input), Err(ParseError::InvalidUncertainty(_))), "{}", input);
+        }
+    }
+
+    #[test]
+    fn parses_quantities_and_ranges() {
+        let registry = UnitRegistry::builtin();
+        let quantity = parse_quantity("2 gallons", registry).unwrap();
+        assert_eq!(quantity, Quantity::new(2.0, Unit::Volume(Volume::Gallon)));
+        assert!(matches!(parse_quantity("2 zzz", registry), Err(ParseError::Unit(LookupError::Unknown { .. }))));
+        assert_eq!(parse_quantity("1 ± 0.1 m", registry).unwrap().uncertainty, Some(0.1));
+        assert!(is_interval("10..12 bar") && is_interval("10–12 bar") && !is_interval("10 bar"));
+        assert_eq!(split_interval("10..12 bar"), Ok((10.0, 12.0, "bar")));
+        assert_eq!(split_interval("-5 – 5 bar"), Ok((-5.0, 5.0, "bar")));
+        let range = parse_interval("10–12 bar", registry).unwrap();
+        assert_eq!((range.interval.low, range.interval.high, range.unit), (10.0, 12.0, Unit::Pressure(Pressure::Bar)));
+        assert_eq!(parse_exact_quantity("1 ± 0.1 m", registry), Err(ParseError::UnexpectedUncertainty));
+    }
+}
@@ -0,0 +1,914 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::builtin::builtin_units;
+use crate::definitions::{DefinitionError, parse_definitions};
+use crate::enums::*;
+use crate::exact::Exact;
+use crate::gnu::{self, GnuImportReport};
+use crate::spellings::{self, Spelled};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum System {
+    Si,
+    SiAccepted, // Non-SI units accepted for use with the SI (litre, hour, tonne, ...)
+    Cgs,        // Mechanical and photometric CGS units (dyne, erg, stilb, ...)
+    CgsEsu,     // Electrostatic CGS units (statampere, statvolt, ...)
+    CgsEmu,     // Electromagnetic CGS units (abampere, maxwell, ...)
+    Imperial,
+    UsCustomary,
+    Natural, // Planck and atomic units
+    Colloquial,
+    Other,
+    UserDefined, // Loaded at runtime
+}
+
+impl System {
+    pub fn name(&self) -> &'static str {
+        match self {
+            System::Si => "SI",
+            System::SiAccepted => "SI-accepted",
+            System::Cgs => "CGS",
+            System::CgsEsu => "CGS-ESU",
+            System::CgsEmu => "CGS-EMU",
+            System::Imperial => "Imperial",
+            System::UsCustomary => "US customary",
+            System::Natural => "natural",
+            System::Colloquial => "colloquial",
+            System::Other => "other",
+            System::UserDefined => "user-defined",
+        }
+    }
+
+    pub const ALL: [System; 11] = [
+        System::Si,
+        System::SiAccepted,
+        System::Cgs,
+        System::CgsEsu,
+        System::CgsEmu,
+        System::Imperial,
+        System::UsCustomary,
+        System::Natural,
+        System::Colloquial,
+        System::Other,
+        System::UserDefined,
+    ];
+
+    // Accepts `name`, ignoring case, spaces, hyphens and underscores ("us_customary").
+    pub fn from_name(name: &str) -> Option<System> {
+        System::ALL.into_iter().find(|system| same_name(system.name(), name))
+    }
+}
+
+// How far a unit's factor can be relied on.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Exactness {
+    Exact,       // Exact by definition, and stored exactly (the international foot)
+    Rounded,     // Exact by definition, but stored rounded (the US gallon as 0.00378541 m³)
+    Measured,    // Depends on measured constants, so carries an uncertainty (the hartree)
+    Placeholder, // A rough or conventional stand-in, not a real conversion (the Beaufort scale)
+}
+
+impl Exactness {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Exactness::Exact => "exact",
+            Exactness::Rounded => "rounded",
+            Exactness::Measured => "measured",
+            Exactness::Placeholder => "placeholder",
+        }
+    }
+
+    pub const ALL: [Exactness; 4] = [Exactness::Exact, Exactness::Rounded, Exactness::Measured, Exactness::Placeholder];
+
+    pub fn from_name(name: &str) -> Option<Exactness> {
+        Exactness::ALL.into_iter().find(|exactness| same_name(exactness.name(), name))
+    }
+}
+
+// Where a unit's definition can be looked up.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Reference {
+    NistSp811,  // NIST Special Publication 811, Guide for the Use of the SI, Appendix B
+    SiBrochure, // BIPM, The International System of Units, 9th edition
+    Codata2022, // CODATA 2022 recommended values of the fundamental physical constants
+}
+
+impl Reference {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Reference::NistSp811 => "NIST SP 811",
+            Reference::SiBrochure => "BIPM SI Brochure",
+            Reference::Codata2022 => "CODATA 2022",
+        }
+    }
+
+    pub const ALL: [Reference; 3] = [Reference::NistSp811, Reference::SiBrochure, Reference::Codata2022];
+
+    // Also accepts the short names "nist", "bipm"/"si" and "codata".
+    pub fn from_name(name: &str) -> Option<Reference> {
+        let short = match name.to_lowercase().as_str() {
+            "nist" => Some(Reference::NistSp811),
+            "bipm" | "si" => Some(Reference::SiBrochure),
+            "codata" => Some(Reference::Codata2022),
+            _ => None,
+        };
+        short.or_else(|| Reference::ALL.into_iter().find(|reference| same_name(reference.name(), name)))
+    }
+}
+
+fn same_name(name: &str, query: &str) -> bool {
+    let normalize = |text: &str| -> String {
+        text.chars().filter(|c| !matches!(c, ' ' | '-' | '_')).flat_map(char::to_lowercase).collect()
+    };
+    normalize(name) == normalize(query)
+}
+
+// Which units a query returns; a field left as None matches any unit.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct UnitFilter {
+    pub dimension: Option<Dimension>,
+    pub system: Option<System>,
+    pub exactness: Option<Exactness>,
+    pub reference: Option<Reference>,
+}
+
+impl UnitFilter {
+    pub fn matches(&self, info: &UnitInfo) -> bool {
+        self.dimension.is_none_or(|dimension| info.dimension == dimension)
+            && self.system.is_none_or(|system| info.system == system)
+            && self.exactness.is_none_or(|exactness| info.exactness == exactness)
+            && self.reference.is_none_or(|reference| info.reference == Some(reference))
+    }
+}
+
+// Everything known about one unit. For built-in units `unit` is the typed enum value;
+// runtime units carry their own definition in `Unit::Custom`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct UnitInfo {
+    pub unit: Unit,
+    pub symbol: String,
+    pub name: String,
+    pub plural: String,
+    pub aliases: Vec<String>,
+    pub dimension: Dimension,
+    pub factor: f64, // base = value * factor + offset
+    pub offset: f64,
+    pub exact_factor: Option<Exact>, // None if only known as a float
+    pub exact_offset: Option<Exact>,
+    pub system: System,
+    pub exactness: Exactness,
+    pub reference: Option<Reference>,
+}
+
+impl UnitInfo {
+    // The decimal `offset` is taken to be exact; use `exact_offset` otherwise.
+    pub fn offset(mut self, offset: f64) -> UnitInfo {
+        self.offset = offset;
+        self.exact_offset = Exact::from_f64(offset);
+        self
+    }
+
+    pub fn exact(mut self, factor: Option<Exact>) -> UnitInfo {
+        self.exact_factor = factor;
+        self
+    }
+
+    pub fn exact_offset(mut self, offset: Option<Exact>) -> UnitInfo {
+        self.exact_offset = offset;
+        self
+    }
+
+    // Only an exact factor has an exact value: `--exact` and `--digits` refuse the
+    // others rather than print digits the table doesn't have.
+    pub fn exactness(mut self, exactness: Exactness) -> UnitInfo {
+        self.exactness = exactness;
+        if exactness != Exactness::Exact {
+            self.exact_factor = None;
+        }
+        self
+    }
+
+    pub fn reference(mut self, reference: Option<Reference>) -> UnitInfo {
+        self.reference = reference;
+        self
+    }
+
+    // Symbol, name, plural and aliases, in that order.
+    pub fn spellings(&self) -> impl Iterator<Item = &str> {
+        [self.symbol.as_str(), self.name.as_str(), self.plural.as_str()]
+            .into_iter()
+            .chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+// Position of a built-in unit within its dimension's enum.
+pub(crate) fn builtin_key(unit: &Unit) -> Option<(Dimension, usize)> {
+    let index = match unit {
+        Unit::Length(u) => u.clone() as usize,
+        Unit::Mass(u) => u.clone() as usize,
+        Unit::Area(u) => u.clone() as usize,
+        Unit::Volume(u) => u.clone() as usize,
+        Unit::Force(u) => u.clone() as usize,
+        Unit::Energy(u) => u.clone() as usize,
+        Unit::Temperature(u) => u.clone() as usize,
+        Unit::Time(u) => u.clone() as usize,
+        Unit::Speed(u) => u.clone() as usize,
+        Unit::Pressure(u) => u.clone() as usize,
+        Unit::Frequency(u) => u.clone() as usize,
+        Unit::ElectricCurrent(u) => u.clone() as usize,
+        Unit::Voltage(u) => u.clone() as usize,
+        Unit::Capacitance(u) => u.clone() as usize,
+        Unit::Luminosity(u) => u.clone() as usize,
+        Unit::Radiation(u) => u.clone() as usize,
+        Unit::MagneticFlux(u) => u.clone() as usize,
+        Unit::Custom(_) => return None,
+    };
+    Some((crate::get_unit_dimension(unit), index))
+}
+
+// All units by spelling. Starts out with the built-in units; definitions files and
+// GNU units databases can be layered on top at runtime.
+#[derive(Debug, Clone)]
+pub struct UnitRegistry {
+    units: Vec<UnitInfo>,
+    by_name: HashMap<String, Vec<usize>>,          // Exact spelling -> indices into `units`
+    by_folded: HashMap<String, Vec<Spelling>>,     // Lowercased spelling -> candidates
+    by_builtin: HashMap<(Dimension, usize), usize>, // builtin_key -> index into `units`
+    lines: HashMap<usize, usize>,                  // Definitions-file line of runtime units
+    prefixes: Vec<(String, f64)>,                  // Longest first, e.g. ("kilo", 1e3), ("k", 1e3)
+    builtin_only: bool,                            // No units added, so `spellings` can answer lookups
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Spelling {
+    index: usize,
+    case_sensitive: bool, // Only ever matches exactly, e.g. "R" for the roentgen
+    prefix: Option<char>, // A prefix letter that must keep its case, e.g. the m of "mg"
+}
+
+// Lowercased, these symbols are common words that would otherwise turn into units
+// ("a", "r", "fl"). Symbols that only differ in case (mA/MA, Pa/pA) need no entry:
+// their lowercased form names more than one unit, so it is ambiguous anyway. build.rs
+// keeps a copy of this list for the generated tables.
+const WORDS: [&str; 7] = ["a", "r", "rd", "am", "ph", "fl", "de"];
+
+fn is_case_sensitive(spelling: &str) -> bool {
+    WORDS.contains(&spelling.to_lowercase().as_str())
+}
+
+// Lowercase SI prefix letters whose capital is another prefix (mega, peta), with the
+// word the names of units so prefixed start with. Their symbols don't match with the
+// letter capitalised, so "Mg" isn't taken for "mg" (the milligram) nor "PM" for "pm".
+// Lowercase queries still match as they always did ("mpa" is the megapascal). build.rs
+// keeps a copy of this list too.
+const PREFIX_CASES: [(char, &str); 2] = [('m', "milli"), ('p', "pico")];
+
+fn prefix_letter(info: &UnitInfo, spelling: &str) -> Option<char> {
+    let first = spelling.chars().next()?;
+    let prefixed = PREFIX_CASES.iter().any(|(letter, word)| *letter == first && info.name.starts_with(word));
+    (spelling == info.symbol && prefixed).then_some(first)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LookupError {
+    Unknown { name: String, suggestions: Vec<String> }, // Closest spellings, best first
+    Ambiguous { name: String, candidates: Vec<Unit> },
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LookupError::Unknown { name, suggestions } => {
+                write!(f, "unknown unit {:?}", name)?;
+                match suggestions.as_slice() {
+                    [] => Ok(()),
+                    [only] => write!(f, "; did you mean {:?}?", only),
+                    [rest @ .., last] => {
+                        let rest: Vec<String> = rest.iter().map(|s| format!("{:?}", s)).collect();
+                        write!(f, "; did you mean {} or {:?}?", rest.join(", "), last)
+                    }
+                }
+            }
+            LookupError::Ambiguous { name, candidates } => {
+                let names: Vec<String> = candidates.iter().map(describe_unit).collect();
+                write!(f, "{:?} is ambiguous; did you mean {}?", name, names.join(" or "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for LookupError {}
+
+// "megahertz (MHz)"
+fn describe_unit(unit: &Unit) -> String {
+    let registry = UnitRegistry::builtin();
+    match unit {
+        Unit::Custom(custom) => custom.name.clone(),
+        _ => format!("{} ({})", registry.name_of(unit), registry.symbol_of(unit)),
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Suggestion {
+    pub spelling: String,
+    pub unit: Unit,
+    pub distance: usize, // Edits away from the input; 0 for known misspellings
+}
+
+const MAX_SUGGESTIONS: usize = 3;
+
+// Misspellings and regional variants, replaced wherever they occur in a name.
+const TYPOS: &[(&str, &str)] = &[
+    ("metre", "meter"),
+    ("litre", "liter"),
+    ("gramme", "gram"),
+    ("farenheit", "fahrenheit"),
+    ("farenheight", "fahrenheit"),
+    ("fahrenheight", "fahrenheit"),
+    ("celcius", "celsius"),
+    ("celsuis", "celsius"),
+    ("centigrade", "celsius"),
+    ("lbs", "pound"),
+    ("lbm", "pound"),
+    ("hrs", "hour"),
+    ("mins", "minute"),
+    ("secs", "second"),
+    ("yds", "yard"),
+    ("sqft", "square_foot"),
+    ("sqm", "square_meter"),
+];
+
+// Optimal string alignment distance: insertions, deletions, substitutions and
+// swaps of adjacent characters each count as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+// A spelling claimed by more than one unit.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Collision {
+    pub spelling: String,
+    pub units: Vec<Unit>,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(PathBuf, std::io::Error),
+    Definition(PathBuf, DefinitionError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            LoadError::Definition(path, err) => write!(f, "{}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl Default for UnitRegistry {
+    fn default() -> UnitRegistry {
+        UnitRegistry::new()
+    }
+}
+
+impl UnitRegistry {
+    pub fn new() -> UnitRegistry {
+        UnitRegistry::builtin().clone()
+    }
+
+    // The shared registry of built-in units.
+    pub fn builtin() -> &'static UnitRegistry {
+        static BUILTIN: OnceLock<UnitRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let mut registry = UnitRegistry {
+                units: vec![],
+                by_name: HashMap::new(),
+                by_folded: HashMap::new(),
+                by_builtin: HashMap::new(),
+                lines: HashMap::new(),
+                prefixes: vec![],
+                builtin_only: false,
+            };
+            for info in builtin_units() {
+                registry.push(info);
+            }
+            registry.builtin_only = true;
+            debug_assert_eq!(registry.collisions(), vec![]);
+            // The generated tables must give the same answers as the maps.
+            debug_assert_eq!(registry.units.len(), spellings::BUILTIN_ROWS);
+            debug_assert!(registry.units.iter().flat_map(UnitInfo::spellings).all(|spelling| {
+                [spelling.to_string(), spelling.to_lowercase(), spelling.to_uppercase()].iter().all(|name| {
+                    match (spellings::lookup(name), registry.resolve_spelling(name)) {
+                        (Spelled::Unit(index), Ok(unit)) => registry.units[index].unit == unit,
+                        (Spelled::Ambiguous, Err(LookupError::Ambiguous { .. })) => true,
+                        (Spelled::Unknown, Err(LookupError::Unknown { .. })) => true,
+                        _ => false,
+                    }
+                })
+            }));
+            // An expression factor without its `.exact(...)` would get a rounded exact value.
+            debug_assert!(registry.units.iter().all(|info| {
+                let agrees = |exact: Option<Exact>, float: f64| exact.is_some_and(|exact| (exact.to_f64() - float).abs() <= float.abs() * 1e-15);
+                let factor = match info.exactness {
+                    Exactness::Exact => agrees(info.exact_factor, info.factor),
+                    _ => info.exact_factor.is_none(),
+                };
+                factor && agrees(info.exact_offset, info.offset)
+            }));
+            registry
+        })
+    }
+
+    fn push(&mut self, info: UnitInfo) -> usize {
+        self.builtin_only = false;
+        let index = self.units.len();
+        let mut seen: Vec<&str> = vec![];
+        for spelling in info.spellings() {
+            if seen.contains(&spelling) {
+                continue;
+            }
+            seen.push(spelling);
+            self.by_name.entry(spelling.to_string()).or_default().push(index);
+            let candidate = Spelling { index, case_sensitive: is_case_sensitive(spelling), prefix: prefix_letter(&info, spelling) };
+            self.by_folded.entry(spelling.to_lowercase()).or_default().push(candidate);
+        }
+        if let Some(key) = builtin_key(&info.unit) {
+            self.by_builtin.insert(key, index);
+        }
+        self.units.push(info);
+        index
+    }
+
+    // Adds a unit at runtime. Fails with the first spelling that is already taken,
+    // leaving the registry unchanged.
+    pub fn insert(&mut self, info: UnitInfo) -> Result<(), String> {
+        if let Some(taken) = info.spellings().find(|spelling| self.is_defined(spelling)) {
+            return Err(taken.to_string());
+        }
+        self.push(info);
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &UnitInfo> {
+        self.units.iter()
+    }
+
+    pub fn units_of(&self, dimension: Dimension) -> impl Iterator<Item = &UnitInfo> {
+        self.units.iter().filter(move |info| info.dimension == dimension)
+    }
+
+    pub fn query(&self, filter: UnitFilter) -> impl Iterator<Item = &UnitInfo> {
+        self.units.iter().filter(move |info| filter.matches(info))
+    }
+
+    // The unit the dimension's factors are relative to (meter, kilogram, kelvin, ...).
+    pub fn base_unit(&self, dimension: Dimension) -> Option<&UnitInfo> {
+        self.units_of(dimension).find(|info| info.factor == 1.0 && info.offset == 0.0)
+    }
+
+    // Units whose spellings or dimension match `query`, best first: exact spellings,
+    // then prefixes and substrings. Only if none of those match are the query's
+    // letters looked for in order ("psq" finds "pound_per_square_inch").
+    pub fn search(&self, query: &str) -> Vec<&UnitInfo> {
+        let query = query.to_lowercase();
+        let score = |text: &str, fuzzy: bool| {
+            let text = text.to_lowercase();
+            if text == query {
+                Some(0)
+            } else if text.starts_with(&query) {
+                Some(1)
+            } else if text.contains(&query) {
+                Some(2)
+            } else if fuzzy {
+                let mut letters = text.chars();
+                query.chars().all(|wanted| letters.any(|c| c == wanted)).then_some(3)
+            } else {
+                None
+            }
+        };
+        let ranked = |fuzzy: bool| {
+            let mut matches: Vec<(usize, usize, &UnitInfo)> = self
+                .units
+                .iter()
+                .enumerate()
+                .filter_map(|(index, info)| {
+                    let spelling = info.spellings().filter_map(|spelling| score(spelling, fuzzy)).min();
+                    let dimension = score(info.dimension.name(), false).map(|score| score.max(2));
+                    Some((spelling.into_iter().chain(dimension).min()?, index, info))
+                })
+                .collect();
+            matches.sort_by_key(|(score, index, _)| (*score, *index));
+            matches.into_iter().map(|(_, _, info)| info).collect::<Vec<&UnitInfo>>()
+        };
+        let matches = ranked(false);
+        if matches.is_empty() { ranked(true) } else { matches }
+    }
+
+    pub fn info(&self, unit: &Unit) -> Option<&UnitInfo> {
+        match builtin_key(unit) {
+            Some(key) => self.by_builtin.get(&key).map(|index| &self.units[*index]),
+            None => self.units.iter().find(|info| info.unit == *unit),
+        }
+    }
+
+    // Display symbol, e.g. "µs"; runtime units are shown by name.
+    pub fn symbol_of(&self, unit: &Unit) -> String {
+        match self.info(unit) {
+            Some(info) => info.symbol.clone(),
+            None => self.name_of(unit),
+        }
+    }
+
+    pub fn name_of(&self, unit: &Unit) -> String {
+        match (self.info(unit), unit) {
+            (Some(info), _) => info.name.clone(),
+            (None, Unit::Custom(custom)) => custom.name.clone(),
+            (None, _) => format!("{:?}", unit),
+        }
+    }
+
+    // Spellings are matched exactly first. Failing that, a case-insensitive match is
+    // used if it picks out a single unit; short symbols never match case-insensitively.
+    // A registered prefix may be applied to any linear unit.
+    pub fn resolve(&self, name: &str) -> Result<Unit, LookupError> {
+        self.resolve_in(name, None)
+    }
+
+    // Like `resolve`, but suggestions for an unknown name are limited to `dimension`.
+    pub fn resolve_in(&self, name: &str, dimension: Option<Dimension>) -> Result<Unit, LookupError> {
+        match self.resolve_unprefixed(name) {
+            Err(LookupError::Unknown { .. }) => self.resolve_prefixed(name).ok_or_else(|| LookupError::Unknown {
+                name: name.to_string(),
+                suggestions: self.suggest(name, dimension).into_iter().map(|suggestion| suggestion.spelling).collect(),
+            }),
+            result => result,
+        }
+    }
+
+    // Like `resolve`, without building an error; for the built-in units it doesn't
+    // allocate.
+    pub fn lookup(&self, name: &str) -> Option<Unit> {
+        if !self.builtin_only {
+            return self.resolve(name).ok();
+        }
+        match spellings::lookup(name) {
+            Spelled::Unit(index) => Some(self.units[index].unit.clone()),
+            Spelled::Ambiguous => None,
+            Spelled::Unknown => self.resolve_prefixed(name),
+        }
+    }
+
+    fn resolve_unprefixed(&self, name: &str) -> Result<Unit, LookupError> {
+        if self.builtin_only {
+            match spellings::lookup(name) {
+                Spelled::Unit(index) => return Ok(self.units[index].unit.clone()),
+                Spelled::Unknown => return Err(LookupError::Unknown { name: name.to_string(), suggestions: vec![] }),
+                Spelled::Ambiguous => {}
+            }
+        }
+        self.resolve_spelling(name)
+    }
+
+    // The lookup itself, by the spelling maps.
+    fn resolve_spelling(&self, name: &str) -> Result<Unit, LookupError> {
+        if let Some(indices) = self.by_name.get(name) {
+            return self.pick(name, indices.clone());
+        }
+        let candidates = self.by_folded.get(&name.to_lowercase()).map_or(&[][..], Vec::as_slice);
+        let all = dedup(candidates.iter().map(|candidate| candidate.index));
+        if all.len() > 1 {
+            return self.pick(name, all);
+        }
+        let usable = candidates
+            .iter()
+            .filter(|candidate| !candidate.case_sensitive && candidate.prefix.is_none_or(|letter| name.starts_with(letter)))
+            .map(|candidate| candidate.index);
+        let usable = dedup(usable);
+        match usable.first() {
+            Some(index) => Ok(self.units[*index].unit.clone()),
+            None => Err(LookupError::Unknown { name: name.to_string(), suggestions: vec![] }),
+        }
+    }
+
+    // Spellings close to `name`, best first: known misspellings and regional variants
+    // ("kilometre", "farenheit"), then spellings within a few edits. Only units of
+    // `dimension` are suggested when it is given.
+    pub fn suggest(&self, name: &str, dimension: Option<Dimension>) -> Vec<Suggestion> {
+        let wanted = |unit: &Unit| dimension.is_none_or(|dimension| crate::get_unit_dimension(unit) == dimension);
+        let lower = name.to_lowercase();
+        let mut suggestions = vec![];
+        for (typo, fix) in TYPOS {
+            if !lower.contains(typo) {
+                continue;
+            }
+            let fixed = lower.replace(typo, fix);
+            if let Ok(unit) = self.resolve_unprefixed(&fixed)
+                && wanted(&unit)
+            {
+                suggestions.push(Suggestion { spelling: fixed, unit, distance: 0 });
+            }
+        }
+        // A known typo is a confident answer; near misses would only add noise.
+        let limit = if suggestions.is_empty() { (lower.chars().count() / 3).max(1) } else { 0 };
+        for info in self.units.iter().filter(|info| wanted(&info.unit)) {
+            for spelling in info.spellings() {
+                let distance = edit_distance(&lower, &spelling.to_lowercase());
+                if distance <= limit {
+                    suggestions.push(Suggestion { spelling: spelling.to_string(), unit: info.unit.clone(), distance });
+                }
+            }
+        }
+        // Stable, so typo-table hits stay ahead of equally close spellings.
+        suggestions.sort_by_key(|suggestion| suggestion.distance);
+        let mut unique: Vec<Suggestion> = vec![];
+        for suggestion in suggestions {
+            if !unique.iter().any(|seen| seen.unit == suggestion.unit) {
+                unique.push(suggestion);
+            }
+        }
+        unique.truncate(MAX_SUGGESTIONS);
+        unique
+    }
+
+    fn pick(&self, name: &str, indices: Vec<usize>) -> Result<Unit, LookupError> {
+        match indices.as_slice() {
+            [index] => Ok(self.units[*index].unit.clone()),
+            _ => Err(LookupError::Ambiguous {
+                name: name.to_string(),
+                candidates: indices.iter().map(|index| self.units[*index].unit.clone()).collect(),
+            }),
+        }
+    }
+
+    fn resolve_prefixed(&self, name: &str) -> Option<Unit> {
+        let (_, scale, info) = self.split_prefix(name)?;
+        Some(Unit::Custom(CustomUnit {
+            name: name.to_string(),
+            dimension: info.dimension,
+            factor: scale * info.factor,
+            offset: 0.0,
+        }))
+    }
+
+    // The registered prefix, its scale and the unit it is applied to in `name`, e.g.
+    // ("k", 1000.0, foo) for "kfoo". Only linear units take prefixes.
+    pub fn split_prefix(&self, name: &str) -> Option<(&str, f64, &UnitInfo)> {
+        for (prefix, scale) in &self.prefixes {
+            let Some(rest) = name.strip_prefix(prefix.as_str()) else { continue };
+            if rest.is_empty() {
+                continue;
+            }
+            let Some(info) = self.unprefixed_info(rest) else { continue };
+            if info.dimension == Dimension::Temperature || info.offset != 0.0 {
+                continue;
+            }
+            return Some((prefix.as_str(), *scale, info));
+        }
+        None
+    }
+
+    fn unprefixed_info(&self, name: &str) -> Option<&UnitInfo> {
+        if self.builtin_only {
+            return match spellings::lookup(name) {
+                Spelled::Unit(index) => Some(&self.units[index]),
+                _ => None,
+            };
+        }
+        self.info(&self.resolve_unprefixed(name).ok()?)
+    }
+
+    // Every spelling claimed by more than one unit, across all dimensions.
+    pub fn collisions(&self) -> Vec<Collision> {
+        let mut collisions: Vec<Collision> = self
+            .by_name
+            .iter()
+            .filter(|(_, indices)| indices.len() > 1)
+            .map(|(spelling, indices)| Collision {
+                spelling: spelling.clone(),
+                units: indices.iter().map(|index| self.units[*index].unit.clone()).collect(),
+            })
+            .collect();
+        collisions.sort_by(|a, b| a.spelling.cmp(&b.spelling));
+        collisions
+    }
+
+    // Registers a runtime unit under all of its aliases.
+    pub fn define(&mut self, unit: CustomUnit, aliases: Vec<String>, line: usize) -> Result<(), String> {
+        let info = UnitInfo {
+            symbol: unit.name.clone(),
+            name: unit.name.clone(),
+            plural: unit.name.clone(),
+            aliases: aliases.into_iter().filter(|alias| *alias != unit.name).collect(),
+            dimension: unit.dimension,
+            factor: unit.factor,
+            offset: unit.offset,
+            exact_factor: None,
+            exact_offset: None,
+            system: System::UserDefined,
+            exactness: Exactness::Rounded, // Worked out from the definition in floating point
+            reference: None,
+            unit: Unit::Custom(unit),
+        };
+        self.insert(info)?;
+        self.lines.insert(self.units.len() - 1, line);
+        Ok(())
+    }
+
+    pub fn define_prefix(&mut self, prefix: &str, scale: f64) {
+        self.prefixes.retain(|(existing, _)| existing != prefix);
+        self.prefixes.push((prefix.to_string(), scale));
+        self.prefixes.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+    }
+
+    // True if `name` is taken exactly (case-sensitively), ignoring prefixes.
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.by_name.contains_key(name)
+    }
+
+    pub fn defined_on_line(&self, name: &str) -> Option<usize> {
+        let index = self.by_name.get(name)?.first()?;
+        self.lines.get(index).copied()
+    }
+
+    pub fn custom_units(&self) -> impl Iterator<Item = &UnitInfo> {
+        self.units.iter().filter(|info| matches!(info.unit, Unit::Custom(_)))
+    }
+
+    // Adds every definition in `source`; nothing is added if any line is invalid.
+    pub fn load_str(&mut self, source: &str) -> Result<usize, DefinitionError> {
+        let definitions = parse_definitions(source, self)?;
+        let count = definitions.len();
+        for definition in definitions {
+            // parse_definitions has already rejected every clash.
+            let _ = self.define(definition.unit, definition.aliases, definition.line);
+        }
+        Ok(count)
+    }
+
+    pub fn load_file(&mut self, path: &Path) -> Result<usize, LoadError> {
+        let source = std::fs::read_to_string(path).map_err(|err| LoadError::Io(path.to_path_buf(), err))?;
+        self.load_str(&source).map_err(|err| LoadError::Definition(path.to_path_buf(), err))
+    }
+
+    // Imports a GNU units database (definitions.units format).
+    pub fn load_gnu_str(&mut self, source: &str) -> GnuImportReport {
+        gnu::import_str(self, source)
+    }
+
+    // Like `load_gnu_str`, but follows `!include` directives relative to `path`.
+    pub fn load_gnu_file(&mut self, path: &Path) -> Result<GnuImportReport, LoadError> {
+        gnu::import_file(self, path)
+    }
+}
+
+fn dedup(indices: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut unique = vec![];
+    for index in indices {
+        if !unique.contains(&index) {
+            unique.push(index);
+        }
+    }
+    unique
+}
+
+// $XDG_CONFIG_HOME/un/units, falling back to ~/.config/un/units.
+pub fn default_definitions_path() -> Option<PathBuf> {
+    let config = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(config.join("un").join("units"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smoot() -> CustomUnit {
+        CustomUnit { name: "smoot".to_string(), dimension: Dimension::Length, factor: 1.7018, offset: 0.0 }
+    }
+
+    #[test]
+    fn resolves_names_symbols_and_aliases() {
+        let registry = UnitRegistry::builtin();
+        let meter = Unit::Length(Length::Meter);
+        for name in ["m", "meter", "meters", "METERS"] {
+            assert_eq!(registry.resolve(name), Ok(meter.clone()), "{}", name);
+        }
+        assert_eq!((registry.symbol_of(&meter), registry.name_of(&meter)), ("m".to_string(), "meter".to_string()));
+        let kelvin = registry.base_unit(Dimension::Temperature).map(|info| info.unit.clone());
+        assert_eq!(kelvin, Some(Unit::Temperature(Temperature::Kelvin)));
+        assert!(registry.collisions().is_empty());
+    }
+
+    #[test]
+    fn symbols_that_differ_by_case_stay_apart() {
+        let registry = UnitRegistry::builtin();
+        assert_eq!(registry.resolve("mA"), Ok(Unit::ElectricCurrent(ElectricCurrent::Milliampere)));
+        assert_eq!(registry.resolve("MA"), Ok(Unit::ElectricCurrent(ElectricCurrent::Megaampere)));
+        assert_eq!(registry.resolve("Pa"), Ok(Unit::Pressure(Pressure::Pascal)));
+        for name in ["ma", "pa", "PA"] {
+            assert!(matches!(registry.resolve(name), Err(LookupError::Ambiguous { .. })), "{}", name);
+        }
+        // A capital M or P would be mega or peta, not milli or pico.
+        for name in ["Mg", "MG", "PM", "Pm"] {
+            assert!(matches!(registry.resolve(name), Err(LookupError::Unknown { .. })), "{}", name);
+            assert_eq!(registry.lookup(name), None, "{}", name);
+        }
+        assert_eq!(registry.resolve("mg"), Ok(Unit::Mass(Mass::Milligram)));
+        assert_eq!(registry.resolve("pm"), Ok(Unit::Length(Length::Picometer)));
+        assert_eq!(registry.resolve("mpa"), Ok(Unit::Pressure(Pressure::Megapascal)));
+        // Names fold, but common words never turn into units by case.
+        assert_eq!(registry.resolve("Celsius"), Ok(Unit::Temperature(Temperature::Celsius)));
+        for word in ["a", "rd", "de"] {
+            assert!(matches!(registry.resolve(word), Err(LookupError::Unknown { .. })), "{}", word);
+        }
+        assert_eq!(registry.lookup("a"), None);
+    }
+
+    #[test]
+    fn unknown_names_get_suggestions() {
+        let registry = UnitRegistry::builtin();
+        let typo = registry.suggest("kilometre", None);
+        assert_eq!(typo.iter().map(|suggestion| (suggestion.spelling.as_str(), suggestion.distance)).collect::<Vec<_>>(), [("kilometer", 0)]);
+        assert_eq!(registry.suggest("metr", Some(Dimension::Length))[0].spelling, "meter");
+        assert!(registry.suggest("metr", Some(Dimension::Mass)).iter().all(|suggestion| suggestion.unit != Unit::Length(Length::Meter)));
+        let error = registry.resolve("farenheit").unwrap_err();
+        assert_eq!(error.to_string(), "unknown unit \"farenheit\"; did you mean \"fahrenheit\"?");
+        assert_eq!(registry.resolve("zzzz").unwrap_err().to_string(), "unknown unit \"zzzz\"");
+    }
+
+    #[test]
+    fn metadata_names_round_trip() {
+        for system in System::ALL {
+            assert_eq!(System::from_name(system.name()), Some(system));
+        }
+        for exactness in Exactness::ALL {
+            assert_eq!(Exactness::from_name(exactness.name()), Some(exactness));
+        }
+        for reference in Reference::ALL {
+            assert_eq!(Reference::from_name(reference.name()), Some(reference));
+        }
+        assert_eq!(System::from_name("us_customary"), Some(System::UsCustomary));
+        assert_eq!(Reference::from_name("codata"), Some(Reference::Codata2022));
+        assert_eq!(Reference::from_name("si"), Some(Reference::SiBrochure));
+        assert_eq!(Exactness::from_name("approximate"), None);
+    }
+
+    #[test]
+    fn filters_narrow_queries() {
+        let registry = UnitRegistry::builtin();
+        let foot = registry.info(&Unit::Length(Length::Foot)).unwrap();
+        assert_eq!((foot.system, foot.exactness, foot.reference), (System::Imperial, Exactness::Exact, Some(Reference::NistSp811)));
+        let imperial = UnitFilter { system: Some(System::Imperial), ..UnitFilter::default() };
+        assert!(registry.query(imperial).any(|info| info.symbol == "ft"));
+        assert!(registry.query(imperial).all(|info| info.system == System::Imperial));
+        let measured = UnitFilter { dimension: Some(Dimension::Energy), reference: Some(Reference::Codata2022), ..UnitFilter::default() };
+        let symbols: Vec<&str> = registry.query(measured).map(|info| info.symbol.as_str()).collect();
+        assert_eq!(symbols, ["Eh"]);
+        // Only exact factors keep an exact value.
+        assert!(registry.iter().all(|info| info.exact_factor.is_none() || info.exactness == Exactness::Exact));
+        assert_eq!(registry.query(UnitFilter::default()).count(), registry.iter().count());
+    }
+
+    #[test]
+    fn runtime_definitions_and_prefixes() {
+        let mut registry = UnitRegistry::new();
+        registry.define(smoot(), vec!["smoots".to_string()], 3).unwrap();
+        assert!(registry.define(smoot(), vec![], 4).is_err());
+        assert_eq!(registry.defined_on_line("smoots"), Some(3));
+        assert_eq!(registry.resolve("smoots"), Ok(Unit::Custom(smoot())));
+        let info = registry.info(&Unit::Custom(smoot())).unwrap();
+        assert_eq!((info.system, info.exactness, info.exact_factor), (System::UserDefined, Exactness::Rounded, None));
+        registry.define_prefix("kilo", 1000.0);
+        let Ok(Unit::Custom(kilosmoot)) = registry.resolve("kilosmoot") else { panic!("kilosmoot") };
+        assert_eq!(kilosmoot.factor, 1701.8);
+        // Offset scales take no prefix.
+        assert!(registry.resolve("kilocelsius").is_err());
+    }
+}
@@ -0,0 +1,586 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::decimal::{Context, Decimal};
+use crate::enums::*;
+use crate::format::FormatOptions;
+use crate::parse::{exponent_len, number_len, parse_number};
+use crate::registry::{LookupError, UnitRegistry};
+use crate::uncertainty::Uncertain;
+use crate::{Quantity, convert_decimal, get_unit_dimension};
+
+// Evaluation for the interactive prompt. Each line is one of
+//
+//   20 km to mi           conversion ("in" and "->" work too)
+//   ans * 2               arithmetic on the previous result (also `_`)
+//   x = 3 ft              assignment to a variable
+//   :units length         a command
+//
+// Quantities can be added, subtracted and compared (divided) within a dimension,
+// and scaled by plain numbers. Numbers may carry a standard uncertainty
+// ("12.3 ± 0.2 mm", "12.3(2) mm"), which propagates to first order through the
+// arithmetic and conversions. A session with a decimal `Context` computes with
+// arbitrary-precision decimals instead of f64.
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Number(f64),
+    Uncertain(Uncertain), // A plain number with an uncertainty
+    Quantity(Quantity),
+    Decimal(Decimal), // Values of a decimal session
+    DecimalQuantity(Decimal, Unit),
+}
+
+impl Value {
+    pub fn display(&self, registry: &UnitRegistry, options: &FormatOptions) -> String {
+        match self {
+            Value::Number(value) => options.format(*value),
+            Value::Uncertain(value) => options.format_uncertain(value.value, value.uncertainty),
+            Value::Quantity(quantity) => {
+                let options = FormatOptions { space: true, ..*options };
+                options.format_uncertain_quantity(quantity.value, quantity.uncertainty, &registry.symbol_of(&quantity.unit))
+            }
+            Value::Decimal(value) => value.to_string(),
+            Value::DecimalQuantity(value, unit) => format!("{} {}", value, registry.symbol_of(unit)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ReplError {
+    Syntax(String),
+    Unit(LookupError),
+    UnknownName(String),
+    UnknownCommand(String),
+    Arithmetic(String),
+    Conversion { from: Dimension, to: Dimension },
+}
+
+impl fmt::Display for ReplError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplError::Syntax(message) => write!(f, "syntax error: {}", message),
+            ReplError::Unit(err) => write!(f, "{}", err),
+            ReplError::UnknownName(name) => write!(f, "{:?} is neither a variable nor a unit", name),
+            ReplError::UnknownCommand(command) => write!(f, "unknown command {:?} (try :help)", command),
+            ReplError::Arithmetic(message) => write!(f, "{}", message),
+            ReplError::Conversion { from, to } => write!(f, "cannot convert {} to {}", from.name(), to.name()),
+        }
+    }
+}
+
+impl std::error::Error for ReplError {}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Response {
+    Value(Value),
+    Text(String),
+    Quit,
+    Nothing, // Blank line
+}
+
+pub const HELP: &str = "\
+Enter a quantity, a conversion or an expression:
+  20 km to mi        convert (also: 20 km in mi, 20 km -> mi)
+  3 ft + 4 in        add or subtract quantities of the same dimension
+  12.3 ± 0.2 mm      a quantity with its uncertainty (also +/-, or 12.3(2) mm)
+  ans * 2            the previous result, also available as _
+  x = 3 ft           store a value in a variable
+  10 km / 2 km       ratio of two quantities
+Commands:
+  :units <dimension> list the units of a dimension, e.g. :units length
+  :help              show this help
+  :quit              leave (Ctrl-D works too)";
+
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+    Number(String), // As written, so decimal sessions lose no digits
+    Name(String),
+    Op(char),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ReplError> {
+    let mut tokens = vec![];
+    let mut rest = input;
+    loop {
+        rest = rest.trim_start();
+        let Some(c) = rest.chars().next() else { break };
+        if c.is_ascii_digit() || (c == '.' && rest[1..].starts_with(|c: char| c.is_ascii_digit())) {
+            let len = literal_len(rest);
+            tokens.push(Token::Number(rest[..len].to_string()));
+            rest = &rest[len..];
+        } else if "+-*/()".contains(c) {
+            tokens.push(Token::Op(c));
+            rest = &rest[c.len_utf8()..];
+        } else {
+            let len = rest.find(|c: char| c.is_whitespace() || "+-*/()".contains(c)).unwrap_or(rest.len());
+            tokens.push(Token::Name(rest[..len].to_string()));
+            rest = &rest[len..];
+        }
+    }
+    Ok(tokens)
+}
+
+// Length of a number literal with its uncertainty, if any: "12.3", "12.3(2)",
+// "12.3 ± 0.2". "+-" is not accepted here, as "3 +-2" reads as 3 + -2.
+fn literal_len(input: &str) -> usize {
+    let mut len = number_len(input);
+    if let Some(inner) = input[len..].strip_prefix('(')
+        && let Some(close) = inner.find(')')
+        && close > 0
+        && inner[..close].chars().all(|c| c.is_ascii_digit() || c == '.')
+    {
+        len += close + 2;
+        return len + exponent_len(&input[len..]);
+    }
+    let after = input[len..].trim_start();
+    for sign in ["±", "+/-"] {
+        if let Some(uncertainty) = after.strip_prefix(sign) {
+            let uncertainty = uncertainty.trim_start();
+            return input.len() - uncertainty.len() + number_len(uncertainty);
+        }
+    }
+    len
+}
+
+// The ways "20 km to mi" splits into an expression and a target, rightmost first
+// for each keyword.
+fn split_targets(line: &str) -> impl Iterator<Item = (&str, &str)> {
+    [" to ", " in ", "->"].into_iter().flat_map(move |keyword| {
+        line.rmatch_indices(keyword).map(move |(index, _)| (&line[..index], line[index + keyword.len()..].trim()))
+    })
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+pub struct Session<'a> {
+    registry: &'a UnitRegistry,
+    variables: HashMap<String, Value>,
+    ans: Option<Value>,
+    context: Option<Context>, // Set for decimal arithmetic
+}
+
+impl Session<'_> {
+    pub fn new(registry: &UnitRegistry) -> Session<'_> {
+        Session { registry, variables: HashMap::new(), ans: None, context: None }
+    }
+
+    // A session computing with arbitrary-precision decimals.
+    pub fn decimal(registry: &UnitRegistry, context: Context) -> Session<'_> {
+        Session { context: Some(context), ..Session::new(registry) }
+    }
+
+    pub fn eval(&mut self, line: &str) -> Result<Response, ReplError> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(Response::Nothing);
+        }
+        if let Some(command) = line.strip_prefix(':') {
+            return self.command(command);
+        }
+        let (name, line) = match line.split_once('=') {
+            Some((name, rest)) if is_variable_name(name.trim()) => (Some(name.trim()), rest.trim()),
+            _ => (None, line),
+        };
+        let (mut value, target) = self.conversion(line)?;
+        if let Some(to) = target {
+            value = match value {
+                Value::Quantity(quantity) => Value::Quantity(self.convert(&quantity, &to)?),
+                Value::DecimalQuantity(value, unit) => Value::DecimalQuantity(self.convert_decimal(&value, &unit, &to)?, to),
+                Value::Number(_) | Value::Uncertain(_) | Value::Decimal(_) => {
+                    return Err(ReplError::Arithmetic(format!("a plain number has no unit to convert to {}", self.registry.symbol_of(&to))));
+                }
+            };
+        }
+        if let Some(name) = name {
+            self.variables.insert(name.to_string(), value.clone());
+        }
+        self.ans = Some(value.clone());
+        Ok(Response::Value(value))
+    }
+
+    // A conversion when the text after to/in/-> names a unit and the text before
+    // it evaluates; otherwise the keyword belongs to the expression, as the inch
+    // in "3 in + 2 ft" does.
+    fn conversion(&self, line: &str) -> Result<(Value, Option<Unit>), ReplError> {
+        for (expression, target) in split_targets(line) {
+            if target.is_empty() {
+                continue;
+            }
+            if let Ok(value) = self.evaluate(expression) {
+                let dimension = match &value {
+                    Value::Quantity(Quantity { unit, .. }) | Value::DecimalQuantity(_, unit) => Some(get_unit_dimension(unit)),
+                    Value::Number(_) | Value::Uncertain(_) | Value::Decimal(_) => None,
+                };
+                if let Ok(to) = self.registry.resolve_in(target, dimension) {
+                    return Ok((value, Some(to)));
+                }
+            }
+        }
+        Ok((self.evaluate(line)?, None))
+    }
+
+    fn command(&self, command: &str) -> Result<Response, ReplError> {
+        let (name, argument) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+        match name {
+            "help" | "h" | "?" => Ok(Response::Text(HELP.to_string())),
+            "quit" | "q" | "exit" => Ok(Response::Quit),
+            "units" => {
+                let dimension = Dimension::from_name(argument.trim())
+                    .ok_or_else(|| ReplError::Syntax(format!("unknown dimension {:?}", argument.trim())))?;
+                let lines: Vec<String> = self
+                    .registry
+                    .units_of(dimension)
+                    .map(|info| format!("{:<12} {}", info.symbol, info.name))
+                    .collect();
+                Ok(Response::Text(lines.join("\n")))
+            }
+            _ => Err(ReplError::UnknownCommand(name.to_string())),
+        }
+    }
+
+    fn convert(&self, quantity: &Quantity, to: &Unit) -> Result<Quantity, ReplError> {
+        let (from_dimension, to_dimension) = (get_unit_dimension(&quantity.unit), get_unit_dimension(to));
+        if from_dimension != to_dimension {
+            return Err(ReplError::Conversion { from: from_dimension, to: to_dimension });
+        }
+        quantity.convert_to(to).ok_or(ReplError::Conversion { from: from_dimension, to: to_dimension })
+    }
+
+    fn convert_decimal(&self, value: &Decimal, from: &Unit, to: &Unit) -> Result<Decimal, ReplError> {
+        let (from_dimension, to_dimension) = (get_unit_dimension(from), get_unit_dimension(to));
+        if from_dimension != to_dimension {
+            return Err(ReplError::Conversion { from: from_dimension, to: to_dimension });
+        }
+        let context = self.context.unwrap_or_default();
+        convert_decimal(value, from, to, &context)
+            .ok_or_else(|| ReplError::Arithmetic(format!("{} has no exact definition to compute with", self.registry.name_of(from))))
+    }
+
+    // A number literal, or the quantity `value` of `unit`.
+    fn number(&self, literal: &str, unit: Option<Unit>) -> Result<Value, ReplError> {
+        let invalid = || ReplError::Syntax(format!("invalid number {:?}", literal));
+        let (value, uncertainty) = parse_number(literal).ok_or_else(invalid)?;
+        if self.context.is_some() && uncertainty.is_some() {
+            return Err(ReplError::Arithmetic("decimal arithmetic does not support uncertainties".to_string()));
+        }
+        let value = Uncertain::new(value, uncertainty.unwrap_or(0.0));
+        Ok(match (self.context, unit) {
+            (None, None) => plain(value),
+            (None, Some(unit)) => quantity(value, unit),
+            (Some(_), None) => Value::Decimal(Decimal::parse(literal).ok_or_else(invalid)?),
+            (Some(_), Some(unit)) => Value::DecimalQuantity(Decimal::parse(literal).ok_or_else(invalid)?, unit),
+        })
+    }
+
+    fn evaluate(&self, expression: &str) -> Result<Value, ReplError> {
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser { session: self, tokens, position: 0 };
+        let value = parser.expression()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(value),
+            Some(token) => Err(ReplError::Syntax(format!("unexpected {:?}", token))),
+        }
+    }
+
+    fn variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "ans" | "_" => self.ans.clone(),
+            _ => self.variables.get(name).cloned(),
+        }
+    }
+
+    // Combines two values with + - * /.
+    fn apply(&self, op: char, left: Value, right: Value) -> Result<Value, ReplError> {
+        use Value::{Decimal as D, DecimalQuantity as DQ};
+        let mismatch = || ReplError::Arithmetic(format!("cannot apply '{}' to these operands", op));
+        let multiplying = || ReplError::Arithmetic("multiplying two quantities is not supported".to_string());
+        if let (Some((a, a_unit)), Some((b, b_unit))) = (float(&left), float(&right)) {
+            return match (op, a_unit, b_unit) {
+                (op, None, None) => Ok(plain(arithmetic(op, a, b))),
+                ('*', Some(unit), None) | ('*', None, Some(unit)) => Ok(quantity(a * b, unit.clone())),
+                ('/', Some(unit), None) => Ok(quantity(a / b, unit.clone())),
+                ('*', Some(_), Some(_)) => Err(multiplying()),
+                ('+' | '-' | '/', Some(unit), Some(b_unit)) => {
+                    let b = self.convert(&Quantity { value: b.value, unit: b_unit.clone(), uncertainty: Some(b.uncertainty) }, unit)?;
+                    let b = Uncertain::new(b.value, b.uncertainty.unwrap_or(0.0));
+                    match op {
+                        '/' => Ok(plain(a / b)),
+                        _ => Ok(quantity(arithmetic(op, a, b), unit.clone())),
+                    }
+                }
+                _ => Err(mismatch()),
+            };
+        }
+        Ok(match (op, left, right) {
+            ('*', DQ(..), DQ(..)) => return Err(multiplying()),
+            (op, D(a), D(b)) => D(self.decimal_op(op, &a, &b)?),
+            ('*', DQ(q, unit), D(n)) | ('*', D(n), DQ(q, unit)) => DQ(self.decimal_op('*', &q, &n)?, unit),
+            ('/', DQ(q, unit), D(n)) => DQ(self.decimal_op('/', &q, &n)?, unit),
+            ('+' | '-' | '/', DQ(a, unit), DQ(b, b_unit)) => {
+                let b = self.convert_decimal(&b, &b_unit, &unit)?;
+                match op {
+                    '/' => D(self.decimal_op(op, &a, &b)?),
+                    _ => DQ(self.decimal_op(op, &a, &b)?, unit),
+                }
+            }
+            _ => return Err(mismatch()),
+        })
+    }
+
+    fn decimal_op(&self, op: char, a: &Decimal, b: &Decimal) -> Result<Decimal, ReplError> {
+        let context = self.context.unwrap_or_default();
+        Ok(match op {
+            '+' => a.add(b, &context),
+            '-' => a.sub(b, &context),
+            '*' => a.mul(b, &context),
+            _ => a.div(b, &context).ok_or_else(|| ReplError::Arithmetic("division by zero".to_string()))?,
+        })
+    }
+}
+
+// A float value as a number with its uncertainty, and its unit if it has one.
+fn float(value: &Value) -> Option<(Uncertain, Option<&Unit>)> {
+    match value {
+        Value::Number(value) => Some((Uncertain::exact(*value), None)),
+        Value::Uncertain(value) => Some((*value, None)),
+        Value::Quantity(quantity) => Some((Uncertain::new(quantity.value, quantity.uncertainty.unwrap_or(0.0)), Some(&quantity.unit))),
+        Value::Decimal(_) | Value::DecimalQuantity(..) => None,
+    }
+}
+
+// Numbers and quantities without an uncertainty stay exact.
+fn plain(value: Uncertain) -> Value {
+    if value.is_exact() { Value::Number(value.value) } else { Value::Uncertain(value) }
+}
+
+fn quantity(value: Uncertain, unit: Unit) -> Value {
+    let quantity = Quantity::new(value.value, unit);
+    Value::Quantity(if value.is_exact() { quantity } else { quantity.with_uncertainty(value.uncertainty) })
+}
+
+fn arithmetic(op: char, a: Uncertain, b: Uncertain) -> Uncertain {
+    match op {
+        '+' => a + b,
+        '-' => a - b,
+        '*' => a * b,
+        _ => a / b,
+    }
+}
+
+struct Parser<'s, 'r> {
+    session: &'s Session<'r>,
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser<'_, '_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    // expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<Value, ReplError> {
+        let mut value = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek().cloned() {
+            self.position += 1;
+            let right = self.term()?;
+            value = self.session.apply(op, value, right)?;
+        }
+        Ok(value)
+    }
+
+    // term := factor (('*' | '/') factor)*
+    fn term(&mut self) -> Result<Value, ReplError> {
+        let mut value = self.factor()?;
+        while let Some(Token::Op(op @ ('*' | '/'))) = self.peek().cloned() {
+            self.position += 1;
+            let right = self.factor()?;
+            value = self.session.apply(op, value, right)?;
+        }
+        Ok(value)
+    }
+
+    // factor := number [unit] | name | '(' expression ')' | '-' factor
+    fn factor(&mut self) -> Result<Value, ReplError> {
+        match self.next() {
+            Some(Token::Number(literal)) => match self.peek() {
+                Some(Token::Name(_)) => {
+                    let unit = self.unit()?;
+                    self.session.number(&literal, Some(unit))
+                }
+                _ => self.session.number(&literal, None),
+            },
+            Some(Token::Name(name)) => {
+                if let Some(value) = self.session.variable(&name) {
+                    return Ok(value);
+                }
+                self.position -= 1;
+                let unit = self.unit().map_err(|_| ReplError::UnknownName(name))?;
+                self.session.number("1", Some(unit))
+            }
+            Some(Token::Op('(')) => {
+                let value = self.expression()?;
+                if self.next() != Some(Token::Op(')')) {
+                    return Err(ReplError::Syntax("missing ')'".to_string()));
+                }
+                // A unit after the parentheses applies to a plain number: "(12.3 ± 0.2) mm".
+                if !matches!(self.peek(), Some(Token::Name(_))) {
+                    return Ok(value);
+                }
+                match value {
+                    Value::Number(value) => Ok(quantity(Uncertain::exact(value), self.unit()?)),
+                    Value::Uncertain(value) => Ok(quantity(value, self.unit()?)),
+                    Value::Decimal(value) => Ok(Value::DecimalQuantity(value, self.unit()?)),
+                    value => Ok(value),
+                }
+            }
+            Some(Token::Op('-')) => match self.factor()? {
+                Value::Number(value) => Ok(Value::Number(-value)),
+                Value::Uncertain(value) => Ok(Value::Uncertain(-value)),
+                Value::Quantity(quantity) => Ok(Value::Quantity(Quantity { value: -quantity.value, ..quantity })),
+                Value::Decimal(value) => Ok(Value::Decimal(value.neg())),
+                Value::DecimalQuantity(value, unit) => Ok(Value::DecimalQuantity(value.neg(), unit)),
+            },
+            Some(token) => Err(ReplError::Syntax(format!("unexpected {:?}", token))),
+            None => Err(ReplError::Syntax("unexpected end of input".to_string())),
+        }
+    }
+
+    // A unit name, joined with following "/name" parts while that still names a
+    // unit ("km/h", "m/s").
+    fn unit(&mut self) -> Result<Unit, ReplError> {
+        let Some(Token::Name(mut name)) = self.next() else {
+            return Err(ReplError::Syntax("expected a unit".to_string()));
+        };
+        while let (Some(Token::Op('/')), Some(Token::Name(next))) = (self.tokens.get(self.position), self.tokens.get(self.position + 1)) {
+            let joined = format!("{}/{}", name, next);
+            if self.session.registry.resolve(&joined).is_err() {
+                break;
+            }
+            name = joined;
+            self.position += 2;
+        }
+        self.session.registry.resolve(&name).map_err(ReplError::Unit)
+    }
+}
+
+// $XDG_DATA_HOME/un/history, falling back to ~/.local/share/un/history.
+pub fn default_history_path() -> Option<PathBuf> {
+    let data = match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".local").join("share"),
+    };
+    Some(data.join("un").join("history"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decimal::Rounding;
+
+    fn value(session: &mut Session, line: &str) -> Value {
+        match session.eval(line) {
+            Ok(Response::Value(value)) => value,
+            other => panic!("{:?} gave {:?}", line, other),
+        }
+    }
+
+    fn quantity_of(session: &mut Session, line: &str) -> (f64, Unit) {
+        match value(session, line) {
+            Value::Quantity(quantity) => (quantity.value, quantity.unit),
+            other => panic!("{:?} gave {:?}", line, other),
+        }
+    }
+
+    fn assert_quantity(session: &mut Session, line: &str, expected: f64, unit: Unit) {
+        let (actual, actual_unit) = quantity_of(session, line);
+        assert_eq!(actual_unit, unit, "{:?}", line);
+        assert!((actual - expected).abs() <= 1e-12 * expected.abs().max(1.0), "{:?} gave {}", line, actual);
+    }
+
+    const INCH: Unit = Unit::Length(Length::Inch);
+    const FOOT: Unit = Unit::Length(Length::Foot);
+    const CENTIMETER: Unit = Unit::Length(Length::Centimeter);
+
+    #[test]
+    fn converts_with_each_keyword() {
+        let mut session = Session::new(UnitRegistry::builtin());
+        assert_quantity(&mut session, "20 km to mi", 12.427423844746679, Unit::Length(Length::Mile));
+        assert_quantity(&mut session, "1 ft -> in", 12.0, INCH);
+        assert_quantity(&mut session, "3 in in cm", 7.62, CENTIMETER);
+    }
+
+    #[test]
+    fn in_is_the_inch_unless_a_target_follows() {
+        let mut session = Session::new(UnitRegistry::builtin());
+        assert_quantity(&mut session, "3 in + 2 ft", 27.0, INCH);
+        assert_quantity(&mut session, "2 ft + 3 in to in", 27.0, INCH);
+        assert_quantity(&mut session, "1 ft - 3 in", 0.75, FOOT);
+    }
+
+    #[test]
+    fn ans_and_underscore_hold_the_previous_result() {
+        let mut session = Session::new(UnitRegistry::builtin());
+        assert_quantity(&mut session, "3 ft", 3.0, FOOT);
+        assert_quantity(&mut session, "ans * 2", 6.0, FOOT);
+        assert_quantity(&mut session, "_ + 1 ft", 7.0, FOOT);
+        assert_quantity(&mut session, "_ to in", 84.0, INCH);
+    }
+
+    #[test]
+    fn variables_keep_their_values() {
+        let mut session = Session::new(UnitRegistry::builtin());
+        assert_quantity(&mut session, "x = 3 ft", 3.0, FOOT);
+        assert_quantity(&mut session, "1 in", 1.0, INCH);
+        assert_quantity(&mut session, "x in in", 36.0, INCH);
+        assert_eq!(value(&mut session, "x / 1 in"), Value::Number(36.0));
+        assert_eq!(session.eval("y + 1"), Err(ReplError::UnknownName("y".to_string())));
+    }
+
+    #[test]
+    fn reports_bad_conversions() {
+        let mut session = Session::new(UnitRegistry::builtin());
+        let mismatch = ReplError::Conversion { from: Dimension::Length, to: Dimension::Mass };
+        assert_eq!(session.eval("20 km to kg"), Err(mismatch));
+        assert!(matches!(session.eval("3 ft * 2 ft"), Err(ReplError::Arithmetic(_))));
+        assert!(matches!(session.eval("5 to mi"), Err(ReplError::Arithmetic(_))));
+    }
+
+    #[test]
+    fn uncertainties_propagate() {
+        let mut session = Session::new(UnitRegistry::builtin());
+        let Value::Quantity(quantity) = value(&mut session, "12.3 ± 0.2 mm to cm") else { panic!() };
+        assert!((quantity.value - 1.23).abs() < 1e-15);
+        assert!((quantity.uncertainty.unwrap() - 0.02).abs() < 1e-15);
+        assert_eq!(value(&mut session, "12.3(2) mm"), value(&mut session, "12.3 +/- 0.2 mm"));
+    }
+
+    #[test]
+    fn decimal_sessions_keep_every_digit() {
+        let mut session = Session::decimal(UnitRegistry::builtin(), Context::new(30, Rounding::HalfEven));
+        let expected = Value::DecimalQuantity(Decimal::parse("0.3").unwrap(), Unit::Length(Length::Meter));
+        assert_eq!(value(&mut session, "0.1 m + 0.2 m"), expected);
+        let expected = Value::DecimalQuantity(Decimal::parse("1609.344").unwrap(), Unit::Length(Length::Meter));
+        assert_eq!(value(&mut session, "1 mi to m"), expected);
+    }
+
+    #[test]
+    fn commands_and_blank_lines() {
+        let mut session = Session::new(UnitRegistry::builtin());
+        assert_eq!(session.eval("   "), Ok(Response::Nothing));
+        assert_eq!(session.eval(":quit"), Ok(Response::Quit));
+        assert!(matches!(session.eval(":units length"), Ok(Response::Text(text)) if text.contains("mile")));
+        assert_eq!(session.eval(":frobnicate"), Err(ReplError::UnknownCommand("frobnicate".to_string())));
+    }
+}
@@ -0,0 +1,15 @@
+// The hash behind the generated spelling tables. It is `include!`d by build.rs,
+// which builds the tables, and by `spellings`, which reads them, so both sides hash
+// alike. It works on chars rather than bytes so that a lowercased spelling can be
+// hashed on the fly, without building the lowercase string.
+
+// FNV-1a over the chars' code points, starting from a state derived from `seed`.
+fn spelling_hash(chars: impl Iterator<Item = char>, seed: u32) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64 ^ u64::from(seed).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    for c in chars {
+        hash ^= u64::from(u32::from(c));
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    // FNV's low bits mix poorly; fold the high half in.
+    hash ^ (hash >> 32)
+}
@@ -0,0 +1,104 @@
+// Allocation-free lookup of the built-in units' spellings, in perfect-hash tables
+// that build.rs generates from src/builtin.rs. A hit gives the unit's row in that
+// table, which is its index in the built-in registry. Lowercased lookups fold the
+// query one char at a time instead of building a lowercase copy.
+
+include!("spelling_hash.rs");
+include!(concat!(env!("OUT_DIR"), "/spellings.rs"));
+
+const AMBIGUOUS: u16 = u16::MAX;
+
+pub(crate) struct Table {
+    displacements: &'static [u32],
+    keys: &'static [&'static str], // Empty for unused slots
+    values: &'static [u16],
+}
+
+impl Table {
+    // `chars` yields the spelling to look up, as often as needed.
+    fn get<I: Iterator<Item = char>>(&self, chars: impl Fn() -> I) -> Option<u16> {
+        let bucket = spelling_hash(chars(), 0) % self.displacements.len() as u64;
+        let seed = self.displacements[bucket as usize];
+        let slot = (spelling_hash(chars(), seed) % self.keys.len() as u64) as usize;
+        let key = self.keys[slot];
+        (!key.is_empty() && key.chars().eq(chars())).then_some(self.values[slot])
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum Spelled {
+    Unit(usize),
+    Ambiguous, // The registry's slower path lists the candidates
+    Unknown,
+}
+
+// The same answer as `UnitRegistry::resolve` without prefixes, for the built-in
+// units: an exact spelling, else a case-insensitive one that names a single unit.
+pub(crate) fn lookup(name: &str) -> Spelled {
+    if name.is_empty() {
+        return Spelled::Unknown;
+    }
+    let folded = || name.chars().flat_map(char::to_lowercase);
+    let value = EXACT.get(|| name.chars()).or_else(|| {
+        // A symbol's prefix letter keeps its case: "Mg" is not "mg".
+        let value = FOLDED.get(folded)?;
+        let cased = CASED.get(folded).is_some_and(|letter| name.chars().next().map(u32::from) != Some(u32::from(letter)));
+        (value == AMBIGUOUS || !cased).then_some(value)
+    });
+    match value {
+        Some(AMBIGUOUS) => Spelled::Ambiguous,
+        Some(index) => Spelled::Unit(usize::from(index)),
+        None => Spelled::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{CustomUnit, Dimension, Length, Unit};
+    use crate::registry::{LookupError, UnitRegistry};
+
+    // The built-in units with one runtime unit added, which answers from the maps.
+    fn maps() -> UnitRegistry {
+        let mut registry = UnitRegistry::new();
+        let custom = CustomUnit { name: "smoot".to_string(), dimension: Dimension::Length, factor: 1.7018, offset: 0.0 };
+        registry.define(custom, vec![], 1).unwrap();
+        registry
+    }
+
+    fn agrees(registry: &UnitRegistry, name: &str) -> bool {
+        let builtin = UnitRegistry::builtin();
+        match (lookup(name), registry.resolve(name)) {
+            (Spelled::Unit(index), Ok(unit)) => builtin.iter().nth(index).is_some_and(|info| info.unit == unit),
+            (Spelled::Ambiguous, Err(LookupError::Ambiguous { .. })) => true,
+            (Spelled::Unknown, Err(LookupError::Unknown { .. })) => true,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn tables_agree_with_the_registry() {
+        let registry = maps();
+        for info in UnitRegistry::builtin().iter() {
+            for spelling in info.spellings() {
+                for name in [spelling.to_string(), spelling.to_lowercase(), spelling.to_uppercase()] {
+                    assert!(agrees(&registry, &name), "{:?}", name);
+                }
+            }
+        }
+        for name in ["", "zzz", "smoot", "Meters", "MA", "ma", "pA", "PA", "a", "Mg", "PM", "mpa"] {
+            assert_eq!(agrees(&registry, name), name != "smoot", "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn looks_up_exact_then_folded_spellings() {
+        let meter = UnitRegistry::builtin().iter().position(|info| info.unit == Unit::Length(Length::Meter));
+        assert_eq!(lookup("m"), Spelled::Unit(meter.unwrap()));
+        assert_eq!(lookup("METERS"), Spelled::Unit(meter.unwrap()));
+        assert_eq!(lookup("pa"), Spelled::Ambiguous);
+        assert_eq!(lookup("a"), Spelled::Unknown);
+        assert_eq!(lookup(""), Spelled::Unknown);
+        assert_eq!(BUILTIN_ROWS, UnitRegistry::builtin().iter().count());
+    }
+}
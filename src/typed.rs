@@ -0,0 +1,386 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::enums::{self, Unit};
+use crate::{Quantity, get_conversion_factor_to_base, get_offset_to_base, get_unit_dimension};
+
+// Quantities whose dimension is part of their type: adding a `Length` to a `Mass`,
+// or passing a `Time` where a `Force` is expected, doesn't compile. The value is
+// kept in the dimension's SI base unit, so arithmetic is plain arithmetic on N and
+// the units are only looked at in `new` and `get`. Since every built-in base unit is
+// coherent (1 J = 1 N·m), products and quotients of base values are base values too.
+//
+//   let distance = Length::new(100.0, enums::Length::Mile);
+//   let time = Time::new(2.0, enums::Time::Hour);
+//   let speed: Speed = distance / time;
+//   speed.get(enums::Speed::MilePerHour) // 50
+//
+// Temperatures are absolute, so two of them don't add: 20 °C + 10 °C isn't 30 °C.
+// Their difference is a `TemperatureDifference`, which scales with each scale's
+// degree but ignores its zero, and can be added to a temperature.
+
+// Number types for typed quantities.
+pub trait Scalar:
+    Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self>
+{
+    fn from_f64(value: f64) -> Self;
+}
+
+impl Scalar for f64 {
+    fn from_f64(value: f64) -> f64 {
+        value
+    }
+}
+
+impl Scalar for f32 {
+    fn from_f64(value: f64) -> f32 {
+        value as f32
+    }
+}
+
+// A dimension at the type level, with the enum of units that measure it.
+pub trait TypedDimension {
+    type Unit;
+    const DIMENSION: enums::Dimension;
+
+    // Differences measure by a unit's factor alone, without its offset.
+    const DIFFERENCE: bool = false;
+
+    fn unit(unit: Self::Unit) -> Unit;
+}
+
+// Dimensions whose quantities add and scale, which is all but absolute temperature.
+pub trait Linear {}
+
+pub struct Measure<D, N = f64> {
+    base: N, // In the dimension's base unit
+    dimension: PhantomData<D>,
+}
+
+// Marker types, one per dimension, and an alias for each: `Length<f64>` is a
+// `Measure<dim::Length, f64>`.
+macro_rules! dimensions {
+    ($($name:ident),* $(,)?) => {
+        pub mod dim {
+            $(
+                #[derive(Debug, PartialEq, Clone, Copy)]
+                pub struct $name;
+
+                impl super::TypedDimension for $name {
+                    type Unit = crate::enums::$name;
+                    const DIMENSION: crate::enums::Dimension = crate::enums::Dimension::$name;
+
+                    fn unit(unit: crate::enums::$name) -> crate::enums::Unit {
+                        crate::enums::Unit::$name(unit)
+                    }
+                }
+            )*
+
+            #[derive(Debug, PartialEq, Clone, Copy)]
+            pub struct TemperatureDifference;
+
+            impl super::TypedDimension for TemperatureDifference {
+                type Unit = crate::enums::Temperature;
+                const DIMENSION: crate::enums::Dimension = crate::enums::Dimension::Temperature;
+                const DIFFERENCE: bool = true;
+
+                fn unit(unit: crate::enums::Temperature) -> crate::enums::Unit {
+                    crate::enums::Unit::Temperature(unit)
+                }
+            }
+        }
+
+        $(pub type $name<N = f64> = Measure<dim::$name, N>;)*
+        pub type TemperatureDifference<N = f64> = Measure<dim::TemperatureDifference, N>;
+    };
+}
+
+dimensions!(
+    Length, Mass, Area, Volume, Force, Energy, Temperature, Time, Speed, Pressure, Frequency, ElectricCurrent, Voltage,
+    Capacitance, Luminosity, Radiation, MagneticFlux,
+);
+
+macro_rules! linear {
+    ($($name:ident),* $(,)?) => {
+        $(impl Linear for dim::$name {})*
+    };
+}
+
+linear!(
+    Length, Mass, Area, Volume, Force, Energy, Time, Speed, Pressure, Frequency, ElectricCurrent, Voltage, Capacitance,
+    Luminosity, Radiation, MagneticFlux, TemperatureDifference,
+);
+
+// The unit's offset to base, or none for a difference.
+fn offset<D: TypedDimension>(unit: &Unit) -> f64 {
+    if D::DIFFERENCE { 0.0 } else { get_offset_to_base(unit) }
+}
+
+impl<D: TypedDimension, N: Scalar> Measure<D, N> {
+    pub fn new(value: N, unit: D::Unit) -> Measure<D, N> {
+        let unit = D::unit(unit);
+        let factor = N::from_f64(get_conversion_factor_to_base(&unit));
+        Measure::from_base(value * factor + N::from_f64(offset::<D>(&unit)))
+    }
+
+    pub fn from_base(base: N) -> Measure<D, N> {
+        Measure { base, dimension: PhantomData }
+    }
+
+    pub fn base(&self) -> N {
+        self.base
+    }
+
+    // The value in any unit of this dimension, which can't fail.
+    pub fn get(&self, unit: D::Unit) -> N {
+        let unit = D::unit(unit);
+        let factor = N::from_f64(get_conversion_factor_to_base(&unit));
+        (self.base - N::from_f64(offset::<D>(&unit))) / factor
+    }
+}
+
+// Bridges to the untyped API.
+impl<D: TypedDimension> Measure<D, f64> {
+    // None if the quantity has a different dimension.
+    pub fn from_quantity(quantity: &Quantity) -> Option<Measure<D, f64>> {
+        if get_unit_dimension(&quantity.unit) != D::DIMENSION {
+            return None;
+        }
+        let (factor, offset) = (get_conversion_factor_to_base(&quantity.unit), offset::<D>(&quantity.unit));
+        Some(Measure::from_base(quantity.value * factor + offset))
+    }
+
+    pub fn to_quantity(&self, unit: D::Unit) -> Quantity {
+        let unit = D::unit(unit);
+        let value = (self.base - offset::<D>(&unit)) / get_conversion_factor_to_base(&unit);
+        Quantity::new(value, unit)
+    }
+}
+
+// Implemented by hand, as deriving would require the markers to implement them.
+impl<D, N: Copy> Clone for Measure<D, N> {
+    fn clone(&self) -> Measure<D, N> {
+        *self
+    }
+}
+
+impl<D, N: Copy> Copy for Measure<D, N> {}
+
+impl<D, N: PartialEq> PartialEq for Measure<D, N> {
+    fn eq(&self, other: &Measure<D, N>) -> bool {
+        self.base == other.base
+    }
+}
+
+impl<D, N: PartialOrd> PartialOrd for Measure<D, N> {
+    fn partial_cmp(&self, other: &Measure<D, N>) -> Option<std::cmp::Ordering> {
+        self.base.partial_cmp(&other.base)
+    }
+}
+
+impl<D: TypedDimension, N: fmt::Debug> fmt::Debug for Measure<D, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} ({} base units)", self.base, D::DIMENSION.name())
+    }
+}
+
+impl<D: Linear, N: Scalar> Add for Measure<D, N> {
+    type Output = Measure<D, N>;
+
+    fn add(self, other: Measure<D, N>) -> Measure<D, N> {
+        Measure { base: self.base + other.base, dimension: PhantomData }
+    }
+}
+
+impl<D: Linear, N: Scalar> Sub for Measure<D, N> {
+    type Output = Measure<D, N>;
+
+    fn sub(self, other: Measure<D, N>) -> Measure<D, N> {
+        Measure { base: self.base - other.base, dimension: PhantomData }
+    }
+}
+
+impl<D: Linear, N: Scalar> Neg for Measure<D, N> {
+    type Output = Measure<D, N>;
+
+    fn neg(self) -> Measure<D, N> {
+        Measure { base: -self.base, dimension: PhantomData }
+    }
+}
+
+impl<N: Scalar> Sub for Measure<dim::Temperature, N> {
+    type Output = Measure<dim::TemperatureDifference, N>;
+
+    fn sub(self, other: Measure<dim::Temperature, N>) -> Measure<dim::TemperatureDifference, N> {
+        Measure::from_base(self.base - other.base)
+    }
+}
+
+impl<N: Scalar> Add<Measure<dim::TemperatureDifference, N>> for Measure<dim::Temperature, N> {
+    type Output = Measure<dim::Temperature, N>;
+
+    fn add(self, other: Measure<dim::TemperatureDifference, N>) -> Measure<dim::Temperature, N> {
+        Measure::from_base(self.base + other.base)
+    }
+}
+
+impl<N: Scalar> Sub<Measure<dim::TemperatureDifference, N>> for Measure<dim::Temperature, N> {
+    type Output = Measure<dim::Temperature, N>;
+
+    fn sub(self, other: Measure<dim::TemperatureDifference, N>) -> Measure<dim::Temperature, N> {
+        Measure::from_base(self.base - other.base)
+    }
+}
+
+// Scaling by a plain number; doubling 10 °C has no meaning.
+impl<D: Linear, N: Scalar> Mul<N> for Measure<D, N> {
+    type Output = Measure<D, N>;
+
+    fn mul(self, factor: N) -> Measure<D, N> {
+        Measure { base: self.base * factor, dimension: PhantomData }
+    }
+}
+
+impl<D: Linear, N: Scalar> Div<N> for Measure<D, N> {
+    type Output = Measure<D, N>;
+
+    fn div(self, divisor: N) -> Measure<D, N> {
+        Measure { base: self.base / divisor, dimension: PhantomData }
+    }
+}
+
+macro_rules! scalar_times_measure {
+    ($($scalar:ty),*) => {
+        $(
+            impl<D: Linear> Mul<Measure<D, $scalar>> for $scalar {
+                type Output = Measure<D, $scalar>;
+
+                fn mul(self, measure: Measure<D, $scalar>) -> Measure<D, $scalar> {
+                    measure * self
+                }
+            }
+        )*
+    };
+}
+
+scalar_times_measure!(f32, f64);
+
+// The ratio of two quantities of one dimension is a plain number.
+impl<D, N: Scalar> Div for Measure<D, N> {
+    type Output = N;
+
+    fn div(self, other: Measure<D, N>) -> N {
+        self.base / other.base
+    }
+}
+
+// Products and quotients across dimensions.
+macro_rules! relations {
+    ($($a:ident $op:tt $b:ident = $c:ident;)*) => {
+        $(relations!(@one $a $op $b = $c);)*
+    };
+    (@one $a:ident * $b:ident = $c:ident) => {
+        impl<N: Scalar> Mul<Measure<dim::$b, N>> for Measure<dim::$a, N> {
+            type Output = Measure<dim::$c, N>;
+
+            fn mul(self, other: Measure<dim::$b, N>) -> Measure<dim::$c, N> {
+                Measure::from_base(self.base * other.base)
+            }
+        }
+    };
+    (@one $a:ident / $b:ident = $c:ident) => {
+        impl<N: Scalar> Div<Measure<dim::$b, N>> for Measure<dim::$a, N> {
+            type Output = Measure<dim::$c, N>;
+
+            fn div(self, other: Measure<dim::$b, N>) -> Measure<dim::$c, N> {
+                Measure::from_base(self.base / other.base)
+            }
+        }
+    };
+}
+
+relations! {
+    Length * Length = Area;
+    Area / Length = Length;
+    Area * Length = Volume;
+    Length * Area = Volume;
+    Volume / Length = Area;
+    Volume / Area = Length;
+    Length / Time = Speed;
+    Speed * Time = Length;
+    Time * Speed = Length;
+    Length / Speed = Time;
+    Force * Length = Energy;
+    Length * Force = Energy;
+    Energy / Length = Force;
+    Energy / Force = Length;
+    Force / Area = Pressure;
+    Pressure * Area = Force;
+    Area * Pressure = Force;
+    Force / Pressure = Area;
+    Pressure * Volume = Energy;
+    Volume * Pressure = Energy;
+    Energy / Volume = Pressure;
+    Energy / Pressure = Volume;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn values_convert_through_the_base_unit() {
+        let mile = Length::new(1.0, enums::Length::Mile);
+        assert_eq!(mile.base(), 1609.344);
+        assert!(close(mile.get(enums::Length::Foot), 5280.0));
+        let body = Temperature::new(37.0, enums::Temperature::Celsius);
+        assert!(close(body.get(enums::Temperature::Fahrenheit), 98.6));
+        assert_eq!(Length::new(2.0f32, enums::Length::Kilometer).get(enums::Length::Meter), 2000.0f32);
+    }
+
+    #[test]
+    fn arithmetic_keeps_dimensions() {
+        let distance = Length::new(100.0, enums::Length::Mile);
+        let time = Time::new(2.0, enums::Time::Hour);
+        let speed: Speed = distance / time;
+        assert!(close(speed.get(enums::Speed::MilePerHour), 50.0));
+        let side = Length::new(3.0, enums::Length::Meter);
+        let area: Area = side * side;
+        assert!(close((area * side / area).get(enums::Length::Meter), 3.0));
+        assert!(close((side + side - side * 0.5).get(enums::Length::Meter), 4.5));
+        assert_eq!(2.0 * side, side * 2.0);
+        assert_eq!(side / Length::new(1.0, enums::Length::Meter), 3.0);
+        assert!(-side < side);
+    }
+
+    #[test]
+    fn temperatures_differ_rather_than_add() {
+        use enums::Temperature::{Celsius, Fahrenheit, Kelvin};
+        let room = Temperature::new(20.0, Celsius);
+        let rise = TemperatureDifference::new(10.0, Celsius);
+        assert!(close((room + rise).get(Celsius), 30.0));
+        assert!(close((room - rise).get(Celsius), 10.0));
+        assert!(close(rise.get(Fahrenheit), 18.0));
+        let gap = Temperature::new(212.0, Fahrenheit) - Temperature::new(32.0, Fahrenheit);
+        assert!(close(gap.get(Celsius), 100.0));
+        assert!(close((gap + rise).get(Kelvin), 110.0));
+    }
+
+    #[test]
+    fn quantities_bridge_by_dimension() {
+        let quantity = Quantity::new(12.0, Unit::Length(enums::Length::Inch));
+        let length = Length::from_quantity(&quantity).unwrap();
+        assert!(close(length.get(enums::Length::Foot), 1.0));
+        assert_eq!(Mass::from_quantity(&quantity), None);
+        let freezing = Quantity::new(0.0, Unit::Temperature(enums::Temperature::Celsius));
+        assert!(close(Temperature::from_quantity(&freezing).unwrap().base(), 273.15));
+        assert_eq!(TemperatureDifference::from_quantity(&freezing).unwrap().base(), 0.0);
+        let degree = TemperatureDifference::new(1.0, enums::Temperature::Kelvin).to_quantity(enums::Temperature::Fahrenheit);
+        assert!(close(degree.value, 1.8));
+    }
+}
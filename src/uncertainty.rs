@@ -0,0 +1,117 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// A value with its standard uncertainty, combined by first-order (GUM) propagation:
+// the uncertainty of f(a, b) is sqrt((df/da * u(a))^2 + (df/db * u(b))^2). Operands
+// are taken to be uncorrelated.
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Uncertain {
+    pub value: f64,
+    pub uncertainty: f64, // Standard uncertainty, never negative
+}
+
+impl Uncertain {
+    pub fn new(value: f64, uncertainty: f64) -> Uncertain {
+        Uncertain { value, uncertainty: uncertainty.abs() }
+    }
+
+    pub fn exact(value: f64) -> Uncertain {
+        Uncertain::new(value, 0.0)
+    }
+
+    // value * factor + offset; the offset is exact and leaves the uncertainty alone.
+    pub fn affine(self, factor: f64, offset: f64) -> Uncertain {
+        Uncertain::new(self.value * factor + offset, self.uncertainty * factor)
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.uncertainty == 0.0
+    }
+}
+
+impl Add for Uncertain {
+    type Output = Uncertain;
+
+    fn add(self, other: Uncertain) -> Uncertain {
+        Uncertain::new(self.value + other.value, self.uncertainty.hypot(other.uncertainty))
+    }
+}
+
+impl Sub for Uncertain {
+    type Output = Uncertain;
+
+    fn sub(self, other: Uncertain) -> Uncertain {
+        self + -other
+    }
+}
+
+impl Neg for Uncertain {
+    type Output = Uncertain;
+
+    fn neg(self) -> Uncertain {
+        Uncertain::new(-self.value, self.uncertainty)
+    }
+}
+
+impl Mul for Uncertain {
+    type Output = Uncertain;
+
+    fn mul(self, other: Uncertain) -> Uncertain {
+        let uncertainty = (other.value * self.uncertainty).hypot(self.value * other.uncertainty);
+        Uncertain::new(self.value * other.value, uncertainty)
+    }
+}
+
+impl Div for Uncertain {
+    type Output = Uncertain;
+
+    fn div(self, other: Uncertain) -> Uncertain {
+        let value = self.value / other.value;
+        let uncertainty = (self.uncertainty / other.value).hypot(value * other.uncertainty / other.value);
+        Uncertain::new(value, uncertainty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Quantity;
+    use crate::enums::{Length, Temperature, Unit};
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn sums_add_in_quadrature() {
+        let sum = Uncertain::new(10.0, 3.0) + Uncertain::new(5.0, 4.0);
+        assert_eq!(sum, Uncertain::new(15.0, 5.0));
+        let difference = Uncertain::new(10.0, 3.0) - Uncertain::new(5.0, 4.0);
+        assert_eq!(difference, Uncertain::new(5.0, 5.0));
+        assert_eq!(-Uncertain::new(2.0, 0.5), Uncertain::new(-2.0, 0.5));
+        assert!((Uncertain::exact(1.0) + Uncertain::exact(2.0)).is_exact());
+    }
+
+    #[test]
+    fn products_add_relative_uncertainties_in_quadrature() {
+        // 3% and 4% relative give 5%.
+        let product = Uncertain::new(100.0, 3.0) * Uncertain::new(2.0, 0.08);
+        assert!(close(product.value, 200.0) && close(product.uncertainty, 10.0));
+        let quotient = Uncertain::new(100.0, 3.0) / Uncertain::new(2.0, 0.08);
+        assert!(close(quotient.value, 50.0) && close(quotient.uncertainty, 2.5));
+        let scaled = Uncertain::new(2.0, 0.1) * Uncertain::exact(-3.0);
+        assert_eq!(scaled, Uncertain::new(-6.0, 0.30000000000000004));
+    }
+
+    #[test]
+    fn offsets_leave_the_uncertainty_alone() {
+        assert_eq!(Uncertain::new(-1.0, 0.5).affine(-2.0, 10.0), Uncertain::new(12.0, 1.0));
+        assert_eq!(Uncertain::new(1.0, -0.5).uncertainty, 0.5);
+        let body = Quantity::new(37.0, Unit::Temperature(Temperature::Celsius)).with_uncertainty(0.5);
+        let fahrenheit = body.convert_to(&Unit::Temperature(Temperature::Fahrenheit)).unwrap();
+        assert!(close(fahrenheit.uncertainty.unwrap(), 0.9));
+        let foot = Quantity::new(1.0, Unit::Length(Length::Foot)).with_uncertainty(-0.01);
+        let inches = foot.convert_to(&Unit::Length(Length::Inch)).unwrap();
+        assert!(close(inches.value, 12.0) && close(inches.uncertainty.unwrap(), 0.12));
+    }
+}
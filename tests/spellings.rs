@@ -0,0 +1,369 @@
+use un::enums::*;
+use un::registry::{LookupError, UnitRegistry};
+
+// The spellings the original lowercasing lookup accepted, which must still find the
+// same unit now that symbols are matched case-sensitively first. This is not all of
+// them: lowercased symbols of two units that differ only in case ("ml", "ma", "mhz",
+// "mn", "mv", "mf") used to name one of the two, and are now refused as ambiguous
+// (see `case_collisions_are_ambiguous`), so `un 500 ml cup` has to be spelt "mL".
+fn baseline() -> Vec<(Unit, &'static [&'static str])> {
+    vec![
+        (Unit::Length(Length::Kilometer), &["kilometer", "kilometers", "km"]),
+        (Unit::Length(Length::Hectometer), &["hectometer", "hectometers", "hm"]),
+        (Unit::Length(Length::Decameter), &["decameter", "decameters", "decam", "dekameter", "dekameters", "dam"]),
+        (Unit::Length(Length::Meter), &["meter", "meters", "m"]),
+        (Unit::Length(Length::Decimeter), &["decimeter", "decimeters", "decim", "dm"]),
+        (Unit::Length(Length::Centimeter), &["centimeter", "centimeters", "cm"]),
+        (Unit::Length(Length::Millimeter), &["millimeter", "millimeters", "mm"]),
+        (Unit::Length(Length::Micron), &["micrometer", "micrometers", "micron", "microns", "µm"]),
+        (Unit::Length(Length::Nanometer), &["nanometer", "nanometers", "nm"]),
+        (Unit::Length(Length::Picometer), &["picometer", "picometers", "pm"]),
+        (Unit::Length(Length::Femtometer), &["femtometer", "femtometers", "fm"]),
+        (Unit::Length(Length::Attometer), &["attometer", "attometers", "am"]),
+        (Unit::Length(Length::LightYear), &["light_year", "light_years", "lightyear", "lightyears", "ly"]),
+        (Unit::Length(Length::AstronomicalUnit), &["astronomical_unit", "astronomical_units", "astronomicalunit", "astronomicalunits", "au"]),
+        (Unit::Length(Length::Parsec), &["parsec", "parsecs", "pc"]),
+        (Unit::Length(Length::Angstrom), &["angstrom", "angstroms", "å"]),
+        (Unit::Length(Length::Inch), &["inch", "inches", "in"]),
+        (Unit::Length(Length::Foot), &["foot", "feet", "ft"]),
+        (Unit::Length(Length::Yard), &["yard", "yards", "yd"]),
+        (Unit::Length(Length::Mile), &["mile", "miles", "mi"]),
+        (Unit::Length(Length::NauticalMile), &["nautical_mile", "nautical_miles", "nauticalmile", "nauticalmiles", "nmi"]),
+        (Unit::Length(Length::League), &["league", "leagues"]),
+        (Unit::Length(Length::Furlong), &["furlong", "furlongs"]),
+        (Unit::Length(Length::Rod), &["rod", "rods"]),
+        (Unit::Length(Length::Chain), &["chain", "chains"]),
+        (Unit::Length(Length::FootballFieldLength), &["football_field", "football_fields", "football_field_length", "football_field_lengths"]),
+        (Unit::Length(Length::WhaleLength), &["whale", "whales", "whale_length", "whale_lengths"]),
+        (Unit::Mass(Mass::Kilogram), &["kilogram", "kilograms", "kg"]),
+        (Unit::Mass(Mass::Hectogram), &["hectogram", "hectograms", "hg"]),
+        (Unit::Mass(Mass::Decagram), &["decagram", "decagrams", "dag"]),
+        (Unit::Mass(Mass::Gram), &["gram", "grams", "g"]),
+        (Unit::Mass(Mass::Decigram), &["decigram", "decigrams", "dg"]),
+        (Unit::Mass(Mass::Centigram), &["centigram", "centigrams", "cg"]),
+        (Unit::Mass(Mass::Milligram), &["milligram", "milligrams", "mg"]),
+        (Unit::Mass(Mass::Microgram), &["microgram", "mcg", "µg"]),
+        (Unit::Mass(Mass::Nanogram), &["nanogram", "nanograms", "ng"]),
+        (Unit::Mass(Mass::Picogram), &["picogram", "picograms", "pg"]),
+        (Unit::Mass(Mass::Femtogram), &["femtogram", "femtograms", "fg"]),
+        (Unit::Mass(Mass::Attogram), &["attogram", "attograms", "ag"]),
+        (Unit::Mass(Mass::Ton), &["ton", "tons"]),
+        (Unit::Mass(Mass::MetricTon), &["metric_ton", "metric_tons", "tonne", "tonnes"]),
+        (Unit::Mass(Mass::Quintal), &["quintal", "quintals", "centner", "centners"]),
+        (Unit::Mass(Mass::Pounds), &["pound", "pounds", "lb", "lbs"]),
+        (Unit::Mass(Mass::Ounce), &["ounce", "ounces", "oz"]),
+        (Unit::Mass(Mass::Stone), &["stone", "stones"]),
+        (Unit::Mass(Mass::Carat), &["carat", "carats", "ct"]),
+        (Unit::Area(Area::SquareMeter), &["square_meter", "square_meters", "sqm", "m2"]),
+        (Unit::Area(Area::SquareKilometer), &["square_kilometer", "square_kilometers", "sqkm", "km2"]),
+        (Unit::Area(Area::SquareCentimeter), &["square_centimeter", "square_centimeters", "sqcm", "cm2"]),
+        (Unit::Area(Area::SquareMillimeter), &["square_millimeter", "square_millimeters", "sqmm", "mm2"]),
+        (Unit::Area(Area::SquareDecimeter), &["square_decimeter", "square_decimeters", "sqdm", "dm2"]),
+        (Unit::Area(Area::Hectare), &["hectare", "hectares", "ha"]),
+        (Unit::Area(Area::Acre), &["acre", "acres"]),
+        (Unit::Area(Area::SquareFoot), &["square_foot", "square_feet", "sqft", "ft2"]),
+        (Unit::Area(Area::SquareInch), &["square_inch", "square_inches", "sqin", "in2"]),
+        (Unit::Area(Area::SquareYard), &["square_yard", "square_yards", "sqyd", "yd2"]),
+        (Unit::Area(Area::SquareMile), &["square_mile", "square_miles", "sqmi", "mi2"]),
+        (Unit::Volume(Volume::CubicMeter), &["cubic_meter", "cubic_meters", "cum", "m3"]),
+        (Unit::Volume(Volume::CubicDecimeter), &["cubic_decimeter", "cubic_decimeters", "cudm", "dm3"]),
+        (Unit::Volume(Volume::CubicCentimeter), &["cubic_centimeter", "cubic_centimeters", "cucc", "cc", "cm3"]),
+        (Unit::Volume(Volume::CubicMillimeter), &["cubic_millimeter", "cubic_millimeters", "cumm", "mm3"]),
+        (Unit::Volume(Volume::Liter), &["liter", "liters", "l"]),
+        (Unit::Volume(Volume::Milliliter), &["milliliter", "milliliters", "mL"]),
+        (Unit::Volume(Volume::Deciliter), &["deciliter", "deciliters", "dl"]),
+        (Unit::Volume(Volume::Kiloliter), &["kiloliter", "kiloliters", "kl"]),
+        (Unit::Volume(Volume::Megaliter), &["megaliter", "megaliters"]),
+        (Unit::Volume(Volume::Gigaliter), &["gigaliter", "gigaliters", "gl"]),
+        (Unit::Volume(Volume::Teraliter), &["teraliter", "teraliters", "tl"]),
+        (Unit::Volume(Volume::CubicInch), &["cubic_inch", "cubic_inches", "cuin", "in3"]),
+        (Unit::Volume(Volume::CubicFoot), &["cubic_foot", "cubic_feet", "cuft", "ft3"]),
+        (Unit::Volume(Volume::CubicYard), &["cubic_yard", "cubic_yards", "cuyd", "yd3"]),
+        (Unit::Volume(Volume::CubicMile), &["cubic_mile", "cubic_miles", "cumi", "mi3"]),
+        (Unit::Volume(Volume::TeracubicFeet), &["teracubic_feet", "tcf"]),
+        (Unit::Volume(Volume::FluidOunce), &["fluid_ounce", "fluid_ounces", "floz"]),
+        (Unit::Volume(Volume::Pint), &["pint", "pints", "pt"]),
+        (Unit::Volume(Volume::Quart), &["quart", "quarts", "qt"]),
+        (Unit::Volume(Volume::Gallon), &["gallon", "gallons", "gal"]),
+        (Unit::Volume(Volume::BarrelLiquid), &["barrel_liquid", "barrel_liquids", "bbl"]),
+        (Unit::Volume(Volume::Bushel), &["bushel", "bushels", "bu"]),
+        (Unit::Volume(Volume::Peck), &["peck", "pecks"]),
+        (Unit::Volume(Volume::Cord), &["cord", "cords"]),
+        (Unit::Volume(Volume::Teaspoon), &["teaspoon", "teaspoons", "tsp"]),
+        (Unit::Volume(Volume::Tablespoon), &["tablespoon", "tablespoons", "tbsp"]),
+        (Unit::Volume(Volume::Cup), &["cup", "cups"]),
+        (Unit::Volume(Volume::AcreFoot), &["acre_foot", "acre_feet", "acft"]),
+        (Unit::Volume(Volume::StandardCubicFoot), &["standard_cubic_foot", "standard_cubic_feet", "scf"]),
+        (Unit::Force(Force::Newton), &["newton", "newtons", "n"]),
+        (Unit::Force(Force::Millinewton), &["millinewton", "millinewtons"]),
+        (Unit::Force(Force::Kilonewton), &["kilonewton", "kilonewtons", "kn"]),
+        (Unit::Force(Force::Meganewton), &["meganewton", "meganewtons"]),
+        (Unit::Force(Force::Dyne), &["dyne", "dynes", "dyn"]),
+        (Unit::Force(Force::KilogramForce), &["kilogram_force", "kilogram_forces", "kilopond", "kiloponds", "kgf", "kp"]),
+        (Unit::Force(Force::GramForce), &["gram_force", "gram_forces", "gf"]),
+        (Unit::Force(Force::PoundForce), &["pound_force", "pound_forces", "lbf"]),
+        (Unit::Force(Force::OunceForce), &["ounce_force", "ounce_forces", "ozf"]),
+        (Unit::Force(Force::TonForce), &["ton_force", "ton_forces"]),
+        (Unit::Force(Force::Poundal), &["poundal", "poundals", "pdl"]),
+        (Unit::Force(Force::Kip), &["kip", "kips"]),
+        (Unit::Force(Force::Sthene), &["sthene", "sthenes"]),
+        (Unit::Force(Force::JoulePerMeter), &["joule_per_meter", "joules_per_meter", "j/m"]),
+        (Unit::Energy(Energy::Joule), &["joule", "joules", "j"]),
+        (Unit::Energy(Energy::Calorie), &["calorie", "calories", "cal"]),
+        (Unit::Energy(Energy::Kilocalorie), &["kilocalorie", "kilocalories", "kcal", "cal_(nutrition)"]),
+        (Unit::Energy(Energy::KilowattHour), &["kilowatt_hour", "kilowatt_hours", "kwh"]),
+        (Unit::Energy(Energy::WattHour), &["watt_hour", "watt_hours", "wh"]),
+        (Unit::Energy(Energy::Electronvolt), &["electronvolt", "electronvolts", "ev"]),
+        (Unit::Energy(Energy::Hartree), &["hartree", "hartrees"]),
+        (Unit::Energy(Energy::BritishThermalUnit), &["british_thermal_unit", "british_thermal_units", "btu"]),
+        (Unit::Energy(Energy::FootPound), &["foot_pound", "foot_pounds", "foot_pound_force", "foot_pound_forces"]),
+        (Unit::Energy(Energy::Erg), &["erg", "ergs"]),
+        (Unit::Energy(Energy::Therm), &["therm", "therms"]),
+        (Unit::Energy(Energy::TonOfTntEquivalent), &["ton_of_tnt_equivalent", "tons_of_tnt_equivalent"]),
+        (Unit::Energy(Energy::HorsepowerHour), &["horsepower_hour", "horsepower_hours"]),
+        (Unit::Energy(Energy::BarrelOfOilEquivalent), &["barrel_of_oil_equivalent", "barrels_of_oil_equivalent", "boe"]),
+        (Unit::Temperature(Temperature::Celsius), &["celsius", "°c"]),
+        (Unit::Temperature(Temperature::Fahrenheit), &["fahrenheit", "°f"]),
+        (Unit::Temperature(Temperature::Kelvin), &["kelvin", "k"]),
+        (Unit::Temperature(Temperature::Rankine), &["rankine", "rankines", "°r"]),
+        (Unit::Temperature(Temperature::Reaumur), &["reaumur", "reaumurs", "°re"]),
+        (Unit::Temperature(Temperature::Delisle), &["delisle", "delisles"]),
+        (Unit::Temperature(Temperature::NewtonScale), &["newton_scale", "newton_scales", "°n"]),
+        (Unit::Temperature(Temperature::Romer), &["romer", "romers", "°ro"]),
+        (Unit::Temperature(Temperature::Leiden), &["leiden", "leidens"]),
+        (Unit::Temperature(Temperature::PlanckTemperature), &["planck_temperature", "planck_temperatures"]),
+        (Unit::Time(Time::Second), &["second", "seconds", "s", "sec"]),
+        (Unit::Time(Time::Minute), &["minute", "minutes", "min"]),
+        (Unit::Time(Time::Hour), &["hour", "hours", "hr"]),
+        (Unit::Time(Time::Day), &["day", "days"]),
+        (Unit::Time(Time::Week), &["week", "weeks", "wk"]),
+        (Unit::Time(Time::Month), &["month", "months"]),
+        (Unit::Time(Time::GregorianYear), &["year", "years", "yr"]), // Was Time::Year, now spelt calendar_year
+        (Unit::Time(Time::Decade), &["decade", "decades"]),
+        (Unit::Time(Time::Century), &["century", "centuries"]),
+        (Unit::Time(Time::Millennium), &["millennium", "millennia"]),
+        (Unit::Time(Time::Microsecond), &["microsecond", "microseconds", "µs"]),
+        (Unit::Time(Time::Millisecond), &["millisecond", "milliseconds", "ms"]),
+        (Unit::Time(Time::Nanosecond), &["nanosecond", "nanoseconds", "ns"]),
+        (Unit::Time(Time::Picosecond), &["picosecond", "picoseconds", "ps"]),
+        (Unit::Time(Time::Fortnight), &["fortnight", "fortnights"]),
+        (Unit::Time(Time::Shake), &["shake", "shakes"]),
+        (Unit::Time(Time::JulianYear), &["julian_year", "julian_years"]),
+        (Unit::Time(Time::LeapYear), &["leap_year", "leap_years"]),
+        (Unit::Time(Time::SiderealDay), &["sidereal_day", "sidereal_days"]),
+        (Unit::Time(Time::PlanckTime), &["planck_time", "planck_times"]),
+        (Unit::Speed(Speed::MeterPerSecond), &["meter_per_second", "meters_per_second", "m/s"]),
+        (Unit::Speed(Speed::KilometerPerHour), &["kilometer_per_hour", "kilometers_per_hour", "km/h", "kph"]),
+        (Unit::Speed(Speed::MilePerHour), &["mile_per_hour", "miles_per_hour", "mph"]),
+        (Unit::Speed(Speed::Knot), &["knot", "knots", "kt"]),
+        (Unit::Speed(Speed::FootPerSecond), &["foot_per_second", "feet_per_second", "ft/s"]),
+        (Unit::Speed(Speed::Mach), &["mach", "machs"]),
+        (Unit::Speed(Speed::SpeedOfLight), &["speed_of_light", "c"]),
+        (Unit::Speed(Speed::InchPerSecond), &["inch_per_second", "inches_per_second", "in/s"]),
+        (Unit::Speed(Speed::FurlongPerFortnight), &["furlong_per_fortnight", "furlongs_per_fortnight"]),
+        (Unit::Speed(Speed::BeaufortScale), &["beaufort_scale", "beaufort_scales"]),
+        (Unit::Speed(Speed::CosmicVelocity), &["cosmic_velocity", "cosmic_velocities"]),
+        (Unit::Speed(Speed::GalileoUnit), &["galileo_unit", "galileo_units"]),
+        (Unit::Speed(Speed::Benz), &["benz", "benzs"]),
+        (Unit::Speed(Speed::YardPerSecond), &["yard_per_second", "yards_per_second", "yd/s"]),
+        (Unit::Speed(Speed::EarthsRotationSpeed), &["earth's_rotation_speed"]),
+        (Unit::Speed(Speed::ParsecPerYear), &["parsec_per_year", "parsecs_per_year"]),
+        (Unit::Speed(Speed::SpeedOfLightC), &["speed_of_light_c"]),
+        (Unit::Speed(Speed::MilePerMinute), &["mile_per_minute", "miles_per_minute", "mpm"]),
+        (Unit::Speed(Speed::KilometerPerSecond), &["kilometer_per_second", "kilometers_per_second", "km/s"]),
+        (Unit::Pressure(Pressure::Pascal), &["pascal", "pascals"]),
+        (Unit::Pressure(Pressure::Decipascal), &["decipascal", "decipascals", "dpa"]),
+        (Unit::Pressure(Pressure::Hectopascal), &["hectopascal", "hectopascals", "hpa"]),
+        (Unit::Pressure(Pressure::Kilopascal), &["kilopascal", "kilopascals", "kpa"]),
+        (Unit::Pressure(Pressure::Megapascal), &["megapascal", "megapascals", "mpa"]),
+        (Unit::Pressure(Pressure::PlanckPressure), &["planck_pressure", "planck_pressures"]),
+        (Unit::Pressure(Pressure::Bar), &["bar", "bars"]),
+        (Unit::Pressure(Pressure::AtmosphereStandard), &["atmosphere_standard", "atmosphere_standards", "atm"]),
+        (Unit::Pressure(Pressure::Torr), &["torr", "torrs"]),
+        (Unit::Pressure(Pressure::PoundPerSquareInch), &["psi", "pound_per_square_inch", "pounds_per_square_inch"]),
+        (Unit::Pressure(Pressure::KipPerSquareInch), &["ksi", "kip_per_square_inch", "kips_per_square_inch"]),
+        (Unit::Pressure(Pressure::MillimeterOfMercury), &["millimeter_of_mercury", "millimeters_of_mercury", "mmhg"]),
+        (Unit::Pressure(Pressure::InchOfMercury), &["inch_of_mercury", "inches_of_mercury", "inhg"]),
+        (Unit::Pressure(Pressure::InchOfWater), &["inch_of_water", "inches_of_water", "inwc"]),
+        (Unit::Pressure(Pressure::FootOfWater), &["foot_of_water", "feet_of_water", "ftwc"]),
+        (Unit::Pressure(Pressure::DynePerSquareCentimeter), &["dyne_per_square_centimeter", "dynes_per_square_centimeter"]),
+        (Unit::Pressure(Pressure::Barye), &["barye", "baryes", "ba"]),
+        (Unit::Pressure(Pressure::TechnicalAtmosphere), &["technical_atmosphere", "technical_atmospheres", "at"]),
+        (Unit::Pressure(Pressure::PoundalPerSquareFoot), &["poundal_per_square_foot", "poundals_per_square_foot"]),
+        (Unit::Pressure(Pressure::SthenePerSquareMeter), &["sthene_per_square_meter", "sthenes_per_square_meter"]),
+        (Unit::Frequency(Frequency::Hertz), &["hertz", "hertzs", "hz"]),
+        (Unit::Frequency(Frequency::Millihertz), &["millihertz", "millihertzs"]),
+        (Unit::Frequency(Frequency::Microhertz), &["microhertz", "microhertzs", "µhz"]),
+        (Unit::Frequency(Frequency::Nanohertz), &["nanohertz", "nanohertzs", "nhz"]),
+        (Unit::Frequency(Frequency::Kilohertz), &["kilohertz", "kilohertzs", "khz"]),
+        (Unit::Frequency(Frequency::Megahertz), &["megahertz", "megahertzs"]),
+        (Unit::Frequency(Frequency::Gigahertz), &["gigahertz", "gigahertzs", "ghz"]),
+        (Unit::Frequency(Frequency::Terahertz), &["terahertz", "terahertzs", "thz"]),
+        (Unit::Frequency(Frequency::PlanckFrequency), &["planck_frequency", "planck_frequencies"]),
+        (Unit::Frequency(Frequency::RevolutionsPerMinute), &["revolutions_per_minute", "rpm"]),
+        (Unit::Frequency(Frequency::RadianPerSecond), &["radian_per_second", "radians_per_second", "rad/s"]),
+        (Unit::Frequency(Frequency::CyclesPerSecond), &["cycles_per_second", "cps"]),
+        (Unit::Frequency(Frequency::FramesPerSecond), &["frames_per_second", "fps"]),
+        (Unit::Frequency(Frequency::BeatsPerMinute), &["beats_per_minute", "bpm"]),
+        (Unit::Frequency(Frequency::CountsPerMinute), &["counts_per_minute", "cpm"]),
+        (Unit::Frequency(Frequency::Fresnel), &["fresnel", "fresnels"]),
+        (Unit::Frequency(Frequency::ReciprocalSecond), &["reciprocal_second", "reciprocal_seconds", "1/s"]),
+        (Unit::Frequency(Frequency::DegreePerSecond), &["degree_per_second", "degrees_per_second", "deg/s"]),
+        (Unit::Frequency(Frequency::GradianPerSecond), &["gradian_per_second", "gradians_per_second", "grad/s"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Ampere), &["ampere", "amperes", "amp", "amps"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Picoampere), &["picoampere", "picoamperes"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Nanoampere), &["nanoampere", "nanoamperes", "na"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Microampere), &["microampere", "microamperes", "µa"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Milliampere), &["milliampere", "milliamperes"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Kiloampere), &["kiloampere", "kiloamperes", "ka"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Megaampere), &["megaampere", "megaamperes"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Teraampere), &["teraampere", "teraamperes", "ta"]),
+        (Unit::ElectricCurrent(ElectricCurrent::PlanckCurrent), &["planck_current", "planck_currents"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Statampere), &["statampere", "statamperes", "s_amp"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Abampere), &["abampere", "abamperes", "abamp"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Biot), &["biot", "biots", "bi"]),
+        (Unit::ElectricCurrent(ElectricCurrent::FranklinPerSecond), &["franklin_per_second", "franklins_per_second"]),
+        (Unit::ElectricCurrent(ElectricCurrent::CoulombPerSecond), &["coulomb_per_second", "coulombs_per_second", "c/s"]),
+        (Unit::ElectricCurrent(ElectricCurrent::VoltPerOhm), &["volt_per_ohm", "volts_per_ohm", "v/ω"]),
+        (Unit::ElectricCurrent(ElectricCurrent::WattPerVolt), &["watt_per_volt", "watts_per_volt", "w/v"]),
+        (Unit::ElectricCurrent(ElectricCurrent::SiemensVolt), &["siemens_volt", "siemens_volts"]),
+        (Unit::ElectricCurrent(ElectricCurrent::EsuPerSecond), &["esu_per_second", "esu_per_seconds"]),
+        (Unit::ElectricCurrent(ElectricCurrent::EmuOfCurrent), &["emu_of_current", "emu_of_currents"]),
+        (Unit::ElectricCurrent(ElectricCurrent::Gilbert), &["gilbert", "gilberts", "gb"]),
+        (Unit::Voltage(Voltage::Volt), &["volt", "volts", "v"]),
+        (Unit::Voltage(Voltage::Picovolt), &["picovolt", "picovolts", "pv"]),
+        (Unit::Voltage(Voltage::Nanovolt), &["nanovolt", "nanovolts", "nv"]),
+        (Unit::Voltage(Voltage::Microvolt), &["microvolt", "microvolts", "µv"]),
+        (Unit::Voltage(Voltage::Millivolt), &["millivolt", "millivolts"]),
+        (Unit::Voltage(Voltage::Decivolt), &["decivolt", "decivolts", "dv"]),
+        (Unit::Voltage(Voltage::Centivolt), &["centivolt", "centivolts", "cv"]),
+        (Unit::Voltage(Voltage::Hectovolt), &["hectovolt", "hectovolts", "hv"]),
+        (Unit::Voltage(Voltage::Kilovolt), &["kilovolt", "kilovolts", "kv"]),
+        (Unit::Voltage(Voltage::Megavolt), &["megavolt", "megavolts"]),
+        (Unit::Voltage(Voltage::Gigavolt), &["gigavolt", "gigavolts", "gv"]),
+        (Unit::Voltage(Voltage::Teravolt), &["teravolt", "teravolts", "tv"]),
+        (Unit::Voltage(Voltage::PlanckVoltage), &["planck_voltage", "planck_voltages"]),
+        (Unit::Voltage(Voltage::Statvolt), &["statvolt", "statvolts", "s_volt"]),
+        (Unit::Voltage(Voltage::Abvolt), &["abvolt", "abvolts", "ab_v"]),
+        (Unit::Voltage(Voltage::EsuOfPotential), &["esu_of_potential", "esu_of_potentials"]),
+        (Unit::Voltage(Voltage::EmuOfPotential), &["emu_of_potential", "emu_of_potentials"]),
+        (Unit::Voltage(Voltage::WattPerAmpere), &["watt_per_ampere", "watts_per_ampere", "w/a"]),
+        (Unit::Voltage(Voltage::JoulePerCoulomb), &["joule_per_coulomb", "joules_per_coulomb", "j/c"]),
+        (Unit::Voltage(Voltage::ElectronvoltPerElementaryCharge), &["electronvolt_per_elementary_charge", "electronvolts_per_elementary_charge", "ev/e"]),
+        (Unit::Capacitance(Capacitance::Farad), &["farad", "farads", "f"]),
+        (Unit::Capacitance(Capacitance::Attofarad), &["attofarad", "attofarads", "af"]),
+        (Unit::Capacitance(Capacitance::Femtofarad), &["femtofarad", "femtofarads", "ff"]),
+        (Unit::Capacitance(Capacitance::Picofarad), &["picofarad", "picofarads", "pf"]),
+        (Unit::Capacitance(Capacitance::Nanofarad), &["nanofarad", "nanofarads", "nf"]),
+        (Unit::Capacitance(Capacitance::Microfarad), &["microfarad", "microfarads", "µf"]),
+        (Unit::Capacitance(Capacitance::Millifarad), &["millifarad", "millifarads"]),
+        (Unit::Capacitance(Capacitance::Decafarad), &["decafarad", "decafarads", "daf"]),
+        (Unit::Capacitance(Capacitance::Hectofarad), &["hectofarad", "hectofarads", "hf"]),
+        (Unit::Capacitance(Capacitance::Kilofarad), &["kilofarad", "kilofarads", "kf"]),
+        (Unit::Capacitance(Capacitance::Megafarad), &["megafarad", "megafarads"]),
+        (Unit::Capacitance(Capacitance::Terafarad), &["terafarad", "terafarads", "tf"]),
+        (Unit::Capacitance(Capacitance::Gigafarad), &["gigafarad", "gigafarads"]),
+        (Unit::Capacitance(Capacitance::PlanckCapacitance), &["planck_capacitance", "planck_capacitances"]),
+        (Unit::Capacitance(Capacitance::Statfarad), &["statfarad", "statfarads", "s_farad"]),
+        (Unit::Capacitance(Capacitance::Abfarad), &["abfarad", "abfarads", "abf"]),
+        (Unit::Capacitance(Capacitance::EsuOfCapacitance), &["esu_of_capacitance", "esu_of_capacitances"]),
+        (Unit::Capacitance(Capacitance::EmuOfCapacitance), &["emu_of_capacitance", "emu_of_capacitances"]),
+        (Unit::Capacitance(Capacitance::CoulombPerVolt), &["coulomb_per_volt", "coulombs_per_volt", "c/v"]),
+        (Unit::Capacitance(Capacitance::SecondPerOhm), &["second_per_ohm", "seconds_per_ohm", "s/ω"]),
+        (Unit::Luminosity(Luminosity::Candela), &["candela", "candelas", "cd"]),
+        (Unit::Luminosity(Luminosity::Lumen), &["lumen", "lumens", "lm"]),
+        (Unit::Luminosity(Luminosity::Lux), &["lux", "luxes", "lx"]),
+        (Unit::Luminosity(Luminosity::CandelaPerSquareMeter), &["candela_per_square_meter", "candelas_per_square_meter", "cd/m2"]),
+        (Unit::Luminosity(Luminosity::LumenPerSquareMeter), &["lumen_per_square_meter", "lumens_per_square_meter", "lm/m2"]),
+        (Unit::Luminosity(Luminosity::Nit), &["nit", "nits"]),
+        (Unit::Luminosity(Luminosity::Stilb), &["stilb", "stilbs", "sb"]),
+        (Unit::Luminosity(Luminosity::Apostilb), &["apostilb", "apostilbs", "asb"]),
+        (Unit::Luminosity(Luminosity::Lambert), &["lambert", "lamberts"]),
+        (Unit::Luminosity(Luminosity::FootLambert), &["foot_lambert", "foot_lamberts"]),
+        (Unit::Luminosity(Luminosity::Phot), &["phot", "phots", "ph"]),
+        (Unit::Luminosity(Luminosity::Bril), &["bril", "brils"]),
+        (Unit::Luminosity(Luminosity::Skot), &["skot", "skots"]),
+        (Unit::Luminosity(Luminosity::WattPerSteradian), &["watt_per_steradian", "watts_per_steradian", "w/sr"]),
+        (Unit::Luminosity(Luminosity::LumenPerWatt), &["lumen_per_watt", "lumens_per_watt", "lm/w"]),
+        (Unit::Luminosity(Luminosity::LumenSecond), &["lumen_second", "lumen_seconds", "lm_s"]),
+        (Unit::Luminosity(Luminosity::Talbot), &["talbot", "talbots"]),
+        (Unit::Luminosity(Luminosity::WattPerSteradianSquareMeter), &["watt_per_steradian_square_meter", "watts_per_steradian_square_meter", "w/(sr·m²)"]),
+        (Unit::Luminosity(Luminosity::Candlepower), &["candlepower", "candlepowers", "cp"]),
+        (Unit::Luminosity(Luminosity::PlanckLuminance), &["planck_luminance", "planck_luminances"]),
+        (Unit::Radiation(Radiation::Gray), &["gray", "grays", "gy"]),
+        (Unit::Radiation(Radiation::Decigray), &["decigray", "decigrays", "dgy"]),
+        (Unit::Radiation(Radiation::Centigray), &["centigray", "centigrays", "cgy"]),
+        (Unit::Radiation(Radiation::Milligray), &["milligray", "milligrays", "mgy"]),
+        (Unit::Radiation(Radiation::Microgray), &["microgray", "micrograys", "µgy"]),
+        (Unit::Radiation(Radiation::Nanogray), &["nanogray", "nanograys", "ngy"]),
+        (Unit::Radiation(Radiation::Picogray), &["picogray", "picograys", "pgy"]),
+        (Unit::Radiation(Radiation::Rad), &["rad", "rads"]),
+        (Unit::Radiation(Radiation::Sievert), &["sievert", "sieverts", "sv"]),
+        (Unit::Radiation(Radiation::Rem), &["rem", "rems"]),
+        (Unit::Radiation(Radiation::Becquerel), &["becquerel", "becquerels", "bq"]),
+        (Unit::Radiation(Radiation::Curie), &["curie", "curies", "ci"]),
+        (Unit::Radiation(Radiation::Rutherford), &["rutherford", "rutherfords"]),
+        (Unit::Radiation(Radiation::Roentgen), &["roentgen", "roentgens"]),
+        (Unit::Radiation(Radiation::CoulombPerKilogram), &["coulomb_per_kilogram", "coulombs_per_kilogram", "c/kg"]),
+        (Unit::Radiation(Radiation::ElectronvoltPerKilogram), &["electronvolt_per_kilogram", "electronvolts_per_kilogram", "ev/kg"]),
+        (Unit::Radiation(Radiation::IonPairPerKilogram), &["ion_pair_per_kilogram", "ion_pairs_per_kilogram"]),
+        (Unit::Radiation(Radiation::Rep), &["rep", "reps"]),
+        (Unit::Radiation(Radiation::WattPerKilogram), &["watt_per_kilogram", "watts_per_kilogram", "w/kg"]),
+        (Unit::Radiation(Radiation::PlanckRadiation), &["planck_radiation", "planck_radiations"]),
+        (Unit::MagneticFlux(MagneticFlux::Weber), &["weber", "webers", "wb"]),
+        (Unit::MagneticFlux(MagneticFlux::Microweber), &["microweber", "microwebers", "µwb"]),
+        (Unit::MagneticFlux(MagneticFlux::Nanoweber), &["nanoweber", "nanowebers", "nwb"]),
+        (Unit::MagneticFlux(MagneticFlux::Picoweber), &["picoweber", "picowebers", "pwb"]),
+        (Unit::MagneticFlux(MagneticFlux::Femtoweber), &["femtoweber", "femtowebers", "fwb"]),
+        (Unit::MagneticFlux(MagneticFlux::Attoweber), &["attoweber", "attowebers", "awb"]),
+        (Unit::MagneticFlux(MagneticFlux::Maxwell), &["maxwell", "maxwells", "mx"]),
+        (Unit::MagneticFlux(MagneticFlux::Megaline), &["megaline", "megalines"]),
+        (Unit::MagneticFlux(MagneticFlux::Kiloline), &["kiloline", "kilolines"]),
+        (Unit::MagneticFlux(MagneticFlux::Milliline), &["milliline", "millilines"]),
+        (Unit::MagneticFlux(MagneticFlux::GammaSquareCentimeter), &["gamma_square_centimeter", "gamma_square_centimeters"]),
+        (Unit::MagneticFlux(MagneticFlux::TeslaSquareMeter), &["tesla_square_meter", "tesla_square_meters", "t_m2"]),
+        (Unit::MagneticFlux(MagneticFlux::GaussSquareCentimeter), &["gauss_square_centimeter", "gauss_square_centimeters", "g_cm2"]),
+        (Unit::MagneticFlux(MagneticFlux::VoltSecond), &["volt_second", "volt_seconds", "v_s"]),
+        (Unit::MagneticFlux(MagneticFlux::CoulombOhm), &["coulomb_ohm", "coulomb_ohms", "c_ω"]),
+        (Unit::MagneticFlux(MagneticFlux::JoulePerAmpere), &["joule_per_ampere", "joules_per_ampere", "j/a"]),
+        (Unit::MagneticFlux(MagneticFlux::HenryAmpere), &["henry_ampere", "henry_amperes", "h_a"]),
+        (Unit::MagneticFlux(MagneticFlux::MagneticFluxQuantum), &["magnetic_flux_quantum", "magnetic_flux_quantums", "φ0"]),
+        (Unit::MagneticFlux(MagneticFlux::UnitPole), &["unit_pole", "unit_poles"]),
+        (Unit::MagneticFlux(MagneticFlux::PlanckFlux), &["planck_flux", "planck_fluxes"]),
+    ]
+}
+
+#[test]
+fn baseline_spellings_still_resolve() {
+    let registry = UnitRegistry::builtin();
+    for (unit, spellings) in baseline() {
+        for spelling in spellings {
+            assert_eq!(registry.resolve(spelling), Ok(unit.clone()), "{:?}", spelling);
+            assert_eq!(registry.lookup(spelling), Some(unit.clone()), "{:?}", spelling);
+        }
+    }
+}
+
+// Lowercased, these name two units that differ only in case.
+#[test]
+fn case_collisions_are_ambiguous() {
+    let registry = UnitRegistry::builtin();
+    for spelling in ["ma", "mf", "mhz", "ml", "mn", "mv", "pa"] {
+        assert!(matches!(registry.resolve(spelling), Err(LookupError::Ambiguous { .. })), "{:?}", spelling);
+        assert_eq!(registry.lookup(spelling), None, "{:?}", spelling);
+    }
+    assert_eq!(registry.resolve("mA"), Ok(Unit::ElectricCurrent(ElectricCurrent::Milliampere)));
+    assert_eq!(registry.resolve("MA"), Ok(Unit::ElectricCurrent(ElectricCurrent::Megaampere)));
+}
+
+// Common words are never taken for a unit symbol, and the aliases that were dropped
+// for clashing with one ("a", "de", "re", "ro") stay unknown.
+#[test]
+fn words_are_not_units() {
+    let registry = UnitRegistry::builtin();
+    for spelling in ["a", "r", "rd", "fl", "de", "re", "ro", "AM", "PH"] {
+        assert!(matches!(registry.resolve(spelling), Err(LookupError::Unknown { .. })), "{:?}", spelling);
+    }
+    assert_eq!(registry.resolve("R"), Ok(Unit::Radiation(Radiation::Roentgen)));
+    assert_eq!(registry.resolve("am"), Ok(Unit::Length(Length::Attometer)));
+}